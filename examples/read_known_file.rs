@@ -2,11 +2,13 @@ use std::{error::Error, fs::File, io::{Cursor, Read}, path::Path};
 use serde::Deserialize;
 use serde_gvas::{header, types::{GvasHeader}};
 
+#[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 struct TestStruct {
     test: i32
 }
 
+#[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 struct UnrealFile {
     u8_test: u8,
@@ -0,0 +1,236 @@
+//! Derives `Serialize` and `Deserialize` for structs stored in GVAS saves, with `#[gvas(...)]`
+//! attributes saying what property types the fields are stored as.
+//!
+//! On the struct:
+//! - `struct_name = "InventoryItem"` is the UE struct name written for it, defaults to the Rust name
+//!
+//! On fields:
+//! - `rename = "ItemId"` is the property name, defaults to the field name
+//! - `property = "NameProperty"` is the property type, for a sequence the type of its elements
+//! - `enum_name = "EItemKind"` is the enum named in the tag of an `EnumProperty` or `ByteProperty`
+//! - `struct_name = "InventoryItem"` is the UE struct name, overriding the one of the field's type
+//! - `default` uses `Default::default()` when the property is missing
+//!
+//! An empty `Vec` is written with the element type of its hint. `Vec`s of numbers, bools and
+//! strings get one from their Rust type, struct arrays that can be empty need a `struct_name`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, LitStr, Type};
+
+const PROPERTY_TYPES: &[&str] = &["BoolProperty", "Int8Property", "ByteProperty", "Int16Property", "UInt16Property", "IntProperty",
+    "UInt32Property", "Int64Property", "UInt64Property", "FloatProperty", "DoubleProperty", "StrProperty", "NameProperty",
+    "ObjectProperty", "SoftObjectProperty", "EnumProperty", "StructProperty", "ArrayProperty"];
+
+#[proc_macro_derive(GvasSerialize, attributes(gvas))]
+pub fn derive_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    parse(&input).map(|parsed| serialize(&input.ident, &parsed)).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[proc_macro_derive(GvasDeserialize, attributes(gvas))]
+pub fn derive_deserialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    parse(&input).map(|parsed| deserialize(&input.ident, &parsed)).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+struct Struct {
+    struct_name: String,
+    fields: Vec<Field>
+}
+
+struct Field {
+    ident: Ident,
+    ty: Type,
+    name: String,
+    /// Name of the newtype struct the value is wrapped in, see `serde_gvas::hint`
+    hint: Option<String>,
+    /// The hint plus the element type of a `Vec` of plain values, so empty arrays can be written
+    serialize_hint: Option<String>,
+    default: bool
+}
+
+fn parse(input: &DeriveInput) -> syn::Result<Struct> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "generic structs aren't supported"));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(&input.ident, "only structs with named fields are supported"))
+        },
+        _ => return Err(syn::Error::new_spanned(&input.ident, "only structs are supported"))
+    };
+
+    let mut struct_name = input.ident.to_string();
+    for attr in gvas_attrs(&input.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("struct_name") {
+                struct_name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("unknown gvas attribute, expected struct_name"))
+            }
+        })?;
+    }
+
+    let fields = fields.iter().map(|field| {
+        let ident = field.ident.clone().expect("named field");
+        let mut name = ident.to_string().trim_start_matches("r#").to_string();
+        let mut default = false;
+        let mut hint = Vec::new();
+        for attr in gvas_attrs(&field.attrs) {
+            attr.parse_nested_meta(|meta| {
+                let key = meta.path.get_ident().map(Ident::to_string).unwrap_or_default();
+                match key.as_str() {
+                    "default" => default = true,
+                    "rename" => name = meta.value()?.parse::<LitStr>()?.value(),
+                    "property" | "enum_name" | "struct_name" => {
+                        let value = meta.value()?.parse::<LitStr>()?;
+                        if key == "property" && !PROPERTY_TYPES.contains(&value.value().as_str()) {
+                            return Err(syn::Error::new_spanned(&value, format!("unknown property type {}", value.value())));
+                        }
+                        if value.value().contains([';', '=']) {
+                            return Err(syn::Error::new_spanned(&value, "names can't contain ';' or '='"));
+                        }
+                        hint.push(format!("{}={}", key, value.value()));
+                    },
+                    _ => return Err(meta.error("unknown gvas attribute, expected rename, property, enum_name, struct_name or default"))
+                }
+                Ok(())
+            })?;
+        }
+        let mut serialize_hint = hint.clone();
+        if !hint.iter().any(|entry| entry.starts_with("property=")) {
+            if let Some(property) = element_property(&field.ty) {
+                serialize_hint.push(format!("property={}", property));
+            }
+        }
+        let hint = (!hint.is_empty()).then(|| format!("$gvas:{}", hint.join(";")));
+        let serialize_hint = (!serialize_hint.is_empty()).then(|| format!("$gvas:{}", serialize_hint.join(";")));
+        Ok(Field { ident, ty: field.ty.clone(), name, hint, serialize_hint, default })
+    }).collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(Struct { struct_name, fields })
+}
+
+/// Property type of the elements of `Vec<T>` for the types serde writes without a hint
+fn element_property(ty: &Type) -> Option<&'static str> {
+    let Type::Path(path) = ty else { return None };
+    let segment = path.path.segments.last().filter(|segment| segment.ident == "Vec")?;
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else { return None };
+    let Some(syn::GenericArgument::Type(Type::Path(element))) = arguments.args.first() else { return None };
+    let element = element.path.get_ident()?.to_string();
+    Some(match element.as_str() {
+        "bool" => "BoolProperty",
+        "i8" => "Int8Property",
+        "u8" => "ByteProperty",
+        "i16" => "Int16Property",
+        "u16" => "UInt16Property",
+        "i32" => "IntProperty",
+        "u32" => "UInt32Property",
+        "i64" => "Int64Property",
+        "u64" => "UInt64Property",
+        "f32" => "FloatProperty",
+        "f64" => "DoubleProperty",
+        "String" => "StrProperty",
+        _ => return None
+    })
+}
+
+fn gvas_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("gvas"))
+}
+
+fn serialize(ident: &Ident, parsed: &Struct) -> TokenStream2 {
+    let struct_name = &parsed.struct_name;
+    let len = parsed.fields.len();
+    let fields = parsed.fields.iter().map(|field| {
+        let (field_ident, name) = (&field.ident, &field.name);
+        match &field.serialize_hint {
+            Some(hint) => quote! { __state.serialize_field(#name, &::serde_gvas::hint::Hinted(#hint, &self.#field_ident))?; },
+            None => quote! { __state.serialize_field(#name, &self.#field_ident)?; }
+        }
+    });
+
+    quote! {
+        impl ::serde_gvas::hint::serde::Serialize for #ident {
+            fn serialize<__S>(&self, __serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+            where
+                __S: ::serde_gvas::hint::serde::Serializer {
+                use ::serde_gvas::hint::serde::ser::SerializeStruct;
+                let mut __state = __serializer.serialize_struct(#struct_name, #len)?;
+                #(#fields)*
+                __state.end()
+            }
+        }
+    }
+}
+
+fn deserialize(ident: &Ident, parsed: &Struct) -> TokenStream2 {
+    let struct_name = &parsed.struct_name;
+    let expecting = format!("struct {}", ident);
+    let names: Vec<&String> = parsed.fields.iter().map(|field| &field.name).collect();
+    let slots: Vec<Ident> = (0..parsed.fields.len()).map(|index| Ident::new(&format!("__field{}", index), proc_macro2::Span::call_site())).collect();
+    let types: Vec<&Type> = parsed.fields.iter().map(|field| &field.ty).collect();
+    let reads = parsed.fields.iter().map(|field| {
+        let ty = &field.ty;
+        match &field.hint {
+            Some(hint) => quote! { __map.next_value_seed(::serde_gvas::hint::HintedSeed::<#ty>::new(#hint))? },
+            None => quote! { __map.next_value::<#ty>()? }
+        }
+    });
+    let inits = parsed.fields.iter().zip(&slots).map(|(field, slot)| {
+        let (field_ident, name) = (&field.ident, &field.name);
+        match field.default {
+            true => quote! { #field_ident: #slot.unwrap_or_default() },
+            false => quote! {
+                #field_ident: match #slot {
+                    ::core::option::Option::Some(__value) => __value,
+                    ::core::option::Option::None => return ::core::result::Result::Err(<__A::Error as ::serde_gvas::hint::serde::de::Error>::missing_field(#name))
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl<'de> ::serde_gvas::hint::serde::Deserialize<'de> for #ident {
+            fn deserialize<__D>(__deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+            where
+                __D: ::serde_gvas::hint::serde::Deserializer<'de> {
+                struct __Visitor;
+
+                impl<'de> ::serde_gvas::hint::serde::de::Visitor<'de> for __Visitor {
+                    type Value = #ident;
+
+                    fn expecting(&self, __formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        __formatter.write_str(#expecting)
+                    }
+
+                    fn visit_map<__A>(self, mut __map: __A) -> ::core::result::Result<#ident, __A::Error>
+                    where
+                        __A: ::serde_gvas::hint::serde::de::MapAccess<'de> {
+                        #(let mut #slots: ::core::option::Option<#types> = ::core::option::Option::None;)*
+                        while let ::core::option::Option::Some(__key) = __map.next_key::<::std::string::String>()? {
+                            match __key.as_str() {
+                                #(#names => {
+                                    if #slots.is_some() {
+                                        return ::core::result::Result::Err(<__A::Error as ::serde_gvas::hint::serde::de::Error>::duplicate_field(#names));
+                                    }
+                                    #slots = ::core::option::Option::Some(#reads);
+                                },)*
+                                _ => {
+                                    __map.next_value::<::serde_gvas::hint::serde::de::IgnoredAny>()?;
+                                }
+                            }
+                        }
+                        ::core::result::Result::Ok(#ident { #(#inits),* })
+                    }
+                }
+
+                __deserializer.deserialize_struct(#struct_name, &[#(#names),*], __Visitor)
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::{env, fs, process::ExitCode};
+
+use serde_gvas::{codegen, header, hexdump, patch, path, text, value, DeserializerOptions, Error};
+use serde_gvas::types::{FGuid, GvasHeader};
+
+const USAGE: &str = "Usage: gvas <command> [options]
+
+Commands:
+    header <file>             Print the save header
+    dump [--recover] <file>   Print the whole save in the text format
+    to-json [--recover] <file> <output>
+                              Convert a save to JSON
+    from-json <file> <output> Convert JSON back into a save
+    codegen [--recover] <file>
+                              Print Rust types matching the save
+    set <file> <path=value>...
+                              Change values in place, e.g. Stats.Gold=500
+    hexdump <file>            Print the bytes of the save with what they hold,
+                              up to where parsing fails";
+
+enum Failure {
+    Usage(String),
+    Failed(String)
+}
+
+impl From<Error> for Failure {
+    fn from(e: Error) -> Self {
+        Failure::Failed(e.to_string())
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(Failure::Usage(msg)) => {
+            eprintln!("{}\n\n{}", msg, USAGE);
+            ExitCode::from(2)
+        },
+        Err(Failure::Failed(msg)) => {
+            eprintln!("error: {}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Failure> {
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => return Err(Failure::Usage(String::from("Missing command")))
+    };
+    let recover = args.iter().any(|arg| arg == "--recover");
+    let paths: Vec<&String> = args.iter().filter(|arg| *arg != "--recover").collect();
+
+    match (command, paths.as_slice()) {
+        ("header", [path]) => {
+            let (header, _, _) = header::de::from_save_bytes(read_file(path)?, DeserializerOptions::default())?;
+            print_header(&header);
+            Ok(())
+        },
+        ("dump", [path]) => {
+            let options = DeserializerOptions { recover, ..Default::default() };
+            let (save, diagnostics) = value::Save::from_bytes_with_diagnostics(read_file(path)?, options)?;
+            print!("{}", text::to_string(&save));
+            for diagnostic in diagnostics {
+                eprintln!("warning: {}", diagnostic);
+            }
+            Ok(())
+        },
+        ("codegen", [path]) => {
+            let options = DeserializerOptions { recover, ..Default::default() };
+            let save = value::Save::from_bytes_with_options(read_file(path)?, options)?;
+            print!("{}", codegen::generate(&save));
+            Ok(())
+        },
+        ("hexdump", [path]) => {
+            let (dump, error) = hexdump::annotate(read_file(path)?);
+            print!("{}", dump);
+            match error {
+                Some(err) => Err(err.into()),
+                None => Ok(())
+            }
+        },
+        ("set", [path, assignments @ ..]) if !assignments.is_empty() => set(path, assignments),
+        #[cfg(feature = "json")]
+        ("to-json", [path, output]) => to_json(path, output, recover),
+        #[cfg(feature = "json")]
+        ("from-json", [path, output]) => from_json(path, output),
+        #[cfg(not(feature = "json"))]
+        ("to-json" | "from-json", [_, _]) => Err(Failure::Failed(String::from("gvas was built without the json feature"))),
+        ("help" | "--help" | "-h", _) => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        ("header" | "dump" | "codegen" | "hexdump" | "set" | "to-json" | "from-json", _) => Err(Failure::Usage(format!("Wrong arguments for {}", command))),
+        _ => Err(Failure::Usage(format!("Unknown command {}", command)))
+    }
+}
+
+fn set(file: &str, assignments: &[&String]) -> Result<(), Failure> {
+    // values are patched in place, so the rest of the save is never decoded
+    let (_, container, mut cursor): (GvasHeader, _, _) = header::de::from_save_bytes(read_file(file)?, DeserializerOptions::default())?;
+    for assignment in assignments {
+        let (target, text) = split_assignment(assignment).ok_or_else(|| Failure::Usage(format!("Expected path=value, got {}", assignment)))?;
+        let target: path::Path = target.parse()?;
+        let value = path::parse_value(&patch::get(&mut cursor, &target)?, text)?;
+        patch::set(&mut cursor, &target, value)?;
+    }
+    let data = container.compress(&cursor.into_inner())?;
+    fs::write(file, data).map_err(|e| Failure::Failed(format!("{}: {}", file, e)))
+}
+
+/// Splits at the first `=` that isn't in a quoted name or key
+fn split_assignment(assignment: &str) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, c) in assignment.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '=' if !quoted => return Some((&assignment[..index], &assignment[index + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(feature = "json")]
+fn to_json(path: &str, output: &str, recover: bool) -> Result<(), Failure> {
+    let options = DeserializerOptions { recover, ..Default::default() };
+    let (save, diagnostics) = value::Save::from_bytes_with_diagnostics(read_file(path)?, options)?;
+    for diagnostic in diagnostics {
+        eprintln!("warning: {}", diagnostic);
+    }
+    let json = serde_gvas::json::to_string(&save)?;
+    write_file(output, json.as_bytes())
+}
+
+#[cfg(feature = "json")]
+fn from_json(path: &str, output: &str) -> Result<(), Failure> {
+    let json = fs::read_to_string(path).map_err(|e| Failure::Failed(format!("{}: {}", path, e)))?;
+    let save = serde_gvas::json::from_str(&json)?;
+    write_file(output, &save.to_vec()?)
+}
+
+#[cfg(feature = "json")]
+fn write_file(path: &str, data: &[u8]) -> Result<(), Failure> {
+    fs::write(path, data).map_err(|e| Failure::Failed(format!("{}: {}", path, e)))
+}
+
+fn read_file(path: &str) -> Result<Vec<u8>, Failure> {
+    fs::read(path).map_err(|e| Failure::Failed(format!("{}: {}", path, e)))
+}
+
+fn format_guid(guid: &FGuid) -> String {
+    format!("{:08X}-{:08X}-{:08X}-{:08X}", guid.a, guid.b, guid.c, guid.d)
+}
+
+fn print_header(header: &GvasHeader) {
+    let engine = &header.engine_version;
+    println!("Save game version: {}", header.save_game_file_version);
+    println!("Package version: {}", header.package_file_ue4_version);
+    if let Some(version) = header.package_file_ue5_version {
+        println!("UE5 package version: {}", version);
+    }
+    println!("Engine version: {}.{}.{}-{}+{}", engine.major, engine.minor, engine.patch, engine.change_list, engine.branch);
+    println!("Save game class: {}", header.save_game_class_name);
+    println!("Custom versions ({}, format {}):", header.custom_versions.len(), header.custom_version_format);
+    for version in &header.custom_versions {
+        let name = version.name().map(String::from).unwrap_or_else(|| format_guid(&version.key));
+        match version.version_name() {
+            Some(version_name) => println!("    {} = {} ({})", name, version.version, version_name),
+            None => println!("    {} = {}", name, version.version)
+        }
+    }
+}
@@ -0,0 +1,371 @@
+//! Generates Rust types for a save from a sample of it
+
+use std::collections::BTreeSet;
+
+use crate::value::{ArrayValue, ByteValue, Property, Save, StructValue, Value};
+
+const KEYWORDS: &[&str] = &["as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false", "fn", "for", "if",
+    "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe",
+    "use", "where", "while", "abstract", "become", "box", "do", "final", "macro", "override", "priv", "typeof", "unsized", "virtual", "yield", "try"];
+
+/// Types from [`crate::types`] the generated code may use
+const WRAPPERS: &[&str] = &["FName", "ObjectPath", "SoftObjectPath", "EnumByte", "FDateTime", "FTimespan", "FGuid"];
+
+/// Emits `#[derive(Deserialize, Serialize)]` structs for every struct type found in the save, named after
+/// their UE struct name, and enums for enum properties with the variants the sample happens to contain.
+/// Fields missing from some instances of a struct become `Option`s. Names, objects, soft objects, enums
+/// in bytes and native structs use the wrappers in [`crate::types`], so they're written back as the
+/// property types they were read from. Maps become `HashMap`s and sets
+/// `Vec`s, both are skipped when serializing since the serializer can't write them. Structs in them
+/// that don't name their struct get a type named after the field. Undecoded properties are left out
+/// with a comment, reading them needs [`crate::DeserializerOptions::recover`].
+pub fn generate(save: &Save) -> String {
+    let mut generator = Generator::default();
+    let root = generator.struct_index(root_name(&save.header.save_game_class_name));
+    generator.add_instance(root, &save.properties);
+    generator.output()
+}
+
+fn root_name(class_name: &str) -> &str {
+    match class_name.rsplit(['.', '/']).next() {
+        Some(name) if !name.is_empty() => name,
+        _ => "SaveGame"
+    }
+}
+
+fn type_ident(name: &str) -> String {
+    let name = name.rsplit(['.', '/']).next().unwrap_or(name);
+    let mut ident: String = name.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '_').collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if matches!(ident.as_str(), "Self" | "Vec" | "String" | "Option" | "Result" | "Box" | "HashMap") || WRAPPERS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+fn field_ident(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut ident = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            ident.push('_');
+            continue;
+        }
+        if c.is_ascii_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_ascii_lowercase());
+            if previous.is_ascii_lowercase() || previous.is_ascii_digit() || (previous.is_ascii_uppercase() && next_lower) {
+                ident.push('_');
+            }
+        }
+        ident.push(c.to_ascii_lowercase());
+    }
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    match ident.as_str() {
+        "self" | "super" | "crate" => ident + "_",
+        keyword if KEYWORDS.contains(&keyword) => format!("r#{}", ident),
+        _ => ident
+    }
+}
+
+fn variant_ident(label: &str) -> String {
+    let name = label.rsplit("::").next().unwrap_or(label);
+    let mut ident = String::new();
+    let mut upper = true;
+    for c in name.chars() {
+        match c.is_ascii_alphanumeric() {
+            true if upper => ident.push(c.to_ascii_uppercase()),
+            true => ident.push(c),
+            false => {}
+        }
+        upper = !c.is_ascii_alphanumeric();
+    }
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, 'V');
+    }
+    ident
+}
+
+/// Rust type of an element that isn't a struct or an enum
+fn bare_type(type_name: &str) -> Option<&'static str> {
+    Some(match type_name {
+        "BoolProperty" => "bool",
+        "Int8Property" => "i8",
+        "ByteProperty" => "u8",
+        "Int16Property" => "i16",
+        "UInt16Property" => "u16",
+        "IntProperty" => "i32",
+        "UInt32Property" => "u32",
+        "Int64Property" => "i64",
+        "UInt64Property" => "u64",
+        "FloatProperty" => "f32",
+        "DoubleProperty" => "f64",
+        "StrProperty" | "EnumProperty" => "String",
+        "NameProperty" => "FName",
+        "ObjectProperty" => "ObjectPath",
+        "SoftObjectProperty" => "SoftObjectPath",
+        _ => return None
+    })
+}
+
+struct Field {
+    name: String,
+    /// Why the field is left out when it can't be deserialized
+    rust_type: Result<String, String>,
+    /// Maps and sets, which are only read
+    read_only: bool,
+    seen: usize
+}
+
+struct StructDef {
+    ue_name: String,
+    instances: usize,
+    fields: Vec<Field>
+}
+
+struct EnumDef {
+    ue_name: String,
+    /// The name tags hold, if any names the enum
+    enum_name: Option<String>,
+    labels: Vec<String>
+}
+
+#[derive(Default)]
+struct Generator {
+    structs: Vec<StructDef>,
+    enums: Vec<EnumDef>,
+    uses_map: bool,
+    /// Wrappers from [`WRAPPERS`] in use
+    wrappers: BTreeSet<&'static str>
+}
+
+impl Generator {
+    fn struct_index(&mut self, ue_name: &str) -> usize {
+        match self.structs.iter().position(|def| def.ue_name == ue_name) {
+            Some(index) => index,
+            None => {
+                self.structs.push(StructDef { ue_name: String::from(ue_name), instances: 0, fields: Vec::new() });
+                self.structs.len() - 1
+            }
+        }
+    }
+
+    fn add_instance(&mut self, index: usize, properties: &[Property]) {
+        self.structs[index].instances += 1;
+        // slots of static arrays share the name of the first one, the derived code can only hold that
+        for property in properties.iter().filter(|property| property.array_index == 0) {
+            let rust_type = self.property_type(&property.name, &property.value);
+            let read_only = matches!(property.value, Value::Set { .. } | Value::Map { .. });
+            let fields = &mut self.structs[index].fields;
+            match fields.iter_mut().find(|field| field.name == property.name) {
+                Some(field) => field.seen += 1,
+                None => fields.push(Field { name: property.name.clone(), rust_type, read_only, seen: 1 })
+            }
+        }
+    }
+
+    fn enum_type(&mut self, enum_name: &str, field_name: &str, labels: &[&str]) -> String {
+        let ue_name = match (enum_name, labels.iter().find_map(|label| label.split_once("::"))) {
+            (name, _) if name != "None" => name.rsplit(['.', '/']).next().unwrap_or(name),
+            (_, Some((prefix, _))) => prefix,
+            _ => field_name
+        };
+        let index = match self.enums.iter().position(|def| def.ue_name == ue_name) {
+            Some(index) => index,
+            None => {
+                self.enums.push(EnumDef { ue_name: String::from(ue_name), enum_name: None, labels: Vec::new() });
+                self.enums.len() - 1
+            }
+        };
+        if enum_name != "None" {
+            self.enums[index].enum_name.get_or_insert_with(|| String::from(enum_name));
+        }
+        for label in labels {
+            if !self.enums[index].labels.iter().any(|known| known == label) {
+                self.enums[index].labels.push(String::from(*label));
+            }
+        }
+        type_ident(ue_name)
+    }
+
+    /// Names a type from [`WRAPPERS`], importing it
+    fn wrapper(&mut self, name: &'static str) -> String {
+        self.wrappers.insert(name);
+        String::from(name)
+    }
+
+    fn bare_type(&mut self, type_name: &str) -> Option<String> {
+        let rust_type = bare_type(type_name)?;
+        match WRAPPERS.iter().find(|wrapper| **wrapper == rust_type) {
+            Some(wrapper) => Some(self.wrapper(wrapper)),
+            None => Some(String::from(rust_type))
+        }
+    }
+
+    /// An enum held in a `ByteProperty` by name
+    fn enum_byte(&mut self, enum_name: &str, field_name: &str, labels: &[&str]) -> String {
+        let enum_type = self.enum_type(enum_name, field_name, labels);
+        format!("{}<{}>", self.wrapper("EnumByte"), enum_type)
+    }
+
+    fn struct_type(&mut self, struct_name: &str, values: &[&StructValue]) -> String {
+        match values.first() {
+            Some(StructValue::DateTime(_)) => self.wrapper("FDateTime"),
+            Some(StructValue::Timespan(_)) => self.wrapper("FTimespan"),
+            Some(StructValue::Guid(_)) => self.wrapper("FGuid"),
+            _ => {
+                let index = self.struct_index(struct_name);
+                for value in values {
+                    if let StructValue::Properties(properties) = value {
+                        self.add_instance(index, properties);
+                    }
+                }
+                type_ident(struct_name)
+            }
+        }
+    }
+
+    fn property_type(&mut self, name: &str, value: &Value) -> Result<String, String> {
+        Ok(match value {
+            Value::Byte { enum_name, value: ByteValue::Label(label) } => self.enum_byte(enum_name, name, &[label]),
+            Value::Enum { enum_name, value } => self.enum_type(enum_name, name, &[value]),
+            Value::Struct { struct_name, value, .. } => self.struct_type(struct_name, &[value]),
+            Value::Array { value: ArrayValue::Bytes(_), .. } => String::from("Vec<u8>"),
+            Value::Array { value: ArrayValue::Structs { struct_name, elements, .. }, .. } => {
+                format!("Vec<{}>", self.struct_type(struct_name, &elements.iter().collect::<Vec<_>>()))
+            },
+            Value::Array { element_type, value: ArrayValue::Values(values) } => {
+                let labels: Vec<&str> = values.iter().filter_map(|value| match value {
+                    Value::Enum { value, .. } | Value::Byte { value: ByteValue::Label(value), .. } => Some(value.as_str()),
+                    _ => None
+                }).collect();
+                match (element_type.as_str(), labels.is_empty()) {
+                    ("EnumProperty", _) => format!("Vec<{}>", self.enum_type("None", name, &labels)),
+                    ("ByteProperty", false) => format!("Vec<{}>", self.enum_byte("None", name, &labels)),
+                    (element_type, _) => format!("Vec<{}>", self.bare_type(element_type).ok_or_else(|| format!("ArrayProperty<{}> isn't decoded", element_type))?)
+                }
+            },
+            Value::Map { key_type, value_type, entries, .. } => {
+                if matches!(key_type.as_str(), "FloatProperty" | "DoubleProperty" | "StructProperty") {
+                    return Err(format!("MapProperty<{}, {}> has keys that can't be hashed", key_type, value_type));
+                }
+                let keys: Vec<&Value> = entries.iter().map(|(key, _)| key).collect();
+                let values: Vec<&Value> = entries.iter().map(|(_, value)| value).collect();
+                let key = self.item_type(&format!("{}Key", name), key_type, &keys)?;
+                let value = self.item_type(&format!("{}Value", name), value_type, &values)?;
+                self.uses_map = true;
+                format!("HashMap<{}, {}>", key, value)
+            },
+            Value::Set { element_type, elements, .. } => {
+                format!("Vec<{}>", self.item_type(&format!("{}Element", name), element_type, &elements.iter().collect::<Vec<_>>())?)
+            },
+            Value::Unknown { type_name, .. } => return Err(format!("{} isn't decoded, reading it needs recover", type_name)),
+            _ => self.bare_type(value.type_name()).unwrap_or_else(|| String::from("u8"))
+        })
+    }
+
+    /// Rust type of the elements of a set or the keys or values of a map, structs that don't name
+    /// their struct are named `name`
+    fn item_type(&mut self, name: &str, type_name: &str, values: &[&Value]) -> Result<String, String> {
+        let structs: Vec<&StructValue> = values.iter().filter_map(|value| match value {
+            Value::Struct { value, .. } => Some(value),
+            _ => None
+        }).collect();
+        let labels: Vec<&str> = values.iter().filter_map(|value| match value {
+            Value::Enum { value, .. } => Some(value.as_str()),
+            _ => None
+        }).collect();
+        match (type_name, values.first()) {
+            ("StructProperty", Some(Value::Struct { struct_name, .. })) if !struct_name.is_empty() => Ok(self.struct_type(struct_name, &structs)),
+            ("StructProperty", Some(_)) => Ok(self.struct_type(name, &structs)),
+            ("StructProperty", None) => Err(String::from("the struct of its elements isn't known without any")),
+            ("EnumProperty", _) => Ok(self.enum_type("None", name, &labels)),
+            _ => self.bare_type(type_name).ok_or_else(|| format!("{} elements aren't decoded", type_name))
+        }
+    }
+
+    fn output(&self) -> String {
+        let mut output = String::new();
+        if self.uses_map {
+            output.push_str("use std::collections::HashMap;\n\n");
+        }
+        output.push_str("use serde::{Deserialize, Serialize};\n");
+        let wrappers: Vec<&str> = self.wrappers.iter().copied().collect();
+        match wrappers.as_slice() {
+            [] => {},
+            [wrapper] => output.push_str(&format!("use serde_gvas::types::{};\n", wrapper)),
+            _ => output.push_str(&format!("use serde_gvas::types::{{{}}};\n", wrappers.join(", ")))
+        }
+
+        for def in &self.structs {
+            output.push_str("\n#[derive(Debug, Deserialize, Serialize)]\n");
+            let ident = type_ident(&def.ue_name);
+            if ident != def.ue_name {
+                output.push_str(&format!("#[serde(rename = {:?})]\n", def.ue_name));
+            }
+            output.push_str(&format!("pub struct {} {{\n", ident));
+            let mut idents: Vec<String> = Vec::new();
+            for field in &def.fields {
+                let rust_type = match &field.rust_type {
+                    Ok(rust_type) => rust_type,
+                    Err(reason) => {
+                        output.push_str(&format!("    // {:?} is left out, {}\n", field.name, reason));
+                        continue;
+                    }
+                };
+                let mut ident = field_ident(&field.name);
+                while idents.contains(&ident) {
+                    ident.push('_');
+                }
+                idents.push(ident.clone());
+
+                let optional = field.seen < def.instances;
+                let rename = (ident.trim_start_matches("r#") != field.name).then(|| format!("rename = {:?}", field.name));
+                let attributes: Vec<String> = rename.into_iter()
+                    .chain(match (field.read_only, optional) {
+                        (true, true) => Some(String::from("default, skip_serializing")),
+                        (true, false) => Some(String::from("skip_serializing")),
+                        (false, true) => Some(String::from("default, skip_serializing_if = \"Option::is_none\"")),
+                        (false, false) => None
+                    })
+                    .collect();
+                if !attributes.is_empty() {
+                    output.push_str(&format!("    #[serde({})]\n", attributes.join(", ")));
+                }
+                match optional {
+                    true => output.push_str(&format!("    pub {}: Option<{}>,\n", ident, rust_type)),
+                    false => output.push_str(&format!("    pub {}: {},\n", ident, rust_type))
+                }
+            }
+            output.push_str("}\n");
+        }
+
+        for def in &self.enums {
+            output.push_str("\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]\n");
+            let ident = type_ident(&def.ue_name);
+            // enums are written with their serde name in the tag
+            if let Some(enum_name) = def.enum_name.as_ref().filter(|enum_name| **enum_name != ident) {
+                output.push_str(&format!("#[serde(rename = {:?})]\n", enum_name));
+            }
+            output.push_str(&format!("pub enum {} {{\n", ident));
+            let mut idents: Vec<String> = Vec::new();
+            for label in &def.labels {
+                let mut ident = variant_ident(label);
+                while idents.contains(&ident) {
+                    ident.push('_');
+                }
+                idents.push(ident.clone());
+                if ident != *label {
+                    output.push_str(&format!("    #[serde(rename = {:?})]\n", label));
+                }
+                output.push_str(&format!("    {},\n", ident));
+            }
+            output.push_str("}\n");
+        }
+        output
+    }
+}
@@ -0,0 +1,283 @@
+//! Containers wrapped around the GVAS stream by some games and by the engine itself.
+//!
+//! Detecting a container always works, inflating and deflating zlib data needs the `compression`
+//! feature. [`Save::from_bytes`](crate::value::Save::from_bytes) detects the container and
+//! [`Save::to_vec`](crate::value::Save::to_vec) wraps the save the same way again.
+
+use std::io::{Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use serde::{Deserialize, Serialize};
+
+use crate::de::{check_limit, DeserializerOptions};
+use crate::error::{Error, Result};
+use crate::transform::SaveTransform;
+
+const PLZ_MAGIC: &[u8; 3] = b"PlZ";
+const PACKAGE_FILE_TAG: i64 = 0x9E2A83C1;
+/// Chunk size the engine compresses with unless told otherwise
+pub const DEFAULT_CHUNK_SIZE: u32 = 131072;
+
+/// How the GVAS stream is stored in a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Container {
+    #[default]
+    Raw,
+    /// Palworld style `PlZ` header (uncompressed size, compressed size, magic, compression type)
+    Plz(PlzCompression),
+    /// The engine's own chunked format, as written by `FArchive::SerializeCompressed`
+    Chunked { chunk_size: u32 }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PlzCompression {
+    None,
+    Zlib,
+    ZlibTwice
+}
+
+impl PlzCompression {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x30 => Some(PlzCompression::None),
+            0x31 => Some(PlzCompression::Zlib),
+            0x32 => Some(PlzCompression::ZlibTwice),
+            _ => None
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            PlzCompression::None => 0x30,
+            PlzCompression::Zlib => 0x31,
+            PlzCompression::ZlibTwice => 0x32
+        }
+    }
+}
+
+impl Container {
+    pub fn is_raw(&self) -> bool {
+        *self == Container::Raw
+    }
+
+    /// Guesses the container from the first bytes of a file, anything unknown is assumed to be raw
+    pub fn detect(data: &[u8]) -> Self {
+        if let Some(chunk_size) = read_package_file_tag(data) {
+            return Container::Chunked { chunk_size };
+        }
+        match data.get(8..12) {
+            Some([b'P', b'l', b'Z', compression]) => match PlzCompression::from_u8(*compression) {
+                Some(compression) => Container::Plz(compression),
+                None => Container::Raw
+            },
+            _ => Container::Raw
+        }
+    }
+
+    /// Unwraps the GVAS stream, refusing to inflate past `options.max_total_size`
+    pub fn decompress(&self, data: Vec<u8>, options: &DeserializerOptions) -> Result<Vec<u8>> {
+        match self {
+            Container::Raw => Ok(data),
+            Container::Plz(compression) => decompress_plz(*compression, &data, options),
+            Container::Chunked { .. } => decompress_chunked(&data, options)
+        }
+    }
+
+    /// Wraps a GVAS stream the same way it was stored originally
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        self.compress_to_writer(&mut output, data)?;
+        Ok(output)
+    }
+
+    pub fn compress_to_writer<W: Write>(&self, mut writer: W, data: &[u8]) -> Result<()> {
+        match self {
+            Container::Raw => writer.write_all(data)?,
+            Container::Plz(compression) => {
+                // the compressed size of double compressed saves is the size after one round
+                let (compressed_size, compressed) = match compression {
+                    PlzCompression::None => (data.len(), data.to_vec()),
+                    PlzCompression::Zlib => {
+                        let compressed = zlib::deflate(data)?;
+                        (compressed.len(), compressed)
+                    },
+                    PlzCompression::ZlibTwice => {
+                        let once = zlib::deflate(data)?;
+                        (once.len(), zlib::deflate(&once)?)
+                    }
+                };
+                writer.write_u32::<LittleEndian>(data.len() as u32)?;
+                writer.write_u32::<LittleEndian>(compressed_size as u32)?;
+                writer.write_all(PLZ_MAGIC)?;
+                writer.write_u8(compression.to_u8())?;
+                writer.write_all(&compressed)?;
+            },
+            Container::Chunked { chunk_size } => compress_chunked(&mut writer, *chunk_size, data)?
+        }
+        Ok(())
+    }
+}
+
+impl SaveTransform for Container {
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        self.decompress(data.to_vec(), &DeserializerOptions::default())
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        self.compress(data)
+    }
+}
+
+/// Detects the container and unwraps the GVAS stream in one go
+pub fn decompress(data: Vec<u8>) -> Result<(Container, Vec<u8>)> {
+    decompress_with_options(data, &DeserializerOptions::default())
+}
+
+pub fn decompress_with_options(data: Vec<u8>, options: &DeserializerOptions) -> Result<(Container, Vec<u8>)> {
+    let container = Container::detect(&data);
+    let data = container.decompress(data, options)?;
+    Ok((container, data))
+}
+
+#[cfg(feature = "compression")]
+mod zlib {
+    use std::io::{Read, Write};
+
+    use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+    use crate::de::check_limit;
+    use crate::error::Result;
+
+    pub(super) fn deflate(data: &[u8]) -> Result<Vec<u8>> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)?;
+        Ok(encoder.finish()?)
+    }
+
+    /// Inflates at most `limit` bytes, so a small file can't expand into gigabytes
+    pub(super) fn inflate<R: Read>(reader: R, limit: u64) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        ZlibDecoder::new(reader).take(limit + 1).read_to_end(&mut data)?;
+        check_limit("total size", limit, data.len() as u64)?;
+        Ok(data)
+    }
+
+    pub(super) fn inflate_twice(data: &[u8], limit: u64) -> Result<Vec<u8>> {
+        inflate(ZlibDecoder::new(data), limit)
+    }
+}
+
+#[cfg(not(feature = "compression"))]
+mod zlib {
+    use crate::error::{Error, Result};
+
+    fn unsupported() -> Error {
+        Error::unsupported("zlib compression without the compression feature")
+    }
+
+    pub(super) fn deflate(_data: &[u8]) -> Result<Vec<u8>> {
+        Err(unsupported())
+    }
+
+    pub(super) fn inflate(_data: &[u8], _limit: u64) -> Result<Vec<u8>> {
+        Err(unsupported())
+    }
+
+    pub(super) fn inflate_twice(_data: &[u8], _limit: u64) -> Result<Vec<u8>> {
+        Err(unsupported())
+    }
+}
+
+fn decompress_plz(compression: PlzCompression, mut data: &[u8], options: &DeserializerOptions) -> Result<Vec<u8>> {
+    let uncompressed_size = data.read_u32::<LittleEndian>()? as u64;
+    let _compressed_size = data.read_u32::<LittleEndian>()?;
+    let mut magic = [0u8; 4];
+    data.read_exact(&mut magic)?;
+    check_limit("total size", options.max_total_size, uncompressed_size)?;
+
+    let decompressed = match compression {
+        PlzCompression::None => data.to_vec(),
+        PlzCompression::Zlib => zlib::inflate(data, uncompressed_size)?,
+        PlzCompression::ZlibTwice => zlib::inflate_twice(data, uncompressed_size)?
+    };
+    if decompressed.len() as u64 != uncompressed_size {
+        return Err(Error::make_data(format!("Expected {} bytes after decompressing, got {}", uncompressed_size, decompressed.len())));
+    }
+    Ok(decompressed)
+}
+
+/// Returns the chunk size if `data` starts with a package file tag
+fn read_package_file_tag(mut data: &[u8]) -> Option<u32> {
+    let tag = data.read_i64::<LittleEndian>().ok()?;
+    let chunk_size = data.read_i64::<LittleEndian>().ok()?;
+    match tag {
+        PACKAGE_FILE_TAG => Some(u32::try_from(chunk_size).unwrap_or(0)),
+        _ => None
+    }
+}
+
+/// Reads every compressed block until the end of the data, large archives are split into several
+fn decompress_chunked(mut data: &[u8], options: &DeserializerOptions) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    while !data.is_empty() {
+        let chunk_size = match read_package_file_tag(data) {
+            Some(chunk_size) if chunk_size > 0 => chunk_size as u64,
+            Some(_) => return Err(Error::make_data(String::from("Invalid compression chunk size"))),
+            None => return Err(Error::make_data(String::from("Expected a package file tag")))
+        };
+        data = &data[16..];
+
+        let _total_compressed = data.read_i64::<LittleEndian>()?;
+        let total_uncompressed = data.read_i64::<LittleEndian>()?;
+        if total_uncompressed < 0 {
+            return Err(Error::make_data(format!("Invalid uncompressed size {}", total_uncompressed)));
+        }
+        check_limit("total size", options.max_total_size, decompressed.len() as u64 + total_uncompressed as u64)?;
+
+        let chunks = (total_uncompressed as u64).div_ceil(chunk_size);
+        if chunks.checked_mul(16).is_none_or(|length| length > data.len() as u64) {
+            return Err(Error::make_data(format!("Archive is too short for {} chunks", chunks)));
+        }
+        let mut sizes = Vec::with_capacity(chunks as usize);
+        for _ in 0..chunks {
+            let compressed = data.read_i64::<LittleEndian>()?;
+            let uncompressed = data.read_i64::<LittleEndian>()?;
+            if compressed < 0 || compressed as u64 > data.len() as u64 || uncompressed < 0 || uncompressed as u64 > chunk_size {
+                return Err(Error::make_data(format!("Invalid chunk sizes {} and {}", compressed, uncompressed)));
+            }
+            sizes.push((compressed as usize, uncompressed as u64));
+        }
+
+        for (compressed, uncompressed) in sizes {
+            let (chunk, rest) = data.split_at(compressed.min(data.len()));
+            let chunk = zlib::inflate(chunk, uncompressed)?;
+            if chunk.len() as u64 != uncompressed {
+                return Err(Error::make_data(format!("Expected {} bytes after decompressing, got {}", uncompressed, chunk.len())));
+            }
+            decompressed.extend_from_slice(&chunk);
+            data = rest;
+        }
+    }
+    Ok(decompressed)
+}
+
+fn compress_chunked<W: Write>(writer: &mut W, chunk_size: u32, data: &[u8]) -> Result<()> {
+    if chunk_size == 0 {
+        return Err(Error::make_data(String::from("Invalid compression chunk size")));
+    }
+    let chunks = data.chunks(chunk_size as usize).map(zlib::deflate).collect::<Result<Vec<_>>>()?;
+    let total_compressed: usize = chunks.iter().map(Vec::len).sum();
+
+    writer.write_i64::<LittleEndian>(PACKAGE_FILE_TAG)?;
+    writer.write_i64::<LittleEndian>(chunk_size as i64)?;
+    writer.write_i64::<LittleEndian>(total_compressed as i64)?;
+    writer.write_i64::<LittleEndian>(data.len() as i64)?;
+    for (compressed, uncompressed) in chunks.iter().zip(data.chunks(chunk_size as usize)) {
+        writer.write_i64::<LittleEndian>(compressed.len() as i64)?;
+        writer.write_i64::<LittleEndian>(uncompressed.len() as i64)?;
+    }
+    for compressed in chunks {
+        writer.write_all(&compressed)?;
+    }
+    Ok(())
+}
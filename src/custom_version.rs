@@ -0,0 +1,276 @@
+use crate::types::FGuid;
+
+/// A custom version GUID known to ship with the engine, with the names of its version steps
+/// (empty when only the GUID is known).
+#[derive(Debug, PartialEq, Eq)]
+pub struct CustomVersionInfo {
+    pub name: &'static str,
+    pub guid: FGuid,
+    pub versions: &'static [&'static str]
+}
+
+impl CustomVersionInfo {
+    pub const fn new(name: &'static str, guid: FGuid) -> Self {
+        CustomVersionInfo { name, guid, versions: &[] }
+    }
+
+    pub fn version_name(&self, version: i32) -> Option<&'static str> {
+        usize::try_from(version).ok().and_then(|i| self.versions.get(i)).copied()
+    }
+
+    pub fn latest_version(&self) -> Option<i32> {
+        self.versions.len().checked_sub(1).map(|v| v as i32)
+    }
+}
+
+macro_rules! custom_version_enum {
+    ($name:ident, $guid:expr, $($variant:ident),*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(i32)]
+        pub enum $name {
+            $($variant),*
+        }
+
+        impl $name {
+            pub const NAME: &'static str = stringify!($name);
+            pub const GUID: FGuid = $guid;
+            pub const VERSIONS: &'static [&'static str] = &[$(stringify!($variant)),*];
+            const ALL: &'static [$name] = &[$($name::$variant),*];
+            pub const INFO: CustomVersionInfo = CustomVersionInfo { name: Self::NAME, guid: Self::GUID, versions: Self::VERSIONS };
+
+            pub fn from_version(version: i32) -> Option<Self> {
+                usize::try_from(version).ok().and_then(|i| Self::ALL.get(i)).copied()
+            }
+
+            pub fn latest() -> Self {
+                Self::ALL[Self::ALL.len() - 1]
+            }
+        }
+    };
+}
+
+custom_version_enum! {
+    FCoreObjectVersion, FGuid::new(0x375EC13C, 0x06E448FB, 0xB50084F0, 0x262A717E),
+    BeforeCustomVersionWasAdded, MaterialInputNativeSerialize, EnumProperties,
+    SkeletalMaterialEditorDataStripping, FProperties
+}
+
+custom_version_enum! {
+    FEditorObjectVersion, FGuid::new(0xE4B068ED, 0xF49442E9, 0xA231DA0B, 0x2E46BB41),
+    BeforeCustomVersionWasAdded, GatheredTextProcessVersionFlagging,
+    GatheredTextPackageCacheFixesV1, RootMetaDataSupport, GatheredTextPackageCacheFixesV2,
+    TextFormatArgumentDataIsVariant, SplineComponentCurvesInStruct,
+    ComboBoxControllerSupportUpdate, RefactorMeshEditorMaterials, AddedFontFaceAssets,
+    UPropertryForMeshSection, WidgetGraphSchema, AddedBackgroundBlurContentSlot,
+    StableUserDefinedEnumDisplayNames, AddedInlineFontFaceAssets,
+    UPropertryForMeshSectionSerialize, FastWidgetTemplates, MaterialThumbnailRenderingChanges,
+    NewSlateClippingSystem, MovieSceneMetaDataSerialization, GatheredTextEditorOnlyPackageLocId,
+    AddedAlwaysSignNumberFormattingOption, AddedMaterialSharedInputs,
+    AddedMorphTargetSectionIndices, SerializeInstancedStaticMeshRenderData,
+    MeshDescriptionNewSerializationMovedToRelease, MeshDescriptionNewAttributeFormat,
+    ChangeSceneCaptureRootComponent, StaticMeshDeprecatedRawMesh, MeshDescriptionBulkDataGuid,
+    MeshDescriptionRemovedHoles, ChangedWidgetComponentWindowVisibilityDefault,
+    CultureInvariantTextSerializationKeyStability, ScrollBarThicknessChange,
+    RemoveLandscapeHoleMaterial, MeshDescriptionTriangles, ComputeWeightedNormals,
+    SkeletalMeshBuildRefactor, SkeletalMeshMoveEditorSourceDataToPrivateAsset,
+    NumberParsingOptionsNumberLimitsAndClamping,
+    SkeletalMeshSourceDataSupport16bitOfMaterialNumber
+}
+
+custom_version_enum! {
+    FFrameworkObjectVersion, FGuid::new(0xCFFC743F, 0x43B04480, 0x939114DF, 0x171D2073),
+    BeforeCustomVersionWasAdded, UseBodySetupCollisionProfile, AnimBlueprintSubgraphFix,
+    MeshSocketScaleUtilization, ExplicitAttachmentRules, MoveCompressedAnimDataToTheDDC,
+    FixNonTransactionalPins, SmartNameRefactor, AddSourceReferenceSkeletonToRig,
+    ConstraintInstanceBehaviorParameters, PoseAssetSupportPerBoneMask,
+    PhysAssetUseSkeletalBodySetup, RemoveSoundWaveCompressionName,
+    AddInternalClothingGraphicalSkinning, WheelOffsetIsFromWheel, MoveCurveTypesToSkeleton,
+    CacheDestructibleOverlaps, GeometryCacheMissingMaterials,
+    LODsUseResolutionIndependentScreenSize, BlendSpacePostLoadSnapToGrid,
+    SupportBlendSpaceRateScale, LODHysteresisUseResolutionIndependentScreenSize,
+    ChangeAudioComponentOverrideSubtitlePriorityDefault, HardSoundReferences,
+    EnforceConstInAnimBlueprintFunctionGraphs, InputKeySelectorTextStyle, EdGraphPinContainerType,
+    ChangeAssetPinsToString, LocalVariablesBlueprintVisible, RemoveUfieldNext,
+    UserDefinedStructsBlueprintVisible, PinsStoreFName, UserDefinedStructsStoreDefaultInstance,
+    FunctionTerminatorNodesUseMemberReference, EditableEventsUseConstRefParameters,
+    BlueprintGeneratedClassIsAlwaysAuthoritative, EnforceBlueprintFunctionVisibility,
+    StoringUCSSerializationIndex
+}
+
+custom_version_enum! {
+    FReleaseObjectVersion, FGuid::new(0x9C54D522, 0xA8264FBE, 0x94210746, 0x61B482D0),
+    BeforeCustomVersionWasAdded, StaticMeshExtendedBoundsFix, NoSyncAsyncPhysAsset,
+    LevelTransArrayConvertedToTArray, AddComponentNodeTemplateUniqueNames,
+    UPropertryForMeshSectionSerialize, ConvertHLODScreenSize, SpeedTreeBillboardSectionInfoFixup,
+    EventSectionParameterStringAssetRef, SkyLightRemoveMobileIrradianceMap,
+    RenameNoTwistToAllowTwistInTwoBoneIK, MaterialLayersParameterSerializationRefactor,
+    AddSkeletalMeshSectionDisable, RemovedMaterialSharedInputCollection, HISMCClusterTreeMigration,
+    PinDefaultValuesVerified, FixBrokenStateMachineReferencesInTransitionGetters,
+    MeshDescriptionNewSerialization, UnclampRGBColorCurves,
+    LinkTimeAnimBlueprintRootDiscoveryBugFix, TrailNodeBlendVariableNameChange,
+    PropertiesSerializeRepCondition, FocalDistanceDisablesDOF, UnusedSoundClass2dreverbSend,
+    GroomAssetVersion1, GroomAssetVersion2, SerializeAnimModifierState, GroomAssetVersion3,
+    DeprecateFilmbackSettings, CustomImplicitCollisionType, FFieldPathOwnerSerialization,
+    ReleaseObjectVersionFixup, PinTypeIncludesUObjectWrapperFlag, WeightFMeshToMeshVertData,
+    AnimationGraphNodeBindingsDisplayedAsPins, SerializeRigVMOffsetSegmentPaths,
+    AbcVelocitiesSupport, MarginAddedToConvexAndBox, StructureDataAddedToConvex,
+    AddedFrontRightUpAxesToLiveLinkPreProcessor, FixupCopiedEventSections,
+    RemoteControlSerializeFunctionArgumentsSize, AddedSubSequenceEntryWarpCounter,
+    LonglatTextureCubeDefaultMaxResolution, GeometryCollectionCacheRemovesMassToLocal
+}
+
+custom_version_enum! {
+    FFortniteMainBranchObjectVersion, FGuid::new(0x601D1886, 0xAC644F84, 0xAA16D3DE, 0x0DEAC7D6),
+    BeforeCustomVersionWasAdded, WorldCompositionTile3DOffset,
+    MaterialInstanceSerializeOptimizationShaderFname, CullDistanceRefactorRemovedDefaultDistance,
+    CullDistanceRefactorNeverCullHlodsByDefault, CullDistanceRefactorNeverCullAlodactorsByDefault,
+    SaveGeneratedMorphTargetByEngine, ConvertReductionSettingOptions,
+    StaticParameterTerrainLayerWeightBlendType, FixUpNoneNameAnimationCurves,
+    EnsureActiveBoneIndicesToContainParents, SerializeInstancedStaticMeshRenderData,
+    CachedMaterialQualityNodeUsage, FontOutlineDropShadowFixup, NewSkeletalMeshImporterWorkflow,
+    NewLandscapeMaterialPerLOD, RemoveUnnecessaryTracksFromPose, FoliageLazyObjPtrToSoftObjPtr,
+    RevertedStoreTimelineNamesInTemplate, AddBakePoseOverrideForSkeletalMeshReductionSetting,
+    StoreTimelineNamesInTemplate, WidgetStopDuplicatingAnimations,
+    AllowSkeletalMeshToReduceTheBaseLOD, ShrinkCurveTableSize, WidgetAnimationDefaultToSelfFail,
+    FortHUDElementNowRequiresTag, FortMappedCookedAnimation, SupportVirtualBoneInRetargeting,
+    FixUpWaterMetadata, MoveWaterMetadataToActor, ReplaceLakeCollision, AnimLayerGuidConformation,
+    MakeOceanCollisionTransient, FFieldPathOwnerSerialization, FixUpUnderwaterPostProcessMaterial,
+    SupportMultipleWaterBodiesPerExclusionVolume, RigVMByteCodeDeterminism,
+    LandscapePhysicalMaterialRenderData, FixupRuntimeVirtualTextureVolume,
+    FixUpRiverCollisionComponents, FixDuplicateRiverSplineMeshCollisionComponents,
+    ContainsStableActorGUIDs, LevelsetSerializationSupportForBodySetup, ChaosSolverPropertiesMoved,
+    GameFeatureDataMovedComponentListAndCheats, ChaosClothAddfictitiousforces,
+    ChaosConvexVariableStructureDataAndVerticesArray, RemoveLandscapeWaterInfo,
+    ChaosClothAddWeightedValue, ChaosClothAddTetherStiffnessWeightMap,
+    ChaosClothFixLODTransitionMaps, ChaosClothAddTetherScaleAndDragLiftWeightMaps,
+    ChaosClothAddMaterialWeightMaps, SerializeFloatChannelShowCurve, LandscapeGrassSingleArray,
+    AddedSubSequenceEntryWarpCounter, WaterBodyComponentRefactor, BPGCCookedEditorTags,
+    TerrainLayerWeightsAreNotParameters, GravityOverrideDefinedInWorldSpace
+}
+
+custom_version_enum! {
+    FAnimPhysObjectVersion, FGuid::new(0x29E575DD, 0xE0A34627, 0x9D10D276, 0x232CDCEA),
+    BeforeCustomVersionWasAdded, ConvertAnimNodeLookAtAxis, BoxSphylElemsUseRotators,
+    ThumbnailSceneInfoAndAssetImportDataAreTransactional, AddedClothingMaskWorkflow,
+    RemoveUIDFromSmartNameSerialize, CreateTargetReference, TuneSoftLimitStiffnessAndDamping,
+    FixInvalidClothParticleMasses, CacheClothMeshInfluences,
+    SmartNameRefactorForDeterministicCooking, RenameDisableAnimCurvesToAllowAnimCurveEvaluation,
+    AddLODToCurveMetaData, FixupBadBlendProfileReferences, AllowMultipleAudioPluginSettings,
+    ChangeRetargetSourceReferenceToSoftObjectPtr, SaveEditorOnlyFullPoseForPoseAsset,
+    GeometryCacheAssetDeprecation
+}
+
+custom_version_enum! {
+    FSequencerObjectVersion, FGuid::new(0x7B5AE74C, 0xD2704C10, 0xA9585798, 0x0B212A5A),
+    BeforeCustomVersionWasAdded, RenameMediaSourcePlatformPlayers,
+    ConvertEnableRootMotionToForceRootLock, ConvertMultipleRowsToTracks,
+    WhenFinishedDefaultsToRestoreState, EvaluationTree, WhenFinishedDefaultsToProjectDefault,
+    FloatToIntConversion, PurgeSpawnableBlueprints, FinishUMGEvaluation, SerializeFloatChannel,
+    ModifyLinearKeysForOldInterp, SerializeFloatChannelCompletely, SpawnableImprovements
+}
+
+custom_version_enum! {
+    FUE5ReleaseStreamObjectVersion, FGuid::new(0xD89B5E42, 0x24BD4D46, 0x8412ACA8, 0xDF641779),
+    BeforeCustomVersionWasAdded, ReflectionMethodEnum, WorldPartitionActorDescSerializeHLODInfo,
+    RemovingTessellation, LevelInstanceSerializeRuntimeBehavior, PoseAssetRuntimeRefactor,
+    WorldPartitionActorDescSerializeActorFolderPath, HairStrandsVertexFormatChange,
+    AddChaosMaxLinearAngularSpeed, PackedLevelInstanceVersion, PackedLevelInstanceBoundsFix,
+    CustomPropertyAnimGraphNodesUseOptionalPinManager, TextFormatArgumentData64bitSupport,
+    MaterialLayerStacksAreNotParameters, MaterialInterfaceSavedCachedData, AddClothMappingLODBias,
+    AddLevelActorPackagingScheme, WorldPartitionActorDescSerializeAttachParent,
+    ConvertedActorGridPlacementToSpatiallyLoadedFlag, ActorGridPlacementDeprecateDefaultValueFixup,
+    PackedLevelActorUseWorldPartitionActorDesc, AddLevelActorFolders,
+    RemoveSkeletalMeshLODModelBulkDatas, ExcludeBrightnessFromEncodedHDRCubemap,
+    VolumetricCloudSampleCountUnification, PoseAssetRawDataGUID, ConvolutionBloomIntensity,
+    WorldPartitionHLODActorDescSerializeHLODSubActors, LargeWorldCoordinates,
+    BlueprintPinsUseRealNumbers, UpdatedDirectionalLightShadowDefaults,
+    GeometryCollectionConvexDefaults, ChaosClothFasterDamping,
+    WorldPartitionLandscapeActorDescSerializeLandscapeActorGuid,
+    AddedInertiaTensorAndRotationOfMassAddedToConvex, ChaosInertiaConvertedToVec3,
+    SerializeFloatPinDefaultValuesAsSinglePrecision, AnimLayeredBoneBlendMasks,
+    StoreReflectionCaptureEncodedHDRDataInRG11B10Format, RawAnimSequenceTrackSerializer,
+    RemoveDuplicatedStyleInfo, LinkedAnimGraphMemberReference,
+    DynamicMeshComponentsDefaultUseExternalTangents, MediaCaptureNewResizeMethods,
+    RigVMSaveDebugMapInGraphFunctionData, LocalExposureDefaultChangeFrom1,
+    WorldPartitionActorDescSerializeActorIsListedInSceneOutliner,
+    OpenColorIODisabledDisplayConfigurationDefault, WorldPartitionExternalDataLayers,
+    ChaosClothFictitiousAngularVelocitySubframeFix, SinglePrecisonParticleDataPT,
+    OrthographicAutoNearFarPlane
+}
+
+custom_version_enum! {
+    FUE5MainStreamObjectVersion, FGuid::new(0x697DD581, 0xE64F41AB, 0xAA4A51EC, 0xBEB7B628),
+    BeforeCustomVersionWasAdded, GeometryCollectionNaniteData, GeometryCollectionNaniteDDC,
+    RemovingSourceAnimationData, MeshDescriptionNewFormat, PartitionActorDescSerializeGridGuid,
+    ExternalActorsMapDataPackageFlag, AnimationAddedBlendProfileModes,
+    WorldPartitionActorDescSerializeDataLayers, RenamingAnimationNumFrames,
+    WorldPartitionHLODActorDescSerializeHLODLayer, GeometryCollectionNaniteCooked,
+    AddedCookedBoolFontFaceAssets, WorldPartitionHLODActorDescSerializeCellHash,
+    GeometryCollectionNaniteTransient, AddedLandscapeSplineActorDesc, AddCollisionConstraintFlag,
+    MantleDbSerialize, AnimSyncGroupsExplicitSyncMethod, FLandscapeActorDescFixupGridIndices,
+    FoliageTypeIncludeInHLOD, IntroducingAnimationDataModel,
+    WorldPartitionActorDescSerializeActorLabel, WorldPartitionActorDescSerializeArchivePersistent,
+    FixForceExternalActorLevelReferenceDuplicates, SerializeMeshDescriptionBase,
+    ConvexUsesVerticesArray, WorldPartitionActorDescSerializeHLODInfo, AddDisabledFlag,
+    MoveCustomAttributesToDataModel, BlendSpaceRuntimeTriangulation,
+    BlendSpaceSmoothingImprovements, RemovingTessellationParameters,
+    SparseClassDataStructSerialization, PackedLevelInstanceBoundsFix,
+    AnimNodeConstantDataRefactorPhase0, MaterialSavedCachedData, RemoveDecalBlendMode,
+    DirLightsAreAtmosphereLightsByDefault, WorldPartitionStreamingCellsNamingShortened,
+    WorldPartitionActorDescGetStreamingBounds, MeshDescriptionVirtualization,
+    TextureSourceVirtualization
+}
+
+pub static KNOWN_CUSTOM_VERSIONS: &[CustomVersionInfo] = &[
+    FCoreObjectVersion::INFO,
+    FEditorObjectVersion::INFO,
+    FFrameworkObjectVersion::INFO,
+    FReleaseObjectVersion::INFO,
+    FFortniteMainBranchObjectVersion::INFO,
+    FAnimPhysObjectVersion::INFO,
+    FSequencerObjectVersion::INFO,
+    FUE5ReleaseStreamObjectVersion::INFO,
+    FUE5MainStreamObjectVersion::INFO,
+    CustomVersionInfo::new("FBlueprintsObjectVersion", FGuid::new(0xB0D832E4, 0x1F894F0D, 0xACCF7EB7, 0x36FD4AA2)),
+    CustomVersionInfo::new("FBuildObjectVersion", FGuid::new(0xE1C64328, 0xA22C4D53, 0xA36C8E86, 0x6417BD8C)),
+    CustomVersionInfo::new("FMobileObjectVersion", FGuid::new(0xB02B49B5, 0xBB2044E9, 0xA30432B7, 0x52E40360)),
+    CustomVersionInfo::new("FNetworkingObjectVersion", FGuid::new(0xA4E4105C, 0x59A149B5, 0xA7C540C4, 0x547EDFEE)),
+    CustomVersionInfo::new("FOnlineObjectVersion", FGuid::new(0x39C831C9, 0x5AE647DC, 0x9A449C17, 0x3E1C8E7C)),
+    CustomVersionInfo::new("FPhysicsObjectVersion", FGuid::new(0x78F01B33, 0xEBEA4F98, 0xB9B484EA, 0xCCB95AA2)),
+    CustomVersionInfo::new("FPlatformObjectVersion", FGuid::new(0x6631380F, 0x2D4D43E0, 0x8009CF27, 0x6956A95A)),
+    CustomVersionInfo::new("FRenderingObjectVersion", FGuid::new(0x12F88B9F, 0x88754AFC, 0xA67CD90C, 0x383ABD29)),
+    CustomVersionInfo::new("FVRObjectVersion", FGuid::new(0xD7296918, 0x1DD64BDD, 0x9DE264A8, 0x3CC13884)),
+    CustomVersionInfo::new("FLoadTimesObjectVersion", FGuid::new(0xC2A15278, 0xBFE74AFE, 0x6C1790FF, 0x531DF755)),
+    CustomVersionInfo::new("FGeometryObjectVersion", FGuid::new(0x6EACA3D4, 0x40EC4CC1, 0xB7868BED, 0x09428FC5)),
+    CustomVersionInfo::new("FAnimObjectVersion", FGuid::new(0xAF43A65D, 0x7FD34947, 0x98733E8E, 0xD9C1BB05)),
+    CustomVersionInfo::new("FReflectionCaptureObjectVersion", FGuid::new(0x6B266CEC, 0x1EC74B8F, 0xA30BE4D9, 0x0942FC07)),
+    CustomVersionInfo::new("FAutomationObjectVersion", FGuid::new(0x0DF73D61, 0xA23F47EA, 0xB72789E9, 0x0C41499A)),
+    CustomVersionInfo::new("FEnterpriseObjectVersion", FGuid::new(0x9DFFBCD6, 0x494F0158, 0xE2211282, 0x3C92A888)),
+    CustomVersionInfo::new("FNiagaraObjectVersion", FGuid::new(0xF2AED0AC, 0x9AFE416F, 0x8664AA7F, 0xFA26D6FC)),
+    CustomVersionInfo::new("FDestructionObjectVersion", FGuid::new(0x174F1F0B, 0xB4C645A5, 0xB13F2EE8, 0xD0FB917D)),
+    CustomVersionInfo::new("FExternalPhysicsCustomObjectVersion", FGuid::new(0x35F94A83, 0xE258406C, 0xA31809F5, 0x9610247C)),
+    CustomVersionInfo::new("FExternalPhysicsMaterialCustomObjectVersion", FGuid::new(0xB68FC16E, 0x8B1B42E2, 0xB453215C, 0x058844FE)),
+    CustomVersionInfo::new("FCineCameraObjectVersion", FGuid::new(0xB2E18506, 0x4273CFC2, 0xA54EF4BB, 0x758BBA07)),
+    CustomVersionInfo::new("FVirtualProductionObjectVersion", FGuid::new(0x64F58936, 0xFD1B42BA, 0xBA967289, 0xD5D0FA4E)),
+    CustomVersionInfo::new("FMediaFrameworkObjectVersion", FGuid::new(0x6F0ED827, 0xA6094895, 0x9C91998D, 0x90180EA4)),
+    CustomVersionInfo::new("FPoseDriverCustomVersion", FGuid::new(0xAFE08691, 0x3A0D4952, 0xB673673B, 0x7CF22D1E)),
+    CustomVersionInfo::new("FTempCustomVersion", FGuid::new(0xCB8AB0CD, 0xE78C4BDE, 0xA8621393, 0x14E9EF62)),
+    CustomVersionInfo::new("FAnimationCustomVersion", FGuid::new(0x2EB5FDBD, 0x01AC4D10, 0x8136F38F, 0x3393A5DA)),
+    CustomVersionInfo::new("FAssetRegistryVersion", FGuid::new(0x717F9EE7, 0xE9B0493A, 0x88B39132, 0x1B388107)),
+    CustomVersionInfo::new("FClothingAssetCustomVersion", FGuid::new(0xFB680AF2, 0x59EF4BA3, 0xBAA819B5, 0x73C8443D)),
+    CustomVersionInfo::new("FParticleSystemCustomVersion", FGuid::new(0x4A56EB40, 0x10F511DC, 0x92D3347E, 0xB2C96AE7)),
+    CustomVersionInfo::new("FSkeletalMeshCustomVersion", FGuid::new(0xD78A4A00, 0xE8584697, 0xBAA819B5, 0x487D46B4)),
+    CustomVersionInfo::new("FRecomputeTangentCustomVersion", FGuid::new(0x5579F886, 0x933A4C1F, 0x83BA087B, 0x6361B92F)),
+    CustomVersionInfo::new("FOverlappingVerticesCustomVersion", FGuid::new(0x612FBE52, 0xDA53400B, 0x910D4F91, 0x9FB1857C)),
+    CustomVersionInfo::new("FFoliageCustomVersion", FGuid::new(0x430C4D19, 0x71544970, 0x87699B69, 0xDF90B0E5)),
+    CustomVersionInfo::new("FProceduralFoliageCustomVersion", FGuid::new(0xAAFE32BD, 0x53954C14, 0xB66A5E25, 0x1032D1DD)),
+    CustomVersionInfo::new("FLiveLinkCustomVersion", FGuid::new(0xAB965196, 0x45D808FC, 0xB7D7228D, 0x78AD569E))
+];
+
+pub fn find(name: &str) -> Option<&'static CustomVersionInfo> {
+    KNOWN_CUSTOM_VERSIONS.iter().find(|info| info.name == name)
+}
+
+pub fn find_by_guid(guid: &FGuid) -> Option<&'static CustomVersionInfo> {
+    KNOWN_CUSTOM_VERSIONS.iter().find(|info| &info.guid == guid)
+}
@@ -1,29 +1,40 @@
+use std::fmt;
 use std::io::{Cursor, Read};
-use std::marker::PhantomData;
 
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, de};
-use serde::de::{DeserializeSeed, SeqAccess, Visitor, value};
-
-use crate::error::{Result, Error};
+use serde::de::{DeserializeSeed, IntoDeserializer, SeqAccess, Visitor};
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+
+use crate::error::{Result, Diagnostic, Error, ErrorCode, PathSegment};
+use crate::fstring::read_fstring;
+use crate::hint::Hint;
+use crate::patch::Element;
+use crate::property::{self, with_codec, PropertyCodec, PropertyType, Tag};
 use crate::types::FGuid;
-use crate::{parse_num, unimplemented_deserialize};
+use crate::structs::StructRegistry;
+use crate::value::{ArrayValue, ByteValue, StructValue, Value};
 
 struct ArrayAccess<'a, 'de: 'a> {
     de: &'a mut Deserializer<'de>,
     elements: i32,
+    index: usize,
     t: String,
-    struct_type: Option<String>
+    struct_type: Option<String>,
+    /// Elements of a byte array are enum labels
+    labels: bool
 }
 
 
 impl<'a, 'de: 'a> ArrayAccess<'a, 'de> {
-    pub fn new(de: &'a mut Deserializer<'de>, elements: i32, t: String, struct_type: Option<String>) -> Self {
+    pub fn new(de: &'a mut Deserializer<'de>, elements: i32, t: String, struct_type: Option<String>, labels: bool) -> Self {
         ArrayAccess {
             de,
             elements,
+            index: 0,
             t,
-            struct_type
+            struct_type,
+            labels
         }
     }
 }
@@ -39,8 +50,179 @@ impl<'a, 'de: 'a> SeqAccess<'de> for ArrayAccess<'a, 'de> {
         }
         self.elements -= 1;
 
-        let mut ad = ArrayDeserializer::new(&mut *self.de, &self.t, &self.struct_type);
-        seed.deserialize(&mut ad).map(Some)
+        let start = self.de.input.position();
+        self.de.path.push(PathSegment::Index(self.index));
+        self.index += 1;
+
+        let mut ad = ArrayDeserializer::new(&mut *self.de, &self.t, &self.struct_type, self.labels);
+        let value = seed.deserialize(&mut ad).map(Some);
+        let value = value.map_err(|e| e.at(start, &self.de.path));
+        self.de.path.pop();
+        value
+    }
+}
+
+/// Hands a value read by a [`PropertyCodec`] to a visitor
+trait Visit {
+    fn visit<'de, V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>;
+}
+
+macro_rules! visit {
+    ($($value:ty => $method:ident),*) => {
+        $(impl Visit for $value {
+            fn visit<'de, V>(self, visitor: V) -> Result<V::Value>
+            where
+                V: Visitor<'de> {
+                visitor.$method(self)
+            }
+        })*
+    };
+}
+
+visit!(bool => visit_bool, i8 => visit_i8, i16 => visit_i16, u16 => visit_u16, i32 => visit_i32, u32 => visit_u32,
+    i64 => visit_i64, u64 => visit_u64, f32 => visit_f32, f64 => visit_f64, String => visit_string);
+
+impl Visit for ByteValue {
+    fn visit<'de, V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        match self {
+            ByteValue::Byte(value) => visitor.visit_u8(value),
+            ByteValue::Label(label) => visitor.visit_string(label)
+        }
+    }
+}
+
+impl Visit for [String; 2] {
+    fn visit<'de, V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        visitor.visit_seq(SeqDeserializer::new(self.into_iter()))
+    }
+}
+
+/// Enum values are stored as their name, or as their index in a `ByteProperty` without an enum
+struct EnumLabel<V>(V);
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for EnumLabel<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    fn visit_u8<E>(self, v: u8) -> std::result::Result<Self::Value, E>
+    where
+        E: de::Error {
+        self.0.visit_enum((v as u32).into_deserializer())
+    }
+
+    fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+    where
+        E: de::Error {
+        self.0.visit_enum(v.into_deserializer())
+    }
+
+    fn visit_string<E>(self, v: String) -> std::result::Result<Self::Value, E>
+    where
+        E: de::Error {
+        self.0.visit_enum(v.into_deserializer())
+    }
+}
+
+/// Hands values from the value tree to serde: fields decoded by a registered struct codec, see
+/// [`crate::structs`], sets and maps, and everything read while recovering
+struct ValueDeserializer(Value);
+
+impl<'de> IntoDeserializer<'de, Error> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+fn visit_struct<'de, V>(value: StructValue, visitor: V) -> Result<V::Value>
+where
+    V: Visitor<'de> {
+    match value {
+        StructValue::DateTime(ticks) => visitor.visit_u64(ticks),
+        StructValue::Timespan(ticks) => visitor.visit_i64(ticks),
+        StructValue::Guid(guid) => visitor.visit_map(MapDeserializer::<_, Error>::new(
+            [("a", guid.a), ("b", guid.b), ("c", guid.c), ("d", guid.d)].into_iter())),
+        StructValue::Properties(fields) => visitor.visit_map(MapDeserializer::new(
+            fields.into_iter().map(|field| (field.name, ValueDeserializer(field.value)))))
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        match self.0 {
+            Value::Bool(value) => value.visit(visitor),
+            Value::Int8(value) => value.visit(visitor),
+            Value::Int16(value) => value.visit(visitor),
+            Value::UInt16(value) => value.visit(visitor),
+            Value::Int(value) => value.visit(visitor),
+            Value::UInt32(value) => value.visit(visitor),
+            Value::Int64(value) => value.visit(visitor),
+            Value::UInt64(value) => value.visit(visitor),
+            Value::Float(value) => value.visit(visitor),
+            Value::Double(value) => value.visit(visitor),
+            Value::Byte { value, .. } => value.visit(visitor),
+            Value::Enum { value, .. } | Value::Str(value) | Value::Name(value) | Value::Object(value) => value.visit(visitor),
+            Value::SoftObject(value) => value.visit(visitor),
+            Value::Struct { value, .. } => visit_struct(value, visitor),
+            Value::Array { value: ArrayValue::Bytes(bytes), .. } => visitor.visit_byte_buf(bytes),
+            Value::Array { value: ArrayValue::Values(values), .. } =>
+                visitor.visit_seq(SeqDeserializer::new(values.into_iter().map(ValueDeserializer))),
+            Value::Array { value: ArrayValue::Structs { struct_name, guid, elements, .. }, .. } =>
+                visitor.visit_seq(SeqDeserializer::new(elements.into_iter()
+                    .map(|value| ValueDeserializer(Value::Struct { struct_name: struct_name.clone(), guid, value })))),
+            Value::Set { elements, .. } => visitor.visit_seq(SeqDeserializer::new(elements.into_iter().map(ValueDeserializer))),
+            Value::Map { entries, .. } => visitor.visit_map(MapDeserializer::new(entries.into_iter()
+                .map(|(key, value)| (ValueDeserializer(key), ValueDeserializer(value))))),
+            Value::Unknown { data, .. } => visitor.visit_byte_buf(data)
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Byte arrays are read as bytes, but also make a sequence of numbers
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        match self.0 {
+            Value::Array { value: ArrayValue::Bytes(bytes), .. } => visitor.visit_seq(SeqDeserializer::new(bytes.into_iter())),
+            value => ValueDeserializer(value).deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_enum<V>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        self.deserialize_any(EnumLabel(visitor))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple
+        tuple_struct map struct identifier ignored_any
     }
 }
 
@@ -67,12 +249,12 @@ impl<'a, 'de> de::Deserializer<'de> for MapKey<'a, 'de> {
 
 struct MapAccess<'a, 'de: 'a> {
     de: &'a mut Deserializer<'de>,
-    i: i32
+    entries: usize
 }
 
 impl<'a, 'de> MapAccess<'a, 'de> {
-    fn new(de: &'a mut Deserializer<'de>, i: i32) -> Self {
-        MapAccess { de, i }
+    fn new(de: &'a mut Deserializer<'de>) -> Self {
+        MapAccess { de, entries: 0 }
     }
 }
 
@@ -87,6 +269,8 @@ impl<'de, 'a> serde::de::MapAccess<'de> for MapAccess<'a, 'de> {
             self.de.parse_string()?;
             return Ok(None);
         }
+        self.entries += 1;
+        check_limit("struct properties", self.de.options.max_struct_properties as u64, self.entries as u64)?;
         seed.deserialize(MapKey {de: &mut *self.de}).map(Some)
     }
 
@@ -100,22 +284,20 @@ impl<'de, 'a> serde::de::MapAccess<'de> for MapAccess<'a, 'de> {
 struct ArrayDeserializer<'a, 'de: 'a> {
     de: &'a mut Deserializer<'de>,
     t: &'a String,
-    struct_type: &'a Option<String>
+    struct_type: &'a Option<String>,
+    labels: bool
 }
 
 impl<'a, 'de> ArrayDeserializer<'a, 'de> {
-    fn new(de: &'a mut Deserializer<'de>, t: &'a String, struct_type: &'a Option<String>) -> Self {
-        ArrayDeserializer { de, t, struct_type } 
+    fn new(de: &'a mut Deserializer<'de>, t: &'a String, struct_type: &'a Option<String>, labels: bool) -> Self {
+        ArrayDeserializer { de, t, struct_type, labels }
     }
 
     fn deserialize_struct<V>(&mut self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de> {
         if let Some(struct_type) = &self.struct_type {
-            match struct_type.as_str() {
-                "DateTime" => visitor.visit_u64(self.de.input.read_u64::<LittleEndian>()?),
-                _ => visitor.visit_map(MapAccess::new(&mut *self.de, -1))
-            }
+            self.de.deserialize_struct_value(struct_type, visitor)
         } else {
             Err(Error::make_other(String::from("Trying to deserialize struct without specifying the type!")))
         }
@@ -130,128 +312,200 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut ArrayDeserializer<'a, 'de> {
         i8 u8 i16 u16 i32 u32 i64 u64 f32 f64 bool string ignored_any
     }
 
-    unimplemented_deserialize!(deserialize_char, 
-        deserialize_str, deserialize_bytes, deserialize_byte_buf, deserialize_option, deserialize_unit, deserialize_map, deserialize_identifier);
+    unsupported_deserialize!(deserialize_char, 
+        deserialize_str, deserialize_bytes, deserialize_byte_buf, deserialize_unit, deserialize_map, deserialize_identifier);
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        visitor.visit_some(self)
+    }
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de> {
-        match self.t.as_str() {
-            "StructProperty" => self.deserialize_struct(visitor),
-            "Int8Property" => visitor.visit_i8(self.de.input.read_i8()?),
-            "ByteProperty" => visitor.visit_u8(self.de.input.read_u8()?),
-            "Int16Property" => visitor.visit_i16(self.de.input.read_i16::<LittleEndian>()?),
-            "UInt16Property" => visitor.visit_u16(self.de.input.read_u16::<LittleEndian>()?),
-            "IntProperty" => visitor.visit_i32(self.de.input.read_i32::<LittleEndian>()?),
-            "UInt32Property" => visitor.visit_u32(self.de.input.read_u32::<LittleEndian>()?),
-            "Int64Property" => visitor.visit_i64(self.de.input.read_i64::<LittleEndian>()?),
-            "UInt64Property" => visitor.visit_u64(self.de.input.read_u64::<LittleEndian>()?),
-            "FloatProperty" => visitor.visit_f32(self.de.input.read_f32::<LittleEndian>()?),
-            "DoubleProperty" => visitor.visit_f64(self.de.input.read_f64::<LittleEndian>()?),
-            "StrProperty" => visitor.visit_string(self.de.parse_string()?),
-            "BoolProperty" => visitor.visit_bool(self.de.input.read_u8()? == 1),
-            _ => Err(Error::make_other(format!("Unkown property type {}", self.t)))
+        match PropertyType::from_name(self.t) {
+            PropertyType::Struct => self.deserialize_struct(visitor),
+            PropertyType::Byte if self.labels => visitor.visit_string(self.de.parse_string()?),
+            property_type => with_codec!(&property_type, C => C::read_element(self.de)?.visit(visitor), _ => Err(property::unknown(&property_type)))
         }
     }
 
     fn deserialize_unit_struct<V>(self, _: &'static str, _: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_unit_struct"))
     }
 
-    fn deserialize_newtype_struct<V>(self, _: &'static str, _: V) -> Result<V::Value> where
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        if let Some(hint) = Hint::parse(name) {
+            hint.check_tag(self.t, None, None, self.struct_type.as_deref())?;
+        }
+        visitor.visit_newtype_struct(self)
     }
 
-    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value> where
+    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_seq"))
     }
 
-    fn deserialize_tuple<V>(self, _: usize, _: V) -> Result<V::Value> where
+    fn deserialize_tuple<V>(self, _: usize, visitor: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        self.deserialize_any(visitor)
     }
 
     fn deserialize_tuple_struct<V>(self, _: &'static str, _: usize, _: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_tuple_struct"))
     }
 
     fn deserialize_struct<V>(self, _name: &'static str, _fields: &'static [&'static str], visitor: V) -> Result<V::Value> where
         V: Visitor<'de> {
-            return visitor.visit_map(MapAccess::new(&mut *self.de, -1));
+        match self.struct_type {
+            Some(struct_type) => self.de.deserialize_struct_value(struct_type, visitor),
+            None => self.de.nested(|de| visitor.visit_map(MapAccess::new(de)))
+        }
     }
 
-    fn deserialize_enum<V>(self, _: &'static str, _: &'static [&'static str], _: V) -> Result<V::Value> where
+    fn deserialize_enum<V>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        self.deserialize_any(EnumLabel(visitor))
     }
 
 }
 
+/// Limits applied while reading, so untrusted files can't make the deserializer
+/// allocate unbounded memory or recurse until the stack overflows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializerOptions {
+    /// Maximum length of a single string, in bytes (or UTF-16 code units)
+    pub max_string_length: usize,
+    /// Maximum number of elements in an array
+    pub max_array_length: usize,
+    /// Maximum number of entries in a map or elements in a set
+    pub max_map_length: usize,
+    /// Maximum number of properties in a single struct
+    pub max_struct_properties: usize,
+    /// Maximum struct/array nesting depth
+    pub max_depth: usize,
+    /// Maximum size of the input that will be parsed, in bytes
+    pub max_total_size: u64,
+    /// When a value doesn't take up exactly its declared size, seek to where it
+    /// should have ended and record a diagnostic instead of failing
+    pub lenient: bool,
+    /// Skip properties that can't be decoded and record a diagnostic instead of failing.
+    /// Skipped values are passed to serde as bytes, or kept as [`crate::value::Value::Unknown`].
+    pub recover: bool,
+    /// Structs with a layout of their own, see [`crate::structs`]
+    pub structs: StructRegistry
+}
+
+impl Default for DeserializerOptions {
+    fn default() -> Self {
+        DeserializerOptions {
+            max_string_length: 16 * 1024 * 1024,
+            max_array_length: 16 * 1024 * 1024,
+            max_map_length: 1024 * 1024,
+            max_struct_properties: 1024 * 1024,
+            max_depth: 128,
+            max_total_size: 1024 * 1024 * 1024,
+            lenient: false,
+            recover: false,
+            structs: StructRegistry::default()
+        }
+    }
+}
+
+impl DeserializerOptions {
+    /// No limits at all, only use this for trusted input.
+    pub fn unlimited() -> Self {
+        DeserializerOptions {
+            max_string_length: usize::MAX,
+            max_array_length: usize::MAX,
+            max_map_length: usize::MAX,
+            max_struct_properties: usize::MAX,
+            max_depth: usize::MAX,
+            max_total_size: u64::MAX,
+            lenient: false,
+            recover: false,
+            structs: StructRegistry::default()
+        }
+    }
+
+    pub(crate) fn check_total_size(&self, input: &Cursor<Vec<u8>>) -> Result<()> {
+        let size = (input.get_ref().len() as u64).saturating_sub(input.position());
+        check_limit("total size", self.max_total_size, size)
+    }
+}
+
+pub(crate) fn check_limit(limit: &'static str, max: u64, actual: u64) -> Result<()> {
+    if actual > max {
+        return Err(Error::new(ErrorCode::LimitExceeded { limit, max, actual }));
+    }
+    Ok(())
+}
+
 pub struct Deserializer<'de> {
-    input: &'de mut Cursor<Vec<u8>>,
-    first: bool
+    pub(crate) input: &'de mut Cursor<Vec<u8>>,
+    first: bool,
+    pub(crate) path: Vec<PathSegment>,
+    pub(crate) options: DeserializerOptions,
+    depth: usize,
+    pub(crate) diagnostics: Vec<Diagnostic>
 }
 
 impl<'de> Deserializer<'de> {
     pub fn from_bytes(input: &'de mut Cursor<Vec<u8>>) -> Self {
-        Deserializer { input, first: true }
+        Deserializer::with_options(input, DeserializerOptions::default())
+    }
+
+    pub fn with_options(input: &'de mut Cursor<Vec<u8>>, options: DeserializerOptions) -> Self {
+        Deserializer { input, first: true, path: Vec::new(), options, depth: 0, diagnostics: Vec::new() }
+    }
+
+    /// Reads a single tagged property, where [`Deserializer::with_options`] reads a whole property list
+    pub(crate) fn for_property(input: &'de mut Cursor<Vec<u8>>, options: DeserializerOptions) -> Self {
+        Deserializer { first: false, ..Deserializer::with_options(input, options) }
+    }
+
+    /// Problems that were worked around so far, see [`DeserializerOptions::lenient`]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics
     }
 }
 
 pub fn from_bytes<'a, T>(input: &'a mut Cursor<Vec<u8>>) -> Result<T> where
     T: Deserialize<'a> {
-        let mut deserializer = Deserializer::from_bytes(input);
-        let t = T::deserialize(&mut deserializer)?;
-        Ok(t)
+        from_bytes_with_options(input, DeserializerOptions::default())
 }
 
+pub fn from_bytes_with_options<'a, T>(input: &'a mut Cursor<Vec<u8>>, options: DeserializerOptions) -> Result<T> where
+    T: Deserialize<'a> {
+        Ok(from_bytes_with_diagnostics(input, options)?.0)
+}
 
-impl<'de> Deserializer<'de> {
-
-    fn parse_u8_property(&mut self) -> Result<u8> {
-        let value_size = self.input.read_i64::<LittleEndian>()?;
-        if value_size != 1 {
-            return Err(Error::make_other(format!("Expected value size of 1 got {} at position {}", value_size, self.input.position())));
-        }
+pub fn from_bytes_with_diagnostics<'a, T>(input: &'a mut Cursor<Vec<u8>>, options: DeserializerOptions) -> Result<(T, Vec<Diagnostic>)> where
+    T: Deserialize<'a> {
+        let start = input.position();
+        options.check_total_size(input).map_err(|e| e.at(start, &[]))?;
+        let mut deserializer = Deserializer::with_options(input, options);
+        let t = T::deserialize(&mut deserializer).map_err(|e| e.at(start, &[]))?;
+        Ok((t, deserializer.into_diagnostics()))
+}
 
-        // this is unique for byteproperty it seems?
-        let _unk = self.parse_string()?; // todo: what is that even for? enum name?
-        self.input.read_exact(&mut [0u8; 1])?; // skipping another byte for some reason
 
-        Ok(self.input.read_u8()?)
-    }
+impl<'de> Deserializer<'de> {
 
-    fn parse_bool_property(&mut self) -> Result<bool> {
-        let value_size = self.input.read_i64::<LittleEndian>()?;
-        // 0 for some reason on boolproperty
-        if value_size != 0 {
-            return Err(Error::make_other(format!("Expected value size of 0 got {} at position {}", value_size, self.input.position())));
-        }
-        
-        let val = self.input.read_u16::<LittleEndian>()?;
-        Ok(val > 0)
-    }
-
-    parse_number_property!(parse_i8_property, read_i8, i8, 1);
-    parse_number_property!(parse_i16_property, read_i16, LittleEndian, i16, 2);
-    parse_number_property!(parse_u16_property, read_u16, LittleEndian, u16, 2);
-    parse_number_property!(parse_i32_property, read_i32, LittleEndian, i32, 4);
-    parse_number_property!(parse_u32_property, read_u32, LittleEndian, u32, 4);
-    parse_number_property!(parse_i64_property, read_i64, LittleEndian, i64, 8);
-    parse_number_property!(parse_u64_property, read_u64, LittleEndian, u64, 8);
-    parse_number_property!(parse_f32_property, read_f32, LittleEndian, f32, 4);
-    parse_number_property!(parse_f64_property, read_f64, LittleEndian, f64, 8);
-    
     fn peek_string(&mut self) -> Result<String> {
         let pos = self.input.position();
         let s = self.parse_string()?;
@@ -259,80 +513,198 @@ impl<'de> Deserializer<'de> {
         Ok(s)
     }
         
-    fn parse_string(&mut self) -> Result<String> {
-        //todo: maybe length checking?
-        let len = self.input.read_i32::<LittleEndian>()?;
-        
-        let mut str_bytes = vec![0u8; len as usize];
-        self.input.read_exact(&mut str_bytes)?;
-        let mut s = String::from_utf8(str_bytes)?;
-        s.pop(); // nullbyte
-        Ok(s)
+    pub(crate) fn parse_string(&mut self) -> Result<String> {
+        read_fstring(self.input, self.options.max_string_length)
     }
 
-    fn parse_string_property(&mut self) -> Result<String> {
-        let _value_size = self.input.read_i64::<LittleEndian>()?;
-        self.input.read_exact(&mut [0u8; 1])?;
+    pub(crate) fn remaining(&self) -> u64 {
+        (self.input.get_ref().len() as u64).saturating_sub(self.input.position())
+    }
 
-        self.parse_string()
+    /// Reads a value that was declared to take up `size` bytes from the current position
+    /// and checks that it really did.
+    pub(crate) fn sized<T, F>(&mut self, size: i64, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Self) -> Result<T> {
+        let start = self.input.position();
+        let value = f(self)?;
+        let actual = (self.input.position() - start) as i64;
+        if actual != size {
+            let code = ErrorCode::SizeMismatch { expected: size, actual };
+            let end = start.checked_add_signed(size).filter(|end| *end <= self.input.get_ref().len() as u64);
+            match end {
+                Some(end) if self.options.lenient => {
+                    self.diagnostics.push(Diagnostic::new(code, start, &self.path));
+                    self.input.set_position(end);
+                },
+                _ => return Err(Error::new(code))
+            }
+        }
+        Ok(value)
     }
 
-    fn parse_guid(&mut self) -> Result<FGuid> {
+    pub(crate) fn nested<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(&mut Self) -> Result<T> {
+        check_limit("nesting depth", self.options.max_depth as u64, self.depth as u64 + 1)?;
+        self.depth += 1;
+        let value = f(self);
+        self.depth -= 1;
+        value
+    }
+
+    pub(crate) fn parse_guid(&mut self) -> Result<FGuid> {
         let guid = FGuid::new(self.input.read_u32::<LittleEndian>()?, self.input.read_u32::<LittleEndian>()?, self.input.read_u32::<LittleEndian>()?, self.input.read_u32::<LittleEndian>()?);
         Ok(guid)
     }
 
-    fn deserialize_struct<V>(&mut self, visitor: V) -> Result<V::Value> 
+    /// Values of structs with properties, or of structs stored as a plain value
+    fn deserialize_struct_value<V>(&mut self, struct_name: &str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        if let Some(codec) = self.options.structs.codec(struct_name) {
+            return visit_struct(StructValue::Properties(codec.decode(self.input)?), visitor);
+        }
+        match struct_name {
+            "DateTime" | "Timespan" | "Guid" => visit_struct(self.read_struct_value(struct_name)?, visitor),
+            _ => self.nested(|de| visitor.visit_map(MapAccess::new(de)))
+        }
+    }
+
+    fn deserialize_array<V>(&mut self, value_type: String, size: i64, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de> {
-        let _struct_value_len = self.input.read_i64::<LittleEndian>()?;
-        let value_type = self.parse_string()?;
-        let _struct_guid = self.parse_guid()?;
-        self.input.read_exact(&mut [0u8; 1])?; // terminator
-
-        match value_type.as_str() {
-            "DateTime" => visitor.visit_u64(self.input.read_u64::<LittleEndian>()?),
-            _ => visitor.visit_map(MapAccess::new(self, 1))
+        let elements = self.input.read_i32::<LittleEndian>()?;
+        if elements < 0 {
+            return Err(Error::make_data(format!("Invalid array length {}", elements)));
+        }
+        check_limit("array length", self.options.max_array_length as u64, elements as u64)?;
+
+        match self.array_element(&value_type, size as u64, elements as usize)? {
+            Element::Struct(tag) => {
+                self.sized(tag.size as i64, |de| de.nested(|de| visitor.visit_seq(ArrayAccess::new(de, elements, value_type, Some(tag.struct_name), false))))
+            },
+            Element::Label => self.nested(|de| visitor.visit_seq(ArrayAccess::new(de, elements, value_type, None, true))),
+            _ => self.nested(|de| visitor.visit_seq(ArrayAccess::new(de, elements, value_type, None, false)))
         }
     }
 
-    fn deserialize_array<V>(&mut self, visitor: V) -> Result<V::Value>
+    fn deserialize_property<V>(&mut self, start: u64, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de> {
-        let struct_value_len = self.input.read_i64::<LittleEndian>()?;
-        let value_type = self.parse_string()?;
-
-        self.input.read_exact(&mut [0u8; 1])?;
-        let elements = match value_type.as_str() {
-            "StructProperty" => {
-                let len = self.input.read_i32::<LittleEndian>()?;
-
-                let dup_var_name = self.parse_string()?;
-                let dup_type_name = self.parse_string()?;
-                let dup_value_len = self.input.read_i64::<LittleEndian>()?;
-                let struct_name = self.parse_string()?;
-                let _struct_guid = self.parse_guid()?;
-
-                self.input.read_exact(&mut [0u8; 1])?; //terminator
-                len
+        let type_name = self.parse_string()?;
+        let property_type = PropertyType::from_name(&type_name);
+        let size = self.input.read_i32::<LittleEndian>()? as i64;
+        let _array_index = self.input.read_i32::<LittleEndian>()?;
+        let tag = property_type.read_tag(self)?;
+        if self.input.read_u8()? != 0 {
+            self.parse_guid()?;
+        }
+
+        match tag {
+            // once a visitor has started on a value it can't be handed the raw bytes anymore, so when recovering
+            // structs and arrays are read into the value tree first, where broken values further down are kept
+            Tag::Struct(..) | Tag::Array(..) if self.options.recover => self.visit_value(start, &type_name, tag, size, visitor),
+            Tag::Struct(struct_name, _) => self.sized(size, |de| de.deserialize_struct_value(&struct_name, visitor)),
+            Tag::Array(element_type) => self.sized(size, |de| de.deserialize_array(element_type, size, visitor)),
+            // elements of sets and maps aren't preceded by their types, so they go through the value tree
+            Tag::Set(_) | Tag::Map(..) => self.visit_value(start, &type_name, tag, size, visitor),
+            tag => with_codec!(&property_type, C => self.sized(size, |de| C::read_value(de, &tag))?.visit(visitor), _ => match self.options.recover {
+                true => visitor.visit_byte_buf(self.skip_value(start, &property_type, size)?),
+                false => Err(property::unknown(&property_type))
+            })
+        }
+    }
+
+    /// Checks the upcoming property against the types a newtype struct name asks for, without consuming it
+    fn check_hint(&mut self, hint: &Hint) -> Result<()> {
+        let start = self.input.position();
+        let name = self.parse_string()?;
+        self.path.push(PathSegment::Field(name));
+        let result = self.check_tag(hint).map_err(|e| e.at(start, &self.path));
+        self.path.pop();
+        self.input.set_position(start);
+        result
+    }
+
+    fn check_tag(&mut self, hint: &Hint) -> Result<()> {
+        let type_name = self.parse_string()?;
+        let _value_size = self.input.read_i32::<LittleEndian>()?;
+        let _array_index = self.input.read_i32::<LittleEndian>()?;
+        match PropertyType::from_name(&type_name).read_tag(self)? {
+            Tag::Enum(enum_name) => hint.check_tag(&type_name, None, Some(&enum_name), None),
+            Tag::Struct(struct_name, _) => hint.check_tag(&type_name, None, None, Some(&struct_name)),
+            Tag::Array(element_type) => {
+                let struct_name = match element_type.as_str() {
+                    "StructProperty" => {
+                        if self.input.read_u8()? != 0 {
+                            self.parse_guid()?;
+                        }
+                        let _elements = self.input.read_i32::<LittleEndian>()?;
+                        Some(self.struct_array_tag()?.struct_name)
+                    },
+                    _ => None
+                };
+                hint.check_tag(&type_name, Some(&element_type), None, struct_name.as_deref())
             },
-            _ => self.input.read_i32::<LittleEndian>()?
-        };
-        visitor.visit_seq(ArrayAccess::new(self, elements, value_type, Some(String::from("TestStruct"))))
+            _ => hint.check_tag(&type_name, None, None, None)
+        }
+    }
+
+    /// Reads the raw value of a property we can't decode, its tag was read already
+    fn skip_value(&mut self, start: u64, property_type: &PropertyType, size: i64) -> Result<Vec<u8>> {
+        self.skip_broken(start, Error::new(ErrorCode::UnknownPropertyType(property_type.name().into())), size)
+    }
+
+    /// Reads the raw value of a property that failed to decode and records why
+    fn skip_broken(&mut self, start: u64, err: Error, size: i64) -> Result<Vec<u8>> {
+        if size < 0 || size as u64 > self.remaining() {
+            return Err(Error::new(ErrorCode::UnexpectedEof));
+        }
+
+        let mut value = vec![0u8; size as usize];
+        self.input.read_exact(&mut value)?;
+        self.diagnostics.push(err.at(start, &self.path).into_diagnostic());
+        Ok(value)
+    }
+
+    /// Reads a value into the value tree and hands it to serde. When recovering, a value that
+    /// can't be read is passed as its raw bytes instead.
+    fn visit_value<V>(&mut self, start: u64, type_name: &str, tag: Tag, size: i64, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        let position = self.input.position();
+        let diagnostics = self.diagnostics.len();
+        match self.sized(size, |de| de.read_value(type_name, tag, size as i32)) {
+            Ok(value) => de::Deserializer::deserialize_any(ValueDeserializer(value), visitor),
+            Err(err) if self.options.recover => {
+                self.input.set_position(position);
+                self.diagnostics.truncate(diagnostics);
+                visitor.visit_byte_buf(self.skip_broken(start, err, size)?)
+            },
+            Err(err) => Err(err)
+        }
     }
 }
 
 
-impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
     type Error = Error;
 
     serde::forward_to_deserialize_any! {
-        i8 u8 i16 u16 i32 u32 i64 u64 f32 f64 bool string seq
+        i8 u8 i16 u16 i32 u32 i64 u64 f32 f64 bool string seq bytes byte_buf map
+    }
+
+    unsupported_deserialize!(deserialize_char, 
+        deserialize_str, deserialize_unit, deserialize_identifier);
+
+    /// Missing properties are left to serde, so a property that is there is always `Some`
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de> {
+        visitor.visit_some(self)
     }
 
-    unimplemented_deserialize!(deserialize_char, 
-        deserialize_str, deserialize_bytes, deserialize_byte_buf, deserialize_option, deserialize_unit, deserialize_map, deserialize_identifier);
-    
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
     where
             V: Visitor<'de> {
@@ -342,66 +714,56 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
     where
             V: Visitor<'de> {
+        let start = self.input.position();
         let value_name = self.parse_string()?;
-        
-        let value_type = self.parse_string()?;
-        match value_type.as_str() {
-            "StructProperty" => self.deserialize_struct(visitor),
-            "ArrayProperty" => self.deserialize_array(visitor),
-            "Int8Property" => visitor.visit_i8(self.parse_i8_property()?),
-            "ByteProperty" => visitor.visit_u8(self.parse_u8_property()?),
-            "Int16Property" => visitor.visit_i16(self.parse_i16_property()?),
-            "UInt16Property" => visitor.visit_u16(self.parse_u16_property()?),
-            "IntProperty" => visitor.visit_i32(self.parse_i32_property()?),
-            "UInt32Property" => visitor.visit_u32(self.parse_u32_property()?),
-            "Int64Property" => visitor.visit_i64(self.parse_i64_property()?),
-            "UInt64Property" => visitor.visit_u64(self.parse_u64_property()?),
-            "FloatProperty" => visitor.visit_f32(self.parse_f32_property()?),
-            "DoubleProperty" => visitor.visit_f64(self.parse_f64_property()?),
-            "StrProperty" => visitor.visit_string(self.parse_string_property()?),
-            "BoolProperty" => visitor.visit_bool(self.parse_bool_property()?),
-            _ => Err(Error::make_other(format!("Unknown property type {}", value_type)))
-        }
+        self.path.push(PathSegment::Field(value_name));
+
+        let value = self.deserialize_property(start, visitor).map_err(|e| e.at(start, &self.path));
+        self.path.pop();
+        value
     }
 
     fn deserialize_unit_struct<V>(self, _: &'static str, _: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_unit_struct"))
     }
 
-    fn deserialize_newtype_struct<V>(self, _: &'static str, _: V) -> Result<V::Value> where
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        if let Some(hint) = Hint::parse(name) {
+            self.check_hint(&hint)?;
+        }
+        visitor.visit_newtype_struct(self)
     }
 
-    fn deserialize_tuple<V>(self, _: usize, _: V) -> Result<V::Value> where
+    fn deserialize_tuple<V>(self, _: usize, visitor: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        self.deserialize_any(visitor)
     }
 
     fn deserialize_tuple_struct<V>(self, _: &'static str, _: usize, _: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_tuple_struct"))
     }
 
     fn deserialize_struct<V>(self, _name: &'static str, _fields: &'static [&'static str], visitor: V) -> Result<V::Value> where
         V: Visitor<'de> {
             if self.first {
                 self.first = false;
-                return visitor.visit_map(MapAccess::new(self, 0));
+                visitor.visit_map(MapAccess::new(self))
             }else {
-                return self.deserialize_any(visitor);
+                self.deserialize_any(visitor)
             }
     }
 
-    fn deserialize_enum<V>(self, _: &'static str, _: &'static [&'static str], _: V) -> Result<V::Value> where
+    fn deserialize_enum<V>(self, _: &'static str, _: &'static [&'static str], visitor: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        self.deserialize_any(EnumLabel(visitor))
     }
 
 }
\ No newline at end of file
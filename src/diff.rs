@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::types::{FEngineVersion, GvasHeader};
+use crate::text::{scalar_text, struct_scalar_text};
+use crate::value::{ArrayValue, Property, Save, StructValue, Value};
+
+/// Differences between two saves, see [`diff`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Diff {
+    pub header: Vec<HeaderChange>,
+    pub properties: Vec<PropertyChange>
+}
+
+/// A header field that differs, `None` when it is missing on that side
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderChange {
+    pub field: String,
+    pub from: Option<String>,
+    pub to: Option<String>
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyChange {
+    /// e.g. `Stats.Gold` or `Inventory[4]`, static array slots are written as `Scores(1)`
+    pub path: String,
+    pub change: Change
+}
+
+/// Struct array elements are reported as [`Value::Struct`] values
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Added(Value),
+    Removed(Value),
+    Changed { from: Value, to: Value }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DiffOptions {
+    /// Struct arrays to align by a field instead of by index, keyed by the name of the array property.
+    /// With `Inventory` mapped to `ItemId`, moving an item around isn't reported as a change.
+    pub array_keys: HashMap<String, String>
+}
+
+impl Diff {
+    pub fn is_empty(&self) -> bool {
+        self.header.is_empty() && self.properties.is_empty()
+    }
+}
+
+pub fn diff(a: &Save, b: &Save) -> Diff {
+    diff_with_options(a, b, &DiffOptions::default())
+}
+
+pub fn diff_with_options(a: &Save, b: &Save, options: &DiffOptions) -> Diff {
+    let mut differ = Differ { options, changes: Vec::new() };
+    differ.properties("", &a.properties, &b.properties);
+    Diff { header: diff_header(&a.header, &b.header), properties: differ.changes }
+}
+
+fn format_engine_version(version: &FEngineVersion) -> String {
+    format!("{}.{}.{}-{}+{}", version.major, version.minor, version.patch, version.change_list, version.branch)
+}
+
+fn diff_header(a: &GvasHeader, b: &GvasHeader) -> Vec<HeaderChange> {
+    let mut changes = Vec::new();
+    let mut field = |field: &str, from: Option<String>, to: Option<String>| {
+        if from != to {
+            changes.push(HeaderChange { field: String::from(field), from, to });
+        }
+    };
+    field("file_type_tag", Some(a.file_type_tag.to_string()), Some(b.file_type_tag.to_string()));
+    field("save_game_file_version", Some(a.save_game_file_version.to_string()), Some(b.save_game_file_version.to_string()));
+    field("package_file_ue4_version", Some(a.package_file_ue4_version.to_string()), Some(b.package_file_ue4_version.to_string()));
+    field("package_file_ue5_version", a.package_file_ue5_version.map(|v| v.to_string()), b.package_file_ue5_version.map(|v| v.to_string()));
+    field("engine_version", Some(format_engine_version(&a.engine_version)), Some(format_engine_version(&b.engine_version)));
+    field("custom_version_format", Some(a.custom_version_format.to_string()), Some(b.custom_version_format.to_string()));
+
+    for version in &a.custom_versions {
+        let name = version.name().map(String::from).unwrap_or_else(|| format!("{:?}", version.key));
+        let other = b.custom_versions.iter().find(|other| other.key == version.key);
+        field(&format!("custom_versions.{}", name), Some(version.version.to_string()), other.map(|other| other.version.to_string()));
+    }
+    for version in b.custom_versions.iter().filter(|version| !a.custom_versions.iter().any(|other| other.key == version.key)) {
+        let name = version.name().map(String::from).unwrap_or_else(|| format!("{:?}", version.key));
+        field(&format!("custom_versions.{}", name), None, Some(version.version.to_string()));
+    }
+
+    field("save_game_class_name", Some(a.save_game_class_name.clone()), Some(b.save_game_class_name.clone()));
+    changes
+}
+
+struct Differ<'a> {
+    options: &'a DiffOptions,
+    changes: Vec<PropertyChange>
+}
+
+impl<'a> Differ<'a> {
+    fn change(&mut self, path: String, change: Change) {
+        self.changes.push(PropertyChange { path, change });
+    }
+
+    /// Properties are matched by name and array index, in order when a name repeats
+    fn properties(&mut self, path: &str, a: &[Property], b: &[Property]) {
+        let key = |property: &Property| (property.name.clone(), property.array_index);
+        let mut matched = vec![false; b.len()];
+        for property in a {
+            let path = property_path(path, property);
+            let other = b.iter().enumerate().position(|(index, other)| !matched[index] && key(other) == key(property));
+            match other {
+                Some(index) => {
+                    matched[index] = true;
+                    self.value(path, &property.name, &property.value, &b[index].value);
+                },
+                None => self.change(path, Change::Removed(property.value.clone()))
+            }
+        }
+        for (property, _) in b.iter().zip(matched).filter(|(_, matched)| !matched) {
+            self.change(property_path(path, property), Change::Added(property.value.clone()));
+        }
+    }
+
+    fn value(&mut self, path: String, name: &str, a: &Value, b: &Value) {
+        if same(a, b) {
+            return;
+        }
+        match (a, b) {
+            (Value::Struct { struct_name: a_name, value: StructValue::Properties(a_properties), .. },
+                Value::Struct { struct_name: b_name, value: StructValue::Properties(b_properties), .. }) if a_name == b_name => {
+                self.properties(&path, a_properties, b_properties);
+            },
+            (Value::Array { element_type: a_type, value: ArrayValue::Values(a_values) },
+                Value::Array { element_type: b_type, value: ArrayValue::Values(b_values) }) if a_type == b_type => {
+                self.by_index(&path, name, a_values, b_values, |value| value.clone());
+            },
+            (Value::Array { value: a_array @ ArrayValue::Structs { struct_name: a_name, .. }, .. },
+                Value::Array { value: b_array @ ArrayValue::Structs { struct_name: b_name, .. }, .. }) if a_name == b_name => {
+                self.structs(path, name, a_array, b_array);
+            },
+            _ => self.change(path, Change::Changed { from: a.clone(), to: b.clone() })
+        }
+    }
+
+    fn by_index<T, F>(&mut self, path: &str, name: &str, a: &[T], b: &[T], to_value: F)
+    where
+        F: Fn(&T) -> Value {
+        for index in 0..a.len().max(b.len()) {
+            let path = format!("{}[{}]", path, index);
+            match (a.get(index), b.get(index)) {
+                (Some(a), Some(b)) => self.value(path, name, &to_value(a), &to_value(b)),
+                (Some(a), None) => self.change(path, Change::Removed(to_value(a))),
+                (None, Some(b)) => self.change(path, Change::Added(to_value(b))),
+                (None, None) => {}
+            }
+        }
+    }
+
+    fn structs(&mut self, path: String, name: &str, a: &ArrayValue, b: &ArrayValue) {
+        let (ArrayValue::Structs { struct_name, guid, elements: a_elements, .. }, ArrayValue::Structs { elements: b_elements, .. }) = (a, b) else {
+            return;
+        };
+        let to_value = |element: &StructValue| Value::Struct { struct_name: struct_name.clone(), guid: *guid, value: element.clone() };
+
+        let key_field = match self.options.array_keys.get(name) {
+            Some(key_field) => key_field,
+            None => return self.by_index(&path, name, a_elements, b_elements, to_value)
+        };
+        let key = |element: &StructValue| match element {
+            StructValue::Properties(properties) => properties.iter().find(|property| &property.name == key_field).map(|property| property.value.clone()),
+            _ => None
+        };
+
+        let mut matched = vec![false; b_elements.len()];
+        for (index, element) in a_elements.iter().enumerate() {
+            let element_key = key(element);
+            let other = element_key.as_ref().and_then(|element_key| {
+                b_elements.iter().enumerate().position(|(index, other)| !matched[index] && key(other).is_some_and(|key| same(&key, element_key)))
+            });
+            let element_path = keyed_path(&path, index, key_field, element_key.as_ref());
+            match other {
+                Some(other) => {
+                    matched[other] = true;
+                    self.value(element_path, name, &to_value(element), &to_value(&b_elements[other]));
+                },
+                None => self.change(element_path, Change::Removed(to_value(element)))
+            }
+        }
+        for (index, element) in b_elements.iter().enumerate().filter(|(index, _)| !matched[*index]) {
+            self.change(keyed_path(&path, index, key_field, key(element).as_ref()), Change::Added(to_value(element)));
+        }
+    }
+}
+
+fn property_path(parent: &str, property: &Property) -> String {
+    let mut path = match parent.is_empty() {
+        true => property.name.clone(),
+        false => format!("{}.{}", parent, property.name)
+    };
+    if property.array_index != 0 {
+        path.push_str(&format!("({})", property.array_index));
+    }
+    path
+}
+
+/// Like `==`, except that floats are compared by their bits so that a NaN equals itself
+fn same(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+        (Value::Double(a), Value::Double(b)) => a.to_bits() == b.to_bits(),
+        (Value::Struct { struct_name: a_name, guid: a_guid, value: a }, Value::Struct { struct_name: b_name, guid: b_guid, value: b }) => {
+            a_name == b_name && a_guid == b_guid && same_struct(a, b)
+        },
+        (Value::Array { element_type: a_type, value: a }, Value::Array { element_type: b_type, value: b }) => a_type == b_type && match (a, b) {
+            (ArrayValue::Values(a), ArrayValue::Values(b)) => same_all(a, b, same),
+            (ArrayValue::Structs { name: a_name, array_index: a_index, property_guid: a_property_guid, struct_name: a_struct, guid: a_guid, elements: a_elements },
+                ArrayValue::Structs { name: b_name, array_index: b_index, property_guid: b_property_guid, struct_name: b_struct, guid: b_guid, elements: b_elements }) => {
+                (a_name, a_index, a_property_guid, a_struct, a_guid) == (b_name, b_index, b_property_guid, b_struct, b_guid) && same_all(a_elements, b_elements, same_struct)
+            },
+            (a, b) => a == b
+        },
+        (Value::Set { element_type: a_type, removed: a_removed, elements: a_elements },
+            Value::Set { element_type: b_type, removed: b_removed, elements: b_elements }) => {
+            a_type == b_type && same_all(a_removed, b_removed, same) && same_all(a_elements, b_elements, same)
+        },
+        (Value::Map { key_type: a_key, value_type: a_value, removed: a_removed, entries: a_entries },
+            Value::Map { key_type: b_key, value_type: b_value, removed: b_removed, entries: b_entries }) => {
+            a_key == b_key && a_value == b_value && same_all(a_removed, b_removed, same)
+                && same_all(a_entries, b_entries, |(a_key, a_value), (b_key, b_value)| same(a_key, b_key) && same(a_value, b_value))
+        },
+        (a, b) => a == b
+    }
+}
+
+fn same_struct(a: &StructValue, b: &StructValue) -> bool {
+    match (a, b) {
+        (StructValue::Properties(a), StructValue::Properties(b)) => same_all(a, b, |a, b| {
+            a.name == b.name && a.array_index == b.array_index && a.guid == b.guid && same(&a.value, &b.value)
+        }),
+        (a, b) => a == b
+    }
+}
+
+fn same_all<T>(a: &[T], b: &[T], same: impl Fn(&T, &T) -> bool) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same(a, b))
+}
+
+/// Aligned elements are named by their key, e.g. `Inventory[ItemId="Sword"]`
+fn keyed_path(path: &str, index: usize, key_field: &str, key: Option<&Value>) -> String {
+    match key {
+        Some(key) => format!("{}[{}={}]", path, key_field, summary(key)),
+        None => format!("{}[{}]", path, index)
+    }
+}
+
+/// A short, single line rendering of a value, scalars are written like in [`crate::text`]
+fn summary(value: &Value) -> String {
+    match value {
+        Value::Struct { struct_name, value: StructValue::Properties(_), .. } => format!("{} {{..}}", struct_name),
+        Value::Struct { value, .. } => struct_scalar_text(value),
+        Value::Array { value: ArrayValue::Bytes(bytes), .. } => format!("[{} bytes]", bytes.len()),
+        Value::Array { value: ArrayValue::Values(values), .. } => format!("[{} elements]", values.len()),
+        Value::Array { value: ArrayValue::Structs { elements, .. }, .. } => format!("[{} elements]", elements.len()),
+        Value::Set { elements, .. } => format!("[{} elements]", elements.len()),
+        Value::Map { entries, .. } => format!("[{} entries]", entries.len()),
+        Value::Unknown { type_name, data, .. } => format!("<{} of {} bytes>", type_name, data.len()),
+        value => scalar_text(value)
+    }
+}
+
+impl Display for PropertyChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.change {
+            Change::Added(_) => write!(f, "{}: added", self.path),
+            Change::Removed(_) => write!(f, "{}: removed", self.path),
+            Change::Changed { from, to } => write!(f, "{}: {} -> {}", self.path, summary(from), summary(to))
+        }
+    }
+}
+
+impl Display for HeaderChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.from, &self.to) {
+            (Some(from), Some(to)) => write!(f, "header.{}: {} -> {}", self.field, from, to),
+            (None, _) => write!(f, "header.{}: added", self.field),
+            (_, None) => write!(f, "header.{}: removed", self.field)
+        }
+    }
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.header {
+            writeln!(f, "{}", change)?;
+        }
+        for change in &self.properties {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,422 @@
+use std::str::FromStr;
+
+use crate::error::Error;
+use crate::types::{FCustomVersion, FEngineVersion, FGuid};
+
+/// Engine releases that `GvasHeader::for_engine` can build a header for.
+/// The custom version lists were taken from saves written by the matching launcher builds.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EngineVersion {
+    UE4_23,
+    UE4_25,
+    UE4_27,
+    UE5_0,
+    UE5_3
+}
+
+struct Preset {
+    major: u16,
+    minor: u16,
+    patch: u16,
+    change_list: u32,
+    branch: &'static str,
+    save_game_file_version: i32,
+    package_file_ue4_version: i32,
+    package_file_ue5_version: Option<i32>,
+    custom_versions: &'static [(FGuid, i32)]
+}
+
+impl EngineVersion {
+    pub const ALL: &'static [EngineVersion] = &[
+        EngineVersion::UE4_23,
+        EngineVersion::UE4_25,
+        EngineVersion::UE4_27,
+        EngineVersion::UE5_0,
+        EngineVersion::UE5_3
+    ];
+
+    fn preset(&self) -> &'static Preset {
+        match self {
+            EngineVersion::UE4_23 => &Preset {
+                major: 4, minor: 23, patch: 1, change_list: 9631420, branch: "++UE4+Release-4.23",
+                save_game_file_version: 2, package_file_ue4_version: 517, package_file_ue5_version: None,
+                custom_versions: UE4_23_CUSTOM_VERSIONS
+            },
+            EngineVersion::UE4_25 => &Preset {
+                major: 4, minor: 25, patch: 4, change_list: 14469661, branch: "++UE4+Release-4.25",
+                save_game_file_version: 2, package_file_ue4_version: 518, package_file_ue5_version: None,
+                custom_versions: UE4_25_CUSTOM_VERSIONS
+            },
+            EngineVersion::UE4_27 => &Preset {
+                major: 4, minor: 27, patch: 2, change_list: 18319896, branch: "++UE4+Release-4.27",
+                save_game_file_version: 2, package_file_ue4_version: 522, package_file_ue5_version: None,
+                custom_versions: UE4_27_CUSTOM_VERSIONS
+            },
+            EngineVersion::UE5_0 => &Preset {
+                major: 5, minor: 0, patch: 3, change_list: 20979098, branch: "++UE5+Release-5.0",
+                save_game_file_version: 3, package_file_ue4_version: 522, package_file_ue5_version: Some(1004),
+                custom_versions: UE5_0_CUSTOM_VERSIONS
+            },
+            EngineVersion::UE5_3 => &Preset {
+                major: 5, minor: 3, patch: 2, change_list: 29314046, branch: "++UE5+Release-5.3",
+                save_game_file_version: 3, package_file_ue4_version: 522, package_file_ue5_version: Some(1009),
+                custom_versions: UE5_3_CUSTOM_VERSIONS
+            }
+        }
+    }
+
+    pub fn from_change_list(change_list: u32) -> Option<Self> {
+        Self::ALL.iter().copied().find(|version| version.preset().change_list == change_list)
+    }
+
+    pub fn from_major_minor(major: u16, minor: u16) -> Option<Self> {
+        Self::ALL.iter().copied().find(|version| {
+            let preset = version.preset();
+            preset.major == major && preset.minor == minor
+        })
+    }
+
+    pub fn engine_version(&self) -> FEngineVersion {
+        let preset = self.preset();
+        FEngineVersion {
+            major: preset.major,
+            minor: preset.minor,
+            patch: preset.patch,
+            change_list: preset.change_list,
+            branch: String::from(preset.branch)
+        }
+    }
+
+    pub fn save_game_file_version(&self) -> i32 {
+        self.preset().save_game_file_version
+    }
+
+    pub fn package_file_ue4_version(&self) -> i32 {
+        self.preset().package_file_ue4_version
+    }
+
+    pub fn package_file_ue5_version(&self) -> Option<i32> {
+        self.preset().package_file_ue5_version
+    }
+
+    pub fn custom_versions(&self) -> Vec<FCustomVersion> {
+        self.preset().custom_versions.iter().map(|&(key, version)| FCustomVersion { key, version }).collect()
+    }
+}
+
+/// Accepts `"4.27"`, `"4.27.2"`, `"UE4_27"`, `"UE5.3"` and branch names like `"++UE4+Release-4.27"`.
+impl FromStr for EngineVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let version = s.rsplit('-').next().unwrap_or(s);
+        let version = version.strip_prefix("UE").unwrap_or(version).replace('_', ".");
+        let mut parts = version.split('.').map(|part| part.parse::<u16>());
+        match (parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor))) => EngineVersion::from_major_minor(major, minor)
+                .ok_or_else(|| Error::make_data(format!("No preset for engine version {}", s))),
+            _ => Err(Error::make_data(format!("Invalid engine version {}", s)))
+        }
+    }
+}
+
+const UE4_23_CUSTOM_VERSIONS: &[(FGuid, i32)] = &[
+    (FGuid::new(0x9C54D522, 0xA8264FBE, 0x94210746, 0x61B482D0), 23),
+    (FGuid::new(0xB0D832E4, 0x1F894F0D, 0xACCF7EB7, 0x36FD4AA2), 10),
+    (FGuid::new(0xE1C64328, 0xA22C4D53, 0xA36C8E86, 0x6417BD8C), 0),
+    (FGuid::new(0x375EC13C, 0x06E448FB, 0xB50084F0, 0x262A717E), 3),
+    (FGuid::new(0xE4B068ED, 0xF49442E9, 0xA231DA0B, 0x2E46BB41), 34),
+    (FGuid::new(0xCFFC743F, 0x43B04480, 0x939114DF, 0x171D2073), 35),
+    (FGuid::new(0xB02B49B5, 0xBB2044E9, 0xA30432B7, 0x52E40360), 2),
+    (FGuid::new(0xA4E4105C, 0x59A149B5, 0xA7C540C4, 0x547EDFEE), 0),
+    (FGuid::new(0x39C831C9, 0x5AE647DC, 0x9A449C17, 0x3E1C8E7C), 0),
+    (FGuid::new(0x78F01B33, 0xEBEA4F98, 0xB9B484EA, 0xCCB95AA2), 0),
+    (FGuid::new(0x6631380F, 0x2D4D43E0, 0x8009CF27, 0x6956A95A), 0),
+    (FGuid::new(0x12F88B9F, 0x88754AFC, 0xA67CD90C, 0x383ABD29), 31),
+    (FGuid::new(0x7B5AE74C, 0xD2704C10, 0xA9585798, 0x0B212A5A), 11),
+    (FGuid::new(0xD7296918, 0x1DD64BDD, 0x9DE264A8, 0x3CC13884), 2),
+    (FGuid::new(0xC2A15278, 0xBFE74AFE, 0x6C1790FF, 0x531DF755), 1),
+    (FGuid::new(0x6EACA3D4, 0x40EC4CC1, 0xB7868BED, 0x09428FC5), 3),
+    (FGuid::new(0x29E575DD, 0xE0A34627, 0x9D10D276, 0x232CDCEA), 17),
+    (FGuid::new(0xAF43A65D, 0x7FD34947, 0x98733E8E, 0xD9C1BB05), 2),
+    (FGuid::new(0x6B266CEC, 0x1EC74B8F, 0xA30BE4D9, 0x0942FC07), 1),
+    (FGuid::new(0x0DF73D61, 0xA23F47EA, 0xB72789E9, 0x0C41499A), 1),
+    (FGuid::new(0x601D1886, 0xAC644F84, 0xAA16D3DE, 0x0DEAC7D6), 27),
+    (FGuid::new(0x9DFFBCD6, 0x494F0158, 0xE2211282, 0x3C92A888), 6),
+    (FGuid::new(0xF2AED0AC, 0x9AFE416F, 0x8664AA7F, 0xFA26D6FC), 1),
+    (FGuid::new(0x174F1F0B, 0xB4C645A5, 0xB13F2EE8, 0xD0FB917D), 9),
+    (FGuid::new(0x717F9EE7, 0xE9B0493A, 0x88B39132, 0x1B388107), 6),
+    (FGuid::new(0x8E7DDCB3, 0x80DA47BB, 0x9FD346A2, 0x93984DF6), 1),
+    (FGuid::new(0xCB8AB0CD, 0xE78C4BDE, 0xA8621393, 0x14E9EF62), 0),
+    (FGuid::new(0xFB680AF2, 0x59EF4BA3, 0xBAA819B5, 0x73C8443D), 1),
+    (FGuid::new(0xAFE08691, 0x3A0D4952, 0xB673673B, 0x7CF22D1E), 2),
+    (FGuid::new(0x2EB5FDBD, 0x01AC4D10, 0x8136F38F, 0x3393A5DA), 5),
+    (FGuid::new(0x509D354F, 0xF6E6492F, 0xA74985B2, 0x073C631C), 0),
+    (FGuid::new(0xA462B7EA, 0xF4994E3A, 0x99C1EC1F, 0x8224E1B2), 2),
+    (FGuid::new(0x430C4D19, 0x71544970, 0x87699B69, 0xDF90B0E5), 13),
+    (FGuid::new(0xAAFE32BD, 0x53954C14, 0xB66A5E25, 0x1032D1DD), 1),
+    (FGuid::new(0x23AFE18E, 0x4CE14E58, 0x8D61C252, 0xB953BEB7), 8),
+    (FGuid::new(0x4A56EB40, 0x10F511DC, 0x92D3347E, 0xB2C96AE7), 2),
+    (FGuid::new(0xD78A4A00, 0xE8584697, 0xBAA819B5, 0x487D46B4), 17),
+    (FGuid::new(0x5579F886, 0x933A4C1F, 0x83BA087B, 0x6361B92F), 1),
+    (FGuid::new(0x612FBE52, 0xDA53400B, 0x910D4F91, 0x9FB1857C), 1),
+    (FGuid::new(0xA4237A36, 0xCAEA41C9, 0x8FA218F8, 0x58681BF3), 4),
+    (FGuid::new(0x804E3F75, 0x70884B49, 0xA4D68C06, 0x3C7EB6DC), 5),
+    (FGuid::new(0x11310AED, 0x2E554D61, 0xAF679AA3, 0xC5A1082C), 17),
+    (FGuid::new(0xAB965196, 0x45D808FC, 0xB7D7228D, 0x78AD569E), 1),
+    (FGuid::new(0x24BB7AF3, 0x56464F83, 0x1F2F2DC2, 0x49AD96FF), 4),
+    (FGuid::new(0xFB26E412, 0x1F154B4D, 0x9372550A, 0x961D2F70), 3)
+];
+
+const UE4_25_CUSTOM_VERSIONS: &[(FGuid, i32)] = &[
+    (FGuid::new(0x9C54D522, 0xA8264FBE, 0x94210746, 0x61B482D0), 30),
+    (FGuid::new(0xB0D832E4, 0x1F894F0D, 0xACCF7EB7, 0x36FD4AA2), 10),
+    (FGuid::new(0xE1C64328, 0xA22C4D53, 0xA36C8E86, 0x6417BD8C), 0),
+    (FGuid::new(0x375EC13C, 0x06E448FB, 0xB50084F0, 0x262A717E), 4),
+    (FGuid::new(0xE4B068ED, 0xF49442E9, 0xA231DA0B, 0x2E46BB41), 38),
+    (FGuid::new(0xCFFC743F, 0x43B04480, 0x939114DF, 0x171D2073), 37),
+    (FGuid::new(0xB02B49B5, 0xBB2044E9, 0xA30432B7, 0x52E40360), 2),
+    (FGuid::new(0xA4E4105C, 0x59A149B5, 0xA7C540C4, 0x547EDFEE), 0),
+    (FGuid::new(0x39C831C9, 0x5AE647DC, 0x9A449C17, 0x3E1C8E7C), 0),
+    (FGuid::new(0x78F01B33, 0xEBEA4F98, 0xB9B484EA, 0xCCB95AA2), 4),
+    (FGuid::new(0x6631380F, 0x2D4D43E0, 0x8009CF27, 0x6956A95A), 0),
+    (FGuid::new(0x12F88B9F, 0x88754AFC, 0xA67CD90C, 0x383ABD29), 43),
+    (FGuid::new(0x7B5AE74C, 0xD2704C10, 0xA9585798, 0x0B212A5A), 12),
+    (FGuid::new(0xD7296918, 0x1DD64BDD, 0x9DE264A8, 0x3CC13884), 3),
+    (FGuid::new(0xC2A15278, 0xBFE74AFE, 0x6C1790FF, 0x531DF755), 1),
+    (FGuid::new(0x6EACA3D4, 0x40EC4CC1, 0xB7868BED, 0x09428FC5), 3),
+    (FGuid::new(0x29E575DD, 0xE0A34627, 0x9D10D276, 0x232CDCEA), 17),
+    (FGuid::new(0xAF43A65D, 0x7FD34947, 0x98733E8E, 0xD9C1BB05), 7),
+    (FGuid::new(0x6B266CEC, 0x1EC74B8F, 0xA30BE4D9, 0x0942FC07), 1),
+    (FGuid::new(0x0DF73D61, 0xA23F47EA, 0xB72789E9, 0x0C41499A), 1),
+    (FGuid::new(0x601D1886, 0xAC644F84, 0xAA16D3DE, 0x0DEAC7D6), 31),
+    (FGuid::new(0x9DFFBCD6, 0x494F0158, 0xE2211282, 0x3C92A888), 10),
+    (FGuid::new(0xF2AED0AC, 0x9AFE416F, 0x8664AA7F, 0xFA26D6FC), 1),
+    (FGuid::new(0x174F1F0B, 0xB4C645A5, 0xB13F2EE8, 0xD0FB917D), 10),
+    (FGuid::new(0x35F94A83, 0xE258406C, 0xA31809F5, 0x9610247C), 37),
+    (FGuid::new(0xB68FC16E, 0x8B1B42E2, 0xB453215C, 0x058844FE), 1),
+    (FGuid::new(0xB2E18506, 0x4273CFC2, 0xA54EF4BB, 0x758BBA07), 1),
+    (FGuid::new(0x54683250, 0x809948AF, 0x8BC89896, 0xFBADF9B7), 0),
+    (FGuid::new(0x717F9EE7, 0xE9B0493A, 0x88B39132, 0x1B388107), 6),
+    (FGuid::new(0x8E7DDCB3, 0x80DA47BB, 0x9FD346A2, 0x93984DF6), 1),
+    (FGuid::new(0xCB8AB0CD, 0xE78C4BDE, 0xA8621393, 0x14E9EF62), 0),
+    (FGuid::new(0xAB965196, 0x45D808FC, 0xB7D7228D, 0x78AD569E), 1),
+    (FGuid::new(0x9950B70E, 0xB41A4E17, 0xBBCCFA0D, 0x57817FD6), 1),
+    (FGuid::new(0xFB680AF2, 0x59EF4BA3, 0xBAA819B5, 0x73C8443D), 2),
+    (FGuid::new(0xAFE08691, 0x3A0D4952, 0xB673673B, 0x7CF22D1E), 2),
+    (FGuid::new(0x2EB5FDBD, 0x01AC4D10, 0x8136F38F, 0x3393A5DA), 5),
+    (FGuid::new(0x509D354F, 0xF6E6492F, 0xA74985B2, 0x073C631C), 0),
+    (FGuid::new(0xA462B7EA, 0xF4994E3A, 0x99C1EC1F, 0x8224E1B2), 2),
+    (FGuid::new(0x430C4D19, 0x71544970, 0x87699B69, 0xDF90B0E5), 14),
+    (FGuid::new(0xAAFE32BD, 0x53954C14, 0xB66A5E25, 0x1032D1DD), 1),
+    (FGuid::new(0x23AFE18E, 0x4CE14E58, 0x8D61C252, 0xB953BEB7), 11),
+    (FGuid::new(0x4A56EB40, 0x10F511DC, 0x92D3347E, 0xB2C96AE7), 2),
+    (FGuid::new(0xD78A4A00, 0xE8584697, 0xBAA819B5, 0x487D46B4), 17),
+    (FGuid::new(0x5579F886, 0x933A4C1F, 0x83BA087B, 0x6361B92F), 1),
+    (FGuid::new(0x612FBE52, 0xDA53400B, 0x910D4F91, 0x9FB1857C), 1),
+    (FGuid::new(0xA4237A36, 0xCAEA41C9, 0x8FA218F8, 0x58681BF3), 4),
+    (FGuid::new(0x804E3F75, 0x70884B49, 0xA4D68C06, 0x3C7EB6DC), 5),
+    (FGuid::new(0x24BB7AF3, 0x56464F83, 0x1F2F2DC2, 0x49AD96FF), 5),
+    (FGuid::new(0xFCF57AFA, 0x50764283, 0xB9A9E658, 0xFFA02D32), 61),
+    (FGuid::new(0x11310AED, 0x2E554D61, 0xAF679AA3, 0xC5A1082C), 17),
+    (FGuid::new(0x76A52329, 0x092345B5, 0x98AED841, 0xCF2F6AD8), 2),
+    (FGuid::new(0x5FBC6907, 0x55C840AE, 0x8E67F184, 0x5EFFF13F), 1),
+    (FGuid::new(0xFB26E412, 0x1F154B4D, 0x9372550A, 0x961D2F70), 3)
+];
+
+const UE4_27_CUSTOM_VERSIONS: &[(FGuid, i32)] = &[
+    (FGuid::new(0xFCF57AFA, 0x50764283, 0xB9A9E658, 0xFFA02D32), 68),
+    (FGuid::new(0xFB26E412, 0x1F154B4D, 0x9372550A, 0x961D2F70), 3),
+    (FGuid::new(0xA7820CFB, 0x20A74359, 0x8C542C14, 0x9623CF50), 6),
+    (FGuid::new(0x82E77C4E, 0x332343A5, 0xB46B13C5, 0x97310DF3), 0),
+    (FGuid::new(0x11310AED, 0x2E554D61, 0xAF679AA3, 0xC5A1082C), 17),
+    (FGuid::new(0x24BB7AF3, 0x56464F83, 0x1F2F2DC2, 0x49AD96FF), 5),
+    (FGuid::new(0x76A52329, 0x092345B5, 0x98AED841, 0xCF2F6AD8), 5),
+    (FGuid::new(0x5FBC6907, 0x55C840AE, 0x8E67F184, 0x5EFFF13F), 1),
+    (FGuid::new(0x9C54D522, 0xA8264FBE, 0x94210746, 0x61B482D0), 43),
+    (FGuid::new(0xB0D832E4, 0x1F894F0D, 0xACCF7EB7, 0x36FD4AA2), 10),
+    (FGuid::new(0xE1C64328, 0xA22C4D53, 0xA36C8E86, 0x6417BD8C), 0),
+    (FGuid::new(0x375EC13C, 0x06E448FB, 0xB50084F0, 0x262A717E), 4),
+    (FGuid::new(0xE4B068ED, 0xF49442E9, 0xA231DA0B, 0x2E46BB41), 40),
+    (FGuid::new(0xCFFC743F, 0x43B04480, 0x939114DF, 0x171D2073), 37),
+    (FGuid::new(0xB02B49B5, 0xBB2044E9, 0xA30432B7, 0x52E40360), 3),
+    (FGuid::new(0xA4E4105C, 0x59A149B5, 0xA7C540C4, 0x547EDFEE), 0),
+    (FGuid::new(0x39C831C9, 0x5AE647DC, 0x9A449C17, 0x3E1C8E7C), 0),
+    (FGuid::new(0x78F01B33, 0xEBEA4F98, 0xB9B484EA, 0xCCB95AA2), 14),
+    (FGuid::new(0x6631380F, 0x2D4D43E0, 0x8009CF27, 0x6956A95A), 0),
+    (FGuid::new(0x12F88B9F, 0x88754AFC, 0xA67CD90C, 0x383ABD29), 45),
+    (FGuid::new(0x7B5AE74C, 0xD2704C10, 0xA9585798, 0x0B212A5A), 13),
+    (FGuid::new(0xD7296918, 0x1DD64BDD, 0x9DE264A8, 0x3CC13884), 3),
+    (FGuid::new(0xC2A15278, 0xBFE74AFE, 0x6C1790FF, 0x531DF755), 1),
+    (FGuid::new(0x6EACA3D4, 0x40EC4CC1, 0xB7868BED, 0x09428FC5), 3),
+    (FGuid::new(0x29E575DD, 0xE0A34627, 0x9D10D276, 0x232CDCEA), 17),
+    (FGuid::new(0xAF43A65D, 0x7FD34947, 0x98733E8E, 0xD9C1BB05), 15),
+    (FGuid::new(0x6B266CEC, 0x1EC74B8F, 0xA30BE4D9, 0x0942FC07), 1),
+    (FGuid::new(0x0DF73D61, 0xA23F47EA, 0xB72789E9, 0x0C41499A), 1),
+    (FGuid::new(0x601D1886, 0xAC644F84, 0xAA16D3DE, 0x0DEAC7D6), 47),
+    (FGuid::new(0xE7086368, 0x6B234C58, 0x84391B70, 0x16265E91), 1),
+    (FGuid::new(0x9DFFBCD6, 0x494F0158, 0xE2211282, 0x3C92A888), 10),
+    (FGuid::new(0xF2AED0AC, 0x9AFE416F, 0x8664AA7F, 0xFA26D6FC), 1),
+    (FGuid::new(0x174F1F0B, 0xB4C645A5, 0xB13F2EE8, 0xD0FB917D), 10),
+    (FGuid::new(0x35F94A83, 0xE258406C, 0xA31809F5, 0x9610247C), 41),
+    (FGuid::new(0xB68FC16E, 0x8B1B42E2, 0xB453215C, 0x058844FE), 1),
+    (FGuid::new(0xB2E18506, 0x4273CFC2, 0xA54EF4BB, 0x758BBA07), 1),
+    (FGuid::new(0x64F58936, 0xFD1B42BA, 0xBA967289, 0xD5D0FA4E), 1),
+    (FGuid::new(0x6F0ED827, 0xA6094895, 0x9C91998D, 0x90180EA4), 2),
+    (FGuid::new(0x717F9EE7, 0xE9B0493A, 0x88B39132, 0x1B388107), 8),
+    (FGuid::new(0x54683250, 0x809948AF, 0x8BC89896, 0xFBADF9B7), 0),
+    (FGuid::new(0x430C4D19, 0x71544970, 0x87699B69, 0xDF90B0E5), 15),
+    (FGuid::new(0xAAFE32BD, 0x53954C14, 0xB66A5E25, 0x1032D1DD), 1),
+    (FGuid::new(0x23AFE18E, 0x4CE14E58, 0x8D61C252, 0xB953BEB7), 11),
+    (FGuid::new(0xA462B7EA, 0xF4994E3A, 0x99C1EC1F, 0x8224E1B2), 4),
+    (FGuid::new(0x2EB5FDBD, 0x01AC4D10, 0x8136F38F, 0x3393A5DA), 5),
+    (FGuid::new(0x509D354F, 0xF6E6492F, 0xA74985B2, 0x073C631C), 0),
+    (FGuid::new(0x4A56EB40, 0x10F511DC, 0x92D3347E, 0xB2C96AE7), 2),
+    (FGuid::new(0xD78A4A00, 0xE8584697, 0xBAA819B5, 0x487D46B4), 18),
+    (FGuid::new(0x5579F886, 0x933A4C1F, 0x83BA087B, 0x6361B92F), 2),
+    (FGuid::new(0x612FBE52, 0xDA53400B, 0x910D4F91, 0x9FB1857C), 1),
+    (FGuid::new(0xA4237A36, 0xCAEA41C9, 0x8FA218F8, 0x58681BF3), 4),
+    (FGuid::new(0x804E3F75, 0x70884B49, 0xA4D68C06, 0x3C7EB6DC), 5),
+    (FGuid::new(0xFB680AF2, 0x59EF4BA3, 0xBAA819B5, 0x73C8443D), 2),
+    (FGuid::new(0x9950B70E, 0xB41A4E17, 0xBBCCFA0D, 0x57817FD6), 1)
+];
+
+const UE5_0_CUSTOM_VERSIONS: &[(FGuid, i32)] = &[
+    (FGuid::new(0x11310AED, 0x2E554D61, 0xAF679AA3, 0xC5A1082C), 17),
+    (FGuid::new(0xA7820CFB, 0x20A74359, 0x8C542C14, 0x9623CF50), 13),
+    (FGuid::new(0xF6DFBB78, 0xBB50A0E4, 0x4018B84D, 0x60CBAF23), 0),
+    (FGuid::new(0x24BB7AF3, 0x56464F83, 0x1F2F2DC2, 0x49AD96FF), 5),
+    (FGuid::new(0x76A52329, 0x092345B5, 0x98AED841, 0xCF2F6AD8), 5),
+    (FGuid::new(0x5FBC6907, 0x55C840AE, 0x8E67F184, 0x5EFFF13F), 1),
+    (FGuid::new(0xFCF57AFA, 0x50764283, 0xB9A9E658, 0xFFA02D32), 70),
+    (FGuid::new(0x82E77C4E, 0x332343A5, 0xB46B13C5, 0x97310DF3), 0),
+    (FGuid::new(0x9C54D522, 0xA8264FBE, 0x94210746, 0x61B482D0), 44),
+    (FGuid::new(0xB0D832E4, 0x1F894F0D, 0xACCF7EB7, 0x36FD4AA2), 10),
+    (FGuid::new(0xE1C64328, 0xA22C4D53, 0xA36C8E86, 0x6417BD8C), 0),
+    (FGuid::new(0x375EC13C, 0x06E448FB, 0xB50084F0, 0x262A717E), 4),
+    (FGuid::new(0xE4B068ED, 0xF49442E9, 0xA231DA0B, 0x2E46BB41), 40),
+    (FGuid::new(0xCFFC743F, 0x43B04480, 0x939114DF, 0x171D2073), 37),
+    (FGuid::new(0xB02B49B5, 0xBB2044E9, 0xA30432B7, 0x52E40360), 3),
+    (FGuid::new(0xA4E4105C, 0x59A149B5, 0xA7C540C4, 0x547EDFEE), 0),
+    (FGuid::new(0x39C831C9, 0x5AE647DC, 0x9A449C17, 0x3E1C8E7C), 0),
+    (FGuid::new(0x78F01B33, 0xEBEA4F98, 0xB9B484EA, 0xCCB95AA2), 20),
+    (FGuid::new(0x6631380F, 0x2D4D43E0, 0x8009CF27, 0x6956A95A), 0),
+    (FGuid::new(0x12F88B9F, 0x88754AFC, 0xA67CD90C, 0x383ABD29), 45),
+    (FGuid::new(0x7B5AE74C, 0xD2704C10, 0xA9585798, 0x0B212A5A), 13),
+    (FGuid::new(0xD7296918, 0x1DD64BDD, 0x9DE264A8, 0x3CC13884), 3),
+    (FGuid::new(0xC2A15278, 0xBFE74AFE, 0x6C1790FF, 0x531DF755), 1),
+    (FGuid::new(0x6EACA3D4, 0x40EC4CC1, 0xB7868BED, 0x09428FC5), 3),
+    (FGuid::new(0x29E575DD, 0xE0A34627, 0x9D10D276, 0x232CDCEA), 17),
+    (FGuid::new(0xAF43A65D, 0x7FD34947, 0x98733E8E, 0xD9C1BB05), 15),
+    (FGuid::new(0x6B266CEC, 0x1EC74B8F, 0xA30BE4D9, 0x0942FC07), 1),
+    (FGuid::new(0x0DF73D61, 0xA23F47EA, 0xB72789E9, 0x0C41499A), 1),
+    (FGuid::new(0x601D1886, 0xAC644F84, 0xAA16D3DE, 0x0DEAC7D6), 59),
+    (FGuid::new(0xE7086368, 0x6B234C58, 0x84391B70, 0x16265E91), 3),
+    (FGuid::new(0x9DFFBCD6, 0x494F0158, 0xE2211282, 0x3C92A888), 10),
+    (FGuid::new(0xF2AED0AC, 0x9AFE416F, 0x8664AA7F, 0xFA26D6FC), 1),
+    (FGuid::new(0x174F1F0B, 0xB4C645A5, 0xB13F2EE8, 0xD0FB917D), 10),
+    (FGuid::new(0x35F94A83, 0xE258406C, 0xA31809F5, 0x9610247C), 41),
+    (FGuid::new(0xB68FC16E, 0x8B1B42E2, 0xB453215C, 0x058844FE), 1),
+    (FGuid::new(0xB2E18506, 0x4273CFC2, 0xA54EF4BB, 0x758BBA07), 1),
+    (FGuid::new(0x64F58936, 0xFD1B42BA, 0xBA967289, 0xD5D0FA4E), 1),
+    (FGuid::new(0x697DD581, 0xE64F41AB, 0xAA4A51EC, 0xBEB7B628), 59),
+    (FGuid::new(0xD89B5E42, 0x24BD4D46, 0x8412ACA8, 0xDF641779), 36),
+    (FGuid::new(0x59DA5D52, 0x12324948, 0xB8785978, 0x70B8E98B), 8),
+    (FGuid::new(0x26075A32, 0x730F4708, 0x88E98C32, 0xF1599D05), 0),
+    (FGuid::new(0x6F0ED827, 0xA6094895, 0x9C91998D, 0x90180EA4), 2),
+    (FGuid::new(0x30D58BE3, 0x95EA4282, 0xA6E3B159, 0xD8EBB06A), 1),
+    (FGuid::new(0x717F9EE7, 0xE9B0493A, 0x88B39132, 0x1B388107), 12),
+    (FGuid::new(0x430C4D19, 0x71544970, 0x87699B69, 0xDF90B0E5), 15),
+    (FGuid::new(0xAAFE32BD, 0x53954C14, 0xB66A5E25, 0x1032D1DD), 1),
+    (FGuid::new(0x23AFE18E, 0x4CE14E58, 0x8D61C252, 0xB953BEB7), 11),
+    (FGuid::new(0xA462B7EA, 0xF4994E3A, 0x99C1EC1F, 0x8224E1B2), 4),
+    (FGuid::new(0x2EB5FDBD, 0x01AC4D10, 0x8136F38F, 0x3393A5DA), 5),
+    (FGuid::new(0x509D354F, 0xF6E6492F, 0xA74985B2, 0x073C631C), 0),
+    (FGuid::new(0x4A56EB40, 0x10F511DC, 0x92D3347E, 0xB2C96AE7), 2),
+    (FGuid::new(0xD78A4A00, 0xE8584697, 0xBAA819B5, 0x487D46B4), 18),
+    (FGuid::new(0x5579F886, 0x933A4C1F, 0x83BA087B, 0x6361B92F), 2),
+    (FGuid::new(0x612FBE52, 0xDA53400B, 0x910D4F91, 0x9FB1857C), 1),
+    (FGuid::new(0xA4237A36, 0xCAEA41C9, 0x8FA218F8, 0x58681BF3), 5),
+    (FGuid::new(0x804E3F75, 0x70884B49, 0xA4D68C06, 0x3C7EB6DC), 5),
+    (FGuid::new(0x1ED048F4, 0x2F2E4C68, 0x89D053A4, 0xF18F102D), 1),
+    (FGuid::new(0xFB680AF2, 0x59EF4BA3, 0xBAA819B5, 0x73C8443D), 2),
+    (FGuid::new(0x9950B70E, 0xB41A4E17, 0xBBCCFA0D, 0x57817FD6), 1)
+];
+
+const UE5_3_CUSTOM_VERSIONS: &[(FGuid, i32)] = &[
+    (FGuid::new(0x9C54D522, 0xA8264FBE, 0x94210746, 0x61B482D0), 44),
+    (FGuid::new(0x62915CA3, 0x1C8E4BF7, 0xA30E12C7, 0xC8219DF7), 32),
+    (FGuid::new(0xCC400D24, 0xE0E94E7B, 0x9BF9A283, 0xDCC0C027), 0),
+    (FGuid::new(0xB0D832E4, 0x1F894F0D, 0xACCF7EB7, 0x36FD4AA2), 10),
+    (FGuid::new(0xE1C64328, 0xA22C4D53, 0xA36C8E86, 0x6417BD8C), 0),
+    (FGuid::new(0x375EC13C, 0x06E448FB, 0xB50084F0, 0x262A717E), 4),
+    (FGuid::new(0xE4B068ED, 0xF49442E9, 0xA231DA0B, 0x2E46BB41), 40),
+    (FGuid::new(0xCFFC743F, 0x43B04480, 0x939114DF, 0x171D2073), 37),
+    (FGuid::new(0xB02B49B5, 0xBB2044E9, 0xA30432B7, 0x52E40360), 3),
+    (FGuid::new(0xA4E4105C, 0x59A149B5, 0xA7C540C4, 0x547EDFEE), 0),
+    (FGuid::new(0x39C831C9, 0x5AE647DC, 0x9A449C17, 0x3E1C8E7C), 0),
+    (FGuid::new(0x78F01B33, 0xEBEA4F98, 0xB9B484EA, 0xCCB95AA2), 20),
+    (FGuid::new(0x6631380F, 0x2D4D43E0, 0x8009CF27, 0x6956A95A), 0),
+    (FGuid::new(0x12F88B9F, 0x88754AFC, 0xA67CD90C, 0x383ABD29), 47),
+    (FGuid::new(0x7B5AE74C, 0xD2704C10, 0xA9585798, 0x0B212A5A), 13),
+    (FGuid::new(0xD7296918, 0x1DD64BDD, 0x9DE264A8, 0x3CC13884), 3),
+    (FGuid::new(0xC2A15278, 0xBFE74AFE, 0x6C1790FF, 0x531DF755), 1),
+    (FGuid::new(0x6EACA3D4, 0x40EC4CC1, 0xB7868BED, 0x09428FC5), 3),
+    (FGuid::new(0x29E575DD, 0xE0A34627, 0x9D10D276, 0x232CDCEA), 17),
+    (FGuid::new(0xAF43A65D, 0x7FD34947, 0x98733E8E, 0xD9C1BB05), 15),
+    (FGuid::new(0x6B266CEC, 0x1EC74B8F, 0xA30BE4D9, 0x0942FC07), 1),
+    (FGuid::new(0x0DF73D61, 0xA23F47EA, 0xB72789E9, 0x0C41499A), 1),
+    (FGuid::new(0x601D1886, 0xAC644F84, 0xAA16D3DE, 0x0DEAC7D6), 111),
+    (FGuid::new(0x8DBC2C5B, 0x54A743E0, 0xA768FCBB, 0x7DA29060), 2),
+    (FGuid::new(0x5B4C06B7, 0x24634AF8, 0x805BBF70, 0xCDF5D0DD), 10),
+    (FGuid::new(0xE7086368, 0x6B234C58, 0x84391B70, 0x16265E91), 11),
+    (FGuid::new(0x9DFFBCD6, 0x494F0158, 0xE2211282, 0x3C92A888), 10),
+    (FGuid::new(0xF2AED0AC, 0x9AFE416F, 0x8664AA7F, 0xFA26D6FC), 1),
+    (FGuid::new(0x174F1F0B, 0xB4C645A5, 0xB13F2EE8, 0xD0FB917D), 10),
+    (FGuid::new(0x35F94A83, 0xE258406C, 0xA31809F5, 0x9610247C), 41),
+    (FGuid::new(0xB68FC16E, 0x8B1B42E2, 0xB453215C, 0x058844FE), 1),
+    (FGuid::new(0xB2E18506, 0x4273CFC2, 0xA54EF4BB, 0x758BBA07), 1),
+    (FGuid::new(0x64F58936, 0xFD1B42BA, 0xBA967289, 0xD5D0FA4E), 1),
+    (FGuid::new(0x697DD581, 0xE64F41AB, 0xAA4A51EC, 0xBEB7B628), 118),
+    (FGuid::new(0xD89B5E42, 0x24BD4D46, 0x8412ACA8, 0xDF641779), 47),
+    (FGuid::new(0x59DA5D52, 0x12324948, 0xB8785978, 0x70B8E98B), 8),
+    (FGuid::new(0x26075A32, 0x730F4708, 0x88E98C32, 0xF1599D05), 0),
+    (FGuid::new(0x6F0ED827, 0xA6094895, 0x9C91998D, 0x90180EA4), 2),
+    (FGuid::new(0x30D58BE3, 0x95EA4282, 0xA6E3B159, 0xD8EBB06A), 1),
+    (FGuid::new(0x717F9EE7, 0xE9B0493A, 0x88B39132, 0x1B388107), 17),
+    (FGuid::new(0x68C409FC, 0x70954986, 0x8963ACD2, 0xC4865183), 3),
+    (FGuid::new(0x430C4D19, 0x71544970, 0x87699B69, 0xDF90B0E5), 15),
+    (FGuid::new(0xAAFE32BD, 0x53954C14, 0xB66A5E25, 0x1032D1DD), 1),
+    (FGuid::new(0x23AFE18E, 0x4CE14E58, 0x8D61C252, 0xB953BEB7), 11),
+    (FGuid::new(0xA462B7EA, 0xF4994E3A, 0x99C1EC1F, 0x8224E1B2), 4),
+    (FGuid::new(0x2EB5FDBD, 0x01AC4D10, 0x8136F38F, 0x3393A5DA), 5),
+    (FGuid::new(0x509D354F, 0xF6E6492F, 0xA74985B2, 0x073C631C), 0),
+    (FGuid::new(0x95A4F03E, 0x7E0B49E4, 0xBA43D356, 0x94FF87D9), 7),
+    (FGuid::new(0xB6E31B1C, 0xD29F11EC, 0x857E9F85, 0x6F9970E2), 1),
+    (FGuid::new(0x4A56EB40, 0x10F511DC, 0x92D3347E, 0xB2C96AE7), 3),
+    (FGuid::new(0x8417998A, 0xBBC043EC, 0x81B3D119, 0x072D2722), 19),
+    (FGuid::new(0xD78A4A00, 0xE8584697, 0xBAA819B5, 0x487D46B4), 18),
+    (FGuid::new(0x5579F886, 0x933A4C1F, 0x83BA087B, 0x6361B92F), 2),
+    (FGuid::new(0x612FBE52, 0xDA53400B, 0x910D4F91, 0x9FB1857C), 1),
+    (FGuid::new(0xA4237A36, 0xCAEA41C9, 0x8FA218F8, 0x58681BF3), 5),
+    (FGuid::new(0x804E3F75, 0x70884B49, 0xA4D68C06, 0x3C7EB6DC), 5),
+    (FGuid::new(0x1ED048F4, 0x2F2E4C68, 0x89D053A4, 0xF18F102D), 1),
+    (FGuid::new(0xFB680AF2, 0x59EF4BA3, 0xBAA819B5, 0x73C8443D), 2),
+    (FGuid::new(0x9950B70E, 0xB41A4E17, 0xBBCCFA0D, 0x57817FD6), 1),
+    (FGuid::new(0x5E1714CD, 0x484E2951, 0x707A89A7, 0x9302AB78), 3),
+    (FGuid::new(0x0925477B, 0x763D4001, 0x9D91D673, 0x0B75B411), 1),
+    (FGuid::new(0x4288211B, 0x454816C6, 0x1A7667B2, 0x507A2A00), 1),
+    (FGuid::new(0xDC49959B, 0x53C04DE7, 0x9156EA88, 0x5E7C5D39), 2),
+    (FGuid::new(0xA7820CFB, 0x20A74359, 0x8C542C14, 0x9623CF50), 27),
+    (FGuid::new(0x82E77C4E, 0x332343A5, 0xB46B13C5, 0x97310DF3), 0),
+    (FGuid::new(0xE21E1CAA, 0xAF47425E, 0x89BF6AD4, 0x4C44A8BB), 0),
+    (FGuid::new(0x134A157E, 0xD5E249A3, 0x8D4E843C, 0x98FE9E31), 2),
+    (FGuid::new(0xFCF57AFA, 0x50764283, 0xB9A9E658, 0xFFA02D32), 79),
+    (FGuid::new(0x11310AED, 0x2E554D61, 0xAF679AA3, 0xC5A1082C), 17),
+    (FGuid::new(0xF6DFBB78, 0xBB50A0E4, 0x4018B84D, 0x60CBAF23), 2),
+    (FGuid::new(0x24BB7AF3, 0x56464F83, 0x1F2F2DC2, 0x49AD96FF), 5),
+    (FGuid::new(0x76A52329, 0x092345B5, 0x98AED841, 0xCF2F6AD8), 5),
+    (FGuid::new(0x5FBC6907, 0x55C840AE, 0x8E67F184, 0x5EFFF13F), 1),
+    (FGuid::new(0x92738C43, 0x29884D9C, 0x9A3D9BBE, 0x6EFF9FC0), 1)
+];
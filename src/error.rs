@@ -1,52 +1,157 @@
 pub type Result<T> = std::result::Result<T, Error>;
-use std::{fmt::Display, io, str::Utf8Error, string::FromUtf8Error};
+use std::{fmt::Display, io, string::FromUtf8Error};
 
 
 #[derive(Debug)]
 pub struct Error {
-    code: ErrorCode
+    code: ErrorCode,
+    offset: Option<u64>,
+    path: Option<String>
 }
 
 impl Error {
-    pub fn make_other(msg: String) -> Self {
+    pub fn new(code: ErrorCode) -> Self {
         Error {
-            code: ErrorCode::Other(msg.into_boxed_str())
+            code,
+            offset: None,
+            path: None
         }
     }
 
+    pub fn make_other(msg: String) -> Self {
+        Error::new(ErrorCode::Other(msg.into_boxed_str()))
+    }
+
     pub fn make_data(msg: String) -> Self {
-        Error {
-            code: ErrorCode::Data(msg.into_boxed_str())
-        }
+        Error::new(ErrorCode::Data(msg.into_boxed_str()))
     }
 
     pub fn make_read(err: io::Error) -> Self {
-        Error {
-            code: ErrorCode::Io(err)
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof => Error::new(ErrorCode::UnexpectedEof),
+            _ => Error::new(ErrorCode::Io(err))
         }
     }
 
+    pub fn unsupported(what: &str) -> Self {
+        Error::new(ErrorCode::Unsupported(what.into()))
+    }
+
     pub fn make_string(err: FromUtf8Error) -> Self {
-        Error {
-            code: ErrorCode::StringParse(err)
+        Error::new(ErrorCode::InvalidString(err.to_string().into_boxed_str()))
+    }
+
+    pub fn code(&self) -> &ErrorCode {
+        &self.code
+    }
+
+    /// Byte offset of the property (or header) being decoded when the error occurred
+    pub fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+
+    /// Path of the property being decoded when the error occurred, e.g. `Inventory[3].ItemId`
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub(crate) fn into_diagnostic(self) -> Diagnostic {
+        Diagnostic {
+            code: self.code,
+            offset: self.offset.unwrap_or_default(),
+            path: self.path.unwrap_or_default()
         }
     }
+
+    /// Attaches a position unless a more precise one was already attached further down.
+    pub(crate) fn at(mut self, offset: u64, path: &[PathSegment]) -> Self {
+        if self.offset.is_none() {
+            self.offset = Some(offset);
+            if !path.is_empty() {
+                self.path = Some(format_path(path));
+            }
+        }
+        self
+    }
+
+    /// Moves the attached position by `base`, for offsets that were counted from a later point
+    pub(crate) fn shifted(mut self, base: u64) -> Self {
+        self.offset = self.offset.map(|offset| offset + base);
+        self
+    }
 }
 
 
 #[derive(Debug)]
 pub enum ErrorCode {
     Io(io::Error),
-    StringParse(FromUtf8Error),
+    UnexpectedEof,
+    InvalidString(Box<str>),
+    UnknownPropertyType(Box<str>),
+    SizeMismatch { expected: i64, actual: i64 },
+    LimitExceeded { limit: &'static str, max: u64, actual: u64 },
+    MissingField(Box<str>),
+    UnsupportedStruct(Box<str>),
+    Unsupported(Box<str>),
     Data(Box<str>),
     Other(Box<str>)
 }
 
+/// A problem that was worked around instead of failing the whole read.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub code: ErrorCode,
+    pub offset: u64,
+    pub path: String
+}
+
+impl Diagnostic {
+    pub(crate) fn new(code: ErrorCode, offset: u64, path: &[PathSegment]) -> Self {
+        Diagnostic { code, offset, path: format_path(path) }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at offset {}", self.code, self.offset)?;
+        if !self.path.is_empty() {
+            write!(f, " in {}", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PathSegment {
+    Field(String),
+    Index(usize)
+}
+
+pub(crate) fn format_path(path: &[PathSegment]) -> String {
+    let mut formatted = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Field(name) => {
+                if !formatted.is_empty() {
+                    formatted.push('.');
+                }
+                formatted.push_str(name);
+            },
+            PathSegment::Index(index) => formatted.push_str(&format!("[{}]", index))
+        }
+    }
+    formatted
+}
+
 
 impl serde::de::Error for Error {
     fn custom<T>(msg: T) -> Self where T: std::fmt::Display {
         Error::make_other(msg.to_string())
     }
+
+    fn missing_field(field: &'static str) -> Self {
+        Error::new(ErrorCode::MissingField(field.into()))
+    }
 }
 
 impl serde::ser::Error for Error {
@@ -78,7 +183,14 @@ impl serde::de::StdError for Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Display::fmt(&self.code, f)
+        Display::fmt(&self.code, f)?;
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {}", offset)?;
+        }
+        if let Some(ref path) = self.path {
+            write!(f, " in {}", path)?;
+        }
+        Ok(())
     }
 }
 
@@ -86,9 +198,16 @@ impl Display for ErrorCode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match *self {
             ErrorCode::Io(ref err) => Display::fmt(err, f),
-            ErrorCode::StringParse(ref err) => Display::fmt(err, f),
+            ErrorCode::UnexpectedEof => f.write_str("Unexpected end of input"),
+            ErrorCode::InvalidString(ref msg) => write!(f, "Invalid string: {}", msg),
+            ErrorCode::UnknownPropertyType(ref t) => write!(f, "Unknown property type {}", t),
+            ErrorCode::SizeMismatch { expected, actual } => write!(f, "Expected value size of {} got {}", expected, actual),
+            ErrorCode::LimitExceeded { limit, max, actual } => write!(f, "Exceeded {} limit of {} with {}", limit, max, actual),
+            ErrorCode::MissingField(ref field) => write!(f, "Missing field {}", field),
+            ErrorCode::UnsupportedStruct(ref name) => write!(f, "Unsupported struct {}", name),
+            ErrorCode::Unsupported(ref what) => write!(f, "Unsupported operation {}", what),
             ErrorCode::Other(ref msg) => f.write_str(msg),
             ErrorCode::Data(ref msg) => f.write_str(msg)
         }
     }
-}
\ No newline at end of file
+}
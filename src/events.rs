@@ -0,0 +1,310 @@
+//! Pull based reading of the properties of a save, one event at a time.
+//!
+//! Every property starts with [`Event::PropertyStart`], followed by its value. Scalars are a single
+//! [`Event::Value`], structs and arrays are closed by [`Event::End`]:
+//!
+//! ```text
+//! PropertyStart { name: "Stats", type_name: "StructProperty", .. }
+//! StructStart { struct_name: "PlayerStats", .. }
+//! PropertyStart { name: "Level", type_name: "IntProperty", .. }
+//! Value(Int(12))
+//! End
+//! ```
+//!
+//! Array elements follow [`Event::ArrayStart`] without a `PropertyStart` of their own and byte
+//! arrays come as a single [`Event::Bytes`]. Structs stored as plain values, like `DateTime`, are a
+//! single [`Event::Value`], and so are whole sets and maps, holding a [`crate::value::Value::Set`]
+//! or [`crate::value::Value::Map`].
+//!
+//! The input is read as it goes, only the top level property being read is held in memory along
+//! with what the source read ahead of it. Offsets in errors and diagnostics count from where the
+//! reader started.
+
+use std::io::{Cursor, Read};
+use std::mem;
+use std::ops::Range;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use crate::de::{check_limit, Deserializer, DeserializerOptions};
+use crate::error::{Diagnostic, Error, ErrorCode, PathSegment, Result};
+use crate::patch::{Element, Tagged};
+use crate::types::FGuid;
+use crate::value::{is_native, ByteValue, Value};
+use crate::property::Tag;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    PropertyStart { name: String, type_name: String, size: u64, array_index: i32, guid: Option<FGuid> },
+    StructStart { struct_name: String, guid: FGuid },
+    ArrayStart { element_type: String, len: usize },
+    /// A scalar, a struct stored as a plain value, or with [`DeserializerOptions::recover`] set a
+    /// property that couldn't be decoded
+    Value(Value),
+    Bytes(Vec<u8>),
+    End
+}
+
+enum Frame {
+    /// A property list up to `None`, the range of the enclosing value unless it is an array element
+    Properties { value: Option<Range<u64>> },
+    Array { element: Element, remaining: usize, index: usize, value: Range<u64> }
+}
+
+pub struct EventReader<R> {
+    input: R,
+    /// The top level property being read, the ones before it are dropped
+    buffer: Cursor<Vec<u8>>,
+    /// Offset of the buffer from where the reader started
+    base: u64,
+    options: DeserializerOptions,
+    path: Vec<PathSegment>,
+    diagnostics: Vec<Diagnostic>,
+    stack: Vec<Frame>,
+    /// A property whose tag was read, its value is the next event
+    pending: Option<Tagged>
+}
+
+/// Reads one event from the buffer of an [`EventReader`]
+struct Events<'a> {
+    de: Deserializer<'a>,
+    stack: &'a mut Vec<Frame>,
+    pending: &'a mut Option<Tagged>
+}
+
+impl<R: Read> EventReader<R> {
+    /// Reads the properties starting at the input's position, e.g. right after the header
+    pub fn new(input: R) -> Self {
+        EventReader::with_options(input, DeserializerOptions::default())
+    }
+
+    pub fn with_options(input: R, options: DeserializerOptions) -> Self {
+        EventReader {
+            input,
+            buffer: Cursor::new(Vec::new()),
+            base: 0,
+            options,
+            path: Vec::new(),
+            diagnostics: Vec::new(),
+            stack: vec![Frame::Properties { value: None }],
+            pending: None
+        }
+    }
+
+    /// Problems that were worked around so far, see [`DeserializerOptions::lenient`] and [`DeserializerOptions::recover`]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics
+    }
+
+    /// Makes sure the buffer holds the next top level property as a whole, or the `None` that ends them
+    fn fill(&mut self) -> Result<()> {
+        if self.stack.len() > 1 || self.pending.is_some() {
+            return Ok(());
+        }
+        let consumed = self.buffer.position();
+        self.buffer.get_mut().drain(..consumed as usize);
+        self.buffer.set_position(0);
+        self.base += consumed;
+
+        loop {
+            // the tag checks that its value is there, whatever else fails shows again when it is read
+            let complete = Deserializer::with_options(&mut self.buffer, self.options.clone()).next_tag();
+            self.buffer.set_position(0);
+            if !matches!(complete, Err(ref err) if matches!(err.code(), ErrorCode::UnexpectedEof)) {
+                return Ok(());
+            }
+            let len = self.buffer.get_ref().len();
+            let read = (&mut self.input).take(len.max(4096) as u64).read_to_end(self.buffer.get_mut())?;
+            if read == 0 {
+                return Ok(());
+            }
+            check_limit("total size", self.options.max_total_size, self.buffer.get_ref().len() as u64)?;
+        }
+    }
+
+    fn step(&mut self) -> Result<Option<Event>> {
+        if self.stack.is_empty() {
+            return Ok(None);
+        }
+        self.fill().map_err(|err| err.at(self.base, &[]))?;
+        let mut de = Deserializer::with_options(&mut self.buffer, self.options.clone());
+        de.path = mem::take(&mut self.path);
+        de.diagnostics = mem::take(&mut self.diagnostics);
+        let recorded = de.diagnostics.len();
+
+        let start = de.input.position();
+        let mut events = Events { de, stack: &mut self.stack, pending: &mut self.pending };
+        let event = events.step().map_err(|err| err.at(start, &events.de.path));
+
+        let de = events.de;
+        self.path = de.path;
+        self.diagnostics = de.diagnostics;
+        for diagnostic in &mut self.diagnostics[recorded..] {
+            diagnostic.offset += self.base;
+        }
+        event.map_err(|err| err.shifted(self.base))
+    }
+}
+
+impl<'a> Events<'a> {
+    fn step(&mut self) -> Result<Option<Event>> {
+        if let Some(tagged) = self.pending.take() {
+            return self.value(tagged).map(Some);
+        }
+
+        let element = match self.stack.last_mut() {
+            None => return Ok(None),
+            Some(Frame::Properties { value }) => {
+                let value = value.clone();
+                return match self.de.next_tag()? {
+                    Some(tagged) => {
+                        self.de.path.push(PathSegment::Field(tagged.name.clone()));
+                        let event = Event::PropertyStart {
+                            name: tagged.name.clone(),
+                            type_name: tagged.type_name.clone(),
+                            size: tagged.value.end - tagged.value.start,
+                            array_index: tagged.array_index,
+                            guid: tagged.guid
+                        };
+                        *self.pending = Some(tagged);
+                        Ok(Some(event))
+                    },
+                    None => self.end(value)
+                };
+            },
+            Some(Frame::Array { remaining: 0, value, .. }) => {
+                let value = value.clone();
+                return self.end(Some(value));
+            },
+            Some(Frame::Array { element: Element::Byte, remaining, .. }) => {
+                if *remaining as u64 > self.de.remaining() {
+                    return Err(Error::new(ErrorCode::UnexpectedEof));
+                }
+                let mut bytes = vec![0u8; *remaining];
+                *remaining = 0;
+                self.de.input.read_exact(&mut bytes)?;
+                return Ok(Some(Event::Bytes(bytes)));
+            },
+            Some(Frame::Array { element, remaining, index, .. }) => {
+                *remaining -= 1;
+                *index += 1;
+                self.de.path.push(PathSegment::Index(*index - 1));
+                element.clone()
+            }
+        };
+
+        let value = match element {
+            Element::Struct(tag) if !is_native(&tag.struct_name, &self.de.options.structs) => {
+                self.push(Frame::Properties { value: None })?;
+                return Ok(Some(Event::StructStart { struct_name: tag.struct_name, guid: tag.guid }));
+            },
+            Element::Struct(tag) => Value::Struct { value: self.de.read_struct_value(&tag.struct_name)?, struct_name: tag.struct_name, guid: tag.guid },
+            Element::Label => Value::Byte { enum_name: String::from("None"), value: ByteValue::Label(self.de.parse_string()?) },
+            Element::Bare(type_name) => self.de.read_bare(&type_name)?,
+            Element::Item(type_name, struct_name, size) => self.de.read_item(&type_name, struct_name.as_deref(), size)?,
+            Element::Byte => unreachable!("byte arrays are read at once")
+        };
+        self.de.path.pop();
+        Ok(Some(Event::Value(value)))
+    }
+
+    /// Reads the value of the property whose tag was just read
+    fn value(&mut self, tagged: Tagged) -> Result<Event> {
+        let size = tagged.value.end - tagged.value.start;
+        match tagged.tag {
+            Tag::Struct(struct_name, guid) if !is_native(&struct_name, &self.de.options.structs) => {
+                self.push(Frame::Properties { value: Some(tagged.value) })?;
+                Ok(Event::StructStart { struct_name, guid })
+            },
+            Tag::Array(element_type) => {
+                let len = self.de.input.read_i32::<LittleEndian>()?;
+                if len < 0 {
+                    return Err(Error::make_data(format!("Invalid array length {}", len)));
+                }
+                check_limit("array length", self.de.options.max_array_length as u64, len as u64)?;
+
+                let element = self.de.array_element(&element_type, size, len as usize)?;
+                self.push(Frame::Array { element, remaining: len as usize, index: 0, value: tagged.value })?;
+                Ok(Event::ArrayStart { element_type, len: len as usize })
+            },
+            tag => {
+                let value = match self.de.read_value(&tagged.type_name, tag, size as i32) {
+                    Ok(value) => {
+                        self.check_size(tagged.value)?;
+                        value
+                    },
+                    Err(err) if self.de.options.recover => {
+                        self.de.diagnostics.push(err.at(tagged.start, &self.de.path).into_diagnostic());
+                        self.de.input.set_position(tagged.value.end);
+
+                        let bytes = self.de.input.get_ref();
+                        Value::Unknown {
+                            tag: bytes[tagged.tag_range.start as usize..tagged.tag_range.end as usize].to_vec(),
+                            data: bytes[tagged.value.start as usize..tagged.value.end as usize].to_vec(),
+                            type_name: tagged.type_name
+                        }
+                    },
+                    Err(err) => return Err(err)
+                };
+                self.de.path.pop();
+                Ok(Event::Value(value))
+            }
+        }
+    }
+
+    fn push(&mut self, frame: Frame) -> Result<()> {
+        check_limit("nesting depth", self.de.options.max_depth as u64, self.stack.len() as u64)?;
+        self.stack.push(frame);
+        Ok(())
+    }
+
+    /// Closes the innermost struct or array, the end of the top level properties ends the stream
+    fn end(&mut self, value: Option<Range<u64>>) -> Result<Option<Event>> {
+        self.stack.pop();
+        if let Some(value) = value {
+            self.check_size(value)?;
+        }
+        if self.stack.is_empty() {
+            return Ok(None);
+        }
+        self.de.path.pop();
+        Ok(Some(Event::End))
+    }
+
+    /// Checks that a value took up exactly its declared size, like [`Deserializer::sized`]
+    fn check_size(&mut self, value: Range<u64>) -> Result<()> {
+        let position = self.de.input.position();
+        if position == value.end {
+            return Ok(());
+        }
+        let code = ErrorCode::SizeMismatch { expected: (value.end - value.start) as i64, actual: position as i64 - value.start as i64 };
+        match self.de.options.lenient {
+            true => {
+                self.de.diagnostics.push(Diagnostic::new(code, value.start, &self.de.path));
+                self.de.input.set_position(value.end);
+                Ok(())
+            },
+            false => Err(Error::new(code))
+        }
+    }
+}
+
+impl<R: Read> Iterator for EventReader<R> {
+    type Item = Result<Event>;
+
+    fn next(&mut self) -> Option<Result<Event>> {
+        match self.step() {
+            Ok(event) => event.map(Ok),
+            Err(err) => {
+                // the position in the stream is lost, so nothing follows an error
+                self.stack.clear();
+                self.pending = None;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::io::{Cursor, Read, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::error::{Error, ErrorCode, Result};
+
+fn remaining(input: &Cursor<Vec<u8>>) -> u64 {
+    (input.get_ref().len() as u64).saturating_sub(input.position())
+}
+
+/// Reads an `FString`: a positive length means a null terminated UTF-8 string,
+/// a negative one means that many UTF-16 code units (also null terminated).
+/// Lengths above `max_length` are rejected before anything is allocated.
+pub(crate) fn read_fstring(input: &mut Cursor<Vec<u8>>, max_length: usize) -> Result<String> {
+    let len = input.read_i32::<LittleEndian>()?;
+    if len.unsigned_abs() as u64 > max_length as u64 {
+        return Err(Error::new(ErrorCode::LimitExceeded { limit: "string length", max: max_length as u64, actual: len.unsigned_abs() as u64 }));
+    }
+    let mut s = if len >= 0 {
+        if len as u64 > remaining(input) {
+            return Err(Error::new(ErrorCode::UnexpectedEof));
+        }
+        let mut str_bytes = vec![0u8; len as usize];
+        input.read_exact(&mut str_bytes)?;
+        String::from_utf8(str_bytes)?
+    } else {
+        let units = len.checked_neg().ok_or_else(|| Error::new(ErrorCode::InvalidString(format!("Invalid string length {}", len).into())))? as u64;
+        if units * 2 > remaining(input) {
+            return Err(Error::new(ErrorCode::UnexpectedEof));
+        }
+        let mut str_units = vec![0u16; units as usize];
+        input.read_u16_into::<LittleEndian>(&mut str_units)?;
+        String::from_utf16(&str_units).map_err(|e| Error::new(ErrorCode::InvalidString(e.to_string().into())))?
+    };
+    if s.ends_with('\0') {
+        s.pop(); // nullbyte
+    }
+    Ok(s)
+}
+
+/// Writes an `FString` the way the engine does: empty strings have no terminator,
+/// anything that isn't plain ASCII is stored as UTF-16.
+pub(crate) fn write_fstring<W: Write>(output: &mut W, s: &str) -> Result<()> {
+    if s.is_empty() {
+        output.write_i32::<LittleEndian>(0)?;
+    } else if s.is_ascii() {
+        output.write_i32::<LittleEndian>(s.len() as i32 + 1)?;
+        output.write_all(s.as_bytes())?;
+        output.write_u8(0)?; // nullbyte
+    } else {
+        let units: Vec<u16> = s.encode_utf16().collect();
+        output.write_i32::<LittleEndian>(-(units.len() as i32 + 1))?;
+        for unit in units {
+            output.write_u16::<LittleEndian>(unit)?;
+        }
+        output.write_u16::<LittleEndian>(0)?; // nullbyte
+    }
+    Ok(())
+}
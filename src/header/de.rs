@@ -1,22 +1,27 @@
-use std::io::{Cursor, Read};
+use std::io::Cursor;
 
 use byteorder::{LittleEndian, ReadBytesExt};
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
 use serde::de::{
     self, DeserializeSeed, SeqAccess, Visitor
 };
-use crate::error::{Result, Error};
+use crate::compression::{self, Container};
+use crate::de::{check_limit, DeserializerOptions};
+use crate::error::{Result, Error, PathSegment};
+use crate::fstring::read_fstring;
 
-use crate::{unimplemented_deserialize};
 
 struct ArrayAccess<'a, 'de: 'a> {
     de: &'a mut Deserializer<'de>,
-    elements: i32
+    elements: i32,
+    index: usize,
+    fields: Option<&'static [&'static str]>
 }
 
 impl<'a, 'de: 'a> ArrayAccess<'a, 'de> {
-    fn new(de: &'a mut Deserializer<'de>, elements: i32) -> Self {
-        ArrayAccess {de, elements}
+    fn new(de: &'a mut Deserializer<'de>, elements: i32, fields: Option<&'static [&'static str]>) -> Self {
+        ArrayAccess {de, elements, index: 0, fields}
     }
 }
 
@@ -30,39 +35,69 @@ impl<'de, 'a> SeqAccess<'de> for ArrayAccess<'a, 'de> {
             return Ok(None);
         }
         self.elements -= 1;
-        seed.deserialize(&mut *self.de).map(Some)
+
+        let segment = match self.fields {
+            Some(fields) => PathSegment::Field(String::from(fields[self.index])),
+            None => PathSegment::Index(self.index)
+        };
+        self.index += 1;
+
+        let start = self.de.input.position();
+        self.de.path.push(segment);
+        let value = seed.deserialize(&mut *self.de).map(Some);
+        let value = value.map_err(|e| e.at(start, &self.de.path));
+        self.de.path.pop();
+        value
     }
 }
 
 pub struct Deserializer<'de> {
     input: &'de mut Cursor<Vec<u8>>,
+    path: Vec<PathSegment>,
+    options: DeserializerOptions
 }
 
 impl<'de> Deserializer<'de> {
     pub fn from_bytes(input: &'de mut Cursor<Vec<u8>>) -> Self {
-        Deserializer { input }
+        Deserializer::with_options(input, DeserializerOptions::default())
+    }
+
+    pub fn with_options(input: &'de mut Cursor<Vec<u8>>, options: DeserializerOptions) -> Self {
+        Deserializer { input, path: Vec::new(), options }
     }
 }
 
 pub fn from_bytes<'a, T>(s: &'a mut Cursor<Vec<u8>>) -> Result<T> where
     T: Deserialize<'a> {
-        let mut deserializer = Deserializer::from_bytes(s);
-        let t = T::deserialize(&mut deserializer)?;
+        from_bytes_with_options(s, DeserializerOptions::default())
+}
+
+pub fn from_bytes_with_options<'a, T>(s: &'a mut Cursor<Vec<u8>>, options: DeserializerOptions) -> Result<T> where
+    T: Deserialize<'a> {
+        let start = s.position();
+        options.check_total_size(s).map_err(|e| e.at(start, &[]))?;
+        let mut deserializer = Deserializer::with_options(s, options);
+        let t = T::deserialize(&mut deserializer).map_err(|e| e.at(start, &[]))?;
         Ok(t)
 }
 
+/// Reads the header of a whole save file, decompressing it first if it is stored in a [`Container`].
+/// Returns the container and the decompressed data, positioned where the body starts.
+pub fn from_save_bytes<T>(data: Vec<u8>, options: DeserializerOptions) -> Result<(T, Container, Cursor<Vec<u8>>)> where
+    T: DeserializeOwned {
+        let (container, data) = compression::decompress_with_options(data, &options)?;
+        let mut cursor = Cursor::new(data);
+        let header = from_bytes_with_options(&mut cursor, options)?;
+        Ok((header, container, cursor))
+}
+
 impl<'de> Deserializer<'de> {
     fn parse_string(&mut self) -> Result<String> {
-        let len = self.input.read_i32::<LittleEndian>()?;
-        let mut buf = vec![0u8; len as usize];
-        self.input.read_exact(&mut buf)?;
-        let mut s = String::from_utf8(buf)?;
-        s.pop(); // nullbyte
-        Ok(s)
+        read_fstring(self.input, self.options.max_string_length)
     }
 }
 
-impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
     type Error = Error;
 
     deserialize_macro!(deserialize_string, parse_string, visit_string);
@@ -85,49 +120,60 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         visitor.visit_u32(self.input.read_u32::<LittleEndian>()?)
     }
 
-    unimplemented_deserialize!(deserialize_any, deserialize_i16, deserialize_i64, deserialize_u64, deserialize_bool, deserialize_i8, deserialize_u8, deserialize_f32, deserialize_f64, deserialize_char, 
-        deserialize_str, deserialize_bytes, deserialize_byte_buf, deserialize_option, deserialize_unit, deserialize_map, deserialize_identifier, deserialize_ignored_any);
+    /// A field the header doesn't have, see [`GvasHeader`](crate::types::GvasHeader)
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+            V: Visitor<'de> {
+        visitor.visit_unit()
+    }
+
+    unsupported_deserialize!(deserialize_any, deserialize_i16, deserialize_i64, deserialize_u64, deserialize_bool, deserialize_i8, deserialize_u8, deserialize_f32, deserialize_f64, deserialize_char, 
+        deserialize_str, deserialize_bytes, deserialize_byte_buf, deserialize_option, deserialize_map, deserialize_identifier, deserialize_ignored_any);
     
     fn deserialize_unit_struct<V>(self, _: &'static str, _: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_unit_struct"))
     }
 
     fn deserialize_newtype_struct<V>(self, _: &'static str, _: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_newtype_struct"))
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
         let arr_len = self.input.read_i32::<LittleEndian>()?;
-        visitor.visit_seq(ArrayAccess::new(self, arr_len))
+        if arr_len < 0 {
+            return Err(Error::make_data(format!("Invalid array length {}", arr_len)));
+        }
+        check_limit("array length", self.options.max_array_length as u64, arr_len as u64)?;
+        visitor.visit_seq(ArrayAccess::new(self, arr_len, None))
     }
 
     fn deserialize_tuple<V>(self, _: usize, _: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_tuple"))
     }
 
     fn deserialize_tuple_struct<V>(self, _: &'static str, _: usize, _: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_tuple_struct"))
     }
 
     fn deserialize_struct<V>(self, _: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value> where
         V: Visitor<'de> {
-        visitor.visit_seq(ArrayAccess::new(self, fields.len() as i32))
+        visitor.visit_seq(ArrayAccess::new(self, fields.len() as i32, Some(fields)))
     }
 
     fn deserialize_enum<V>(self, _: &'static str, _: &'static [&'static str], _: V) -> Result<V::Value> where
         V: Visitor<'de> 
     {
-        unimplemented!()
+        Err(Error::unsupported("deserialize_enum"))
     }
 
 }
\ No newline at end of file
@@ -1,26 +1,42 @@
-use std::io::{Cursor, Write};
+use std::io::Write;
 
 use byteorder::{LittleEndian, WriteBytesExt};
 use serde::{Serialize, ser};
 
 use crate::error::{Result, Error};
+use crate::fstring::write_fstring;
 
-pub struct Serializer<'se> {
-    output: &'se mut Cursor<Vec<u8>>
+pub struct Serializer<W> {
+    output: W
+}
+
+impl<W: Write> Serializer<W> {
+    pub fn new(output: W) -> Self {
+        Serializer { output }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.output
+    }
 }
 
 pub fn to_vec<T>(value: &T) -> Result<Vec<u8>> 
 where
     T: Serialize {
-    let mut cursor = Cursor::new(Vec::new());
-    let mut serializer = Serializer {
-        output: &mut cursor
-    };
-    value.serialize(&mut serializer)?;
-    Ok(cursor.get_ref().to_vec())
+    let mut output = Vec::new();
+    to_writer(&mut output, value)?;
+    Ok(output)
 }
 
-impl<'se, 'a> ser::Serializer for &'a mut Serializer<'se> {
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<()>
+where
+    W: Write,
+    T: Serialize {
+    let mut serializer = Serializer::new(writer);
+    value.serialize(&mut serializer)
+}
+
+impl<W: Write> ser::Serializer for &mut Serializer<W> {
     type Ok = ();
 
     type Error = Error;
@@ -33,23 +49,20 @@ impl<'se, 'a> ser::Serializer for &'a mut Serializer<'se> {
     type SerializeStruct = Self;
     type SerializeStructVariant = Self;
 
-    unimplemented_serialize!(serialize_i16, i16);
-    unimplemented_serialize!(serialize_i64, i64);
-    unimplemented_serialize!(serialize_u64, u64);
-    unimplemented_serialize!(serialize_bool, bool);
-    unimplemented_serialize!(serialize_i8, i8);
-    unimplemented_serialize!(serialize_u8, u8);
-    unimplemented_serialize!(serialize_f32, f32);
-    unimplemented_serialize!(serialize_f64, f64);
-    unimplemented_serialize!(serialize_char, char);
-    unimplemented_serialize!(serialize_bytes, &[u8]);
-    unimplemented_serialize!(serialize_unit_struct, &'static str);
+    unsupported_serialize!(serialize_i16, i16);
+    unsupported_serialize!(serialize_i64, i64);
+    unsupported_serialize!(serialize_u64, u64);
+    unsupported_serialize!(serialize_bool, bool);
+    unsupported_serialize!(serialize_i8, i8);
+    unsupported_serialize!(serialize_u8, u8);
+    unsupported_serialize!(serialize_f32, f32);
+    unsupported_serialize!(serialize_f64, f64);
+    unsupported_serialize!(serialize_char, char);
+    unsupported_serialize!(serialize_bytes, &[u8]);
+    unsupported_serialize!(serialize_unit_struct, &'static str);
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok> {
-        self.output.write_i32::<LittleEndian>((v.len() as i32) + 1i32)?; // nullbyte
-        self.output.write(v.as_bytes())?;
-        self.output.write(&[0u8; 1])?; // nullbyte
-        Ok(())
+        write_fstring(&mut self.output, v)
     }
 
     fn serialize_i32(self, v: i32) -> Result<Self::Ok> {
@@ -69,88 +82,88 @@ impl<'se, 'a> ser::Serializer for &'a mut Serializer<'se> {
 
     
     fn serialize_unit(self) -> Result<Self::Ok> {
-        unimplemented!()
+        Err(Error::unsupported("serialize_unit"))
     }
 
     fn serialize_none(self) -> Result<Self::Ok> {
-        unimplemented!()
+        Err(Error::unsupported("serialize_none"))
     }
 
-    fn serialize_some<T: ?Sized>(self, value: &T) -> Result<Self::Ok>
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
     where
-        T: Serialize {
-        unimplemented!()
+        T: ?Sized + Serialize {
+        value.serialize(self)
     }
 
     fn serialize_unit_variant(
         self,
-        name: &'static str,
-        variant_index: u32,
-        variant: &'static str,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
     ) -> Result<Self::Ok> {
-        unimplemented!()
+        Err(Error::unsupported("serialize_unit_variant"))
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(
+    fn serialize_newtype_struct<T>(
         self,
-        name: &'static str,
-        value: &T,
+        _name: &'static str,
+        _value: &T,
     ) -> Result<Self::Ok>
     where
-        T: Serialize {
-        unimplemented!()
+        T: ?Sized + Serialize {
+        Err(Error::unsupported("serialize_newtype_struct"))
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(
+    fn serialize_newtype_variant<T>(
         self,
-        name: &'static str,
-        variant_index: u32,
-        variant: &'static str,
-        value: &T,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
     ) -> Result<Self::Ok>
     where
-        T: Serialize {
-        unimplemented!()
+        T: ?Sized + Serialize {
+        Err(Error::unsupported("serialize_newtype_variant"))
     }
 
     fn serialize_tuple_struct(
         self,
-        name: &'static str,
-        len: usize,
+        _name: &'static str,
+        _len: usize,
     ) -> Result<Self::SerializeTupleStruct> {
-        unimplemented!()
+        Err(Error::unsupported("serialize_tuple_struct"))
     }
 
     fn serialize_tuple_variant(
         self,
-        name: &'static str,
-        variant_index: u32,
-        variant: &'static str,
-        len: usize,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
-        unimplemented!()
+        Err(Error::unsupported("serialize_tuple_variant"))
     }
 
     fn serialize_struct(
         self,
-        name: &'static str,
-        len: usize,
+        _name: &'static str,
+        _len: usize,
     ) -> Result<Self::SerializeStruct> {
         Ok(self)
     }
 
     fn serialize_struct_variant(
         self,
-        name: &'static str,
-        variant_index: u32,
-        variant: &'static str,
-        len: usize,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
     ) -> Result<Self::SerializeStructVariant> {
-        unimplemented!()
+        Err(Error::unsupported("serialize_struct_variant"))
     }
 
-    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
-        unimplemented!()
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(Error::unsupported("serialize_map"))
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
@@ -162,18 +175,18 @@ impl<'se, 'a> ser::Serializer for &'a mut Serializer<'se> {
         }
     }
 
-    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
-        unimplemented!()
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(Error::unsupported("serialize_tuple"))
     }
 }
 
-impl<'se, 'a> ser::SerializeSeq for &'a mut Serializer<'se> {
+impl<W: Write> ser::SerializeSeq for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
     where
-        T: Serialize {
+        T: ?Sized + Serialize {
         value.serialize(&mut **self)
     }
 
@@ -182,84 +195,84 @@ impl<'se, 'a> ser::SerializeSeq for &'a mut Serializer<'se> {
     }
 }
 
-impl<'se, 'a> ser::SerializeTuple for &'a mut Serializer<'se> {
+impl<W: Write> ser::SerializeTuple for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    fn serialize_element<T>(&mut self, _value: &T) -> Result<()>
     where
-        T: Serialize {
-        unimplemented!()
+        T: ?Sized + Serialize {
+        Err(Error::unsupported("serialize_element"))
     }
 
     fn end(self) -> Result<Self::Ok> {
-        unimplemented!()
+        Err(Error::unsupported("end"))
     }
 }
 
-impl<'se, 'a> ser::SerializeTupleStruct for &'a mut Serializer<'se> {
+impl<W: Write> ser::SerializeTupleStruct for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    fn serialize_field<T>(&mut self, _value: &T) -> Result<()>
     where
-        T: Serialize {
-        unimplemented!()
+        T: ?Sized + Serialize {
+        Err(Error::unsupported("serialize_field"))
     }
 
     fn end(self) -> Result<Self::Ok> {
-        unimplemented!()
+        Err(Error::unsupported("end"))
     }
 }
 
-impl<'se, 'a> ser::SerializeTupleVariant for &'a mut Serializer<'se> {
+impl<W: Write> ser::SerializeTupleVariant for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    fn serialize_field<T>(&mut self, _value: &T) -> Result<()>
     where
-        T: Serialize {
-        unimplemented!()
+        T: ?Sized + Serialize {
+        Err(Error::unsupported("serialize_field"))
     }
 
     fn end(self) -> Result<Self::Ok> {
-        unimplemented!()
+        Err(Error::unsupported("end"))
     }
 }
 
-impl<'se, 'a> ser::SerializeMap for &'a mut Serializer<'se> {
+impl<W: Write> ser::SerializeMap for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
 
-    fn serialize_key<T: ?Sized>(&mut self, key: &T) -> Result<()>
+    fn serialize_key<T>(&mut self, _key: &T) -> Result<()>
     where
-        T: Serialize {
-        unimplemented!()
+        T: ?Sized + Serialize {
+        Err(Error::unsupported("serialize_key"))
     }
 
-    fn serialize_value<T: ?Sized>(&mut self, value: &T) -> Result<()>
+    fn serialize_value<T>(&mut self, _value: &T) -> Result<()>
     where
-        T: Serialize {
-        unimplemented!()
+        T: ?Sized + Serialize {
+        Err(Error::unsupported("serialize_value"))
     }
 
     fn end(self) -> Result<Self::Ok> {
-        unimplemented!()
+        Err(Error::unsupported("end"))
     }
 }
 
 
-impl<'se, 'a> ser::SerializeStruct for &'a mut Serializer<'se> {
+impl<W: Write> ser::SerializeStruct for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
 
-    fn serialize_field<T: ?Sized>(
+    fn serialize_field<T>(
         &mut self,
-        key: &'static str,
+        _key: &'static str,
         value: &T,
     ) -> Result<()>
     where
-        T: Serialize {
+        T: ?Sized + Serialize {
         value.serialize(&mut **self)
     }
 
@@ -268,21 +281,21 @@ impl<'se, 'a> ser::SerializeStruct for &'a mut Serializer<'se> {
     }
 }
 
-impl<'se, 'a> ser::SerializeStructVariant for &'a mut Serializer<'se> {
+impl<W: Write> ser::SerializeStructVariant for &mut Serializer<W> {
     type Ok = ();
     type Error = Error;
 
-    fn serialize_field<T: ?Sized>(
+    fn serialize_field<T>(
         &mut self,
-        key: &'static str,
-        value: &T,
+        _key: &'static str,
+        _value: &T,
     ) -> Result<()>
     where
-        T: Serialize {
-        unimplemented!()
+        T: ?Sized + Serialize {
+        Err(Error::unsupported("serialize_field"))
     }
 
     fn end(self) -> Result<Self::Ok> {
-        unimplemented!()
+        Err(Error::unsupported("end"))
     }
 }
\ No newline at end of file
@@ -0,0 +1,341 @@
+//! Hex dump of a save with every piece of the property tags and values labeled, for working out
+//! layouts the deserializers can't read yet.
+//!
+//! ```text
+//! 0000047E  08 00 00 00 75 38 5F 74 65 73 74 00              name "u8_test"
+//! 0000048A  0D 00 00 00 42 79 74 65 50 72 6F 70 65 72 74 79  type "ByteProperty"
+//! 0000049A  00
+//! 0000049B  01 00 00 00                                      size 1
+//! ```
+//!
+//! Struct members, elements and map entries are indented below their property. Reading stops at
+//! the first error, the bytes from there on are marked with `!!`.
+
+use std::fmt::Write;
+use std::io::Cursor;
+use std::ops::Range;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+
+use crate::compression;
+use crate::de::{check_limit, Deserializer, DeserializerOptions};
+use crate::error::{Error, ErrorCode, PathSegment, Result};
+use crate::header;
+use crate::patch::holds_labels;
+use crate::text::{format_guid, quote, scalar_text, struct_scalar_text};
+use crate::property::{PropertyType, Tag};
+use crate::structs::Item;
+use crate::types::{FGuid, GvasHeader};
+use crate::value::{is_native, item_size, StructValue, Value};
+
+const BYTES_PER_LINE: usize = 16;
+/// Longer values are cut off, like big byte arrays
+const MAX_LINES: usize = 8;
+const MAX_UNPARSED: usize = 512;
+
+struct Span {
+    range: Range<u64>,
+    depth: usize,
+    label: String
+}
+
+struct Annotator<'de> {
+    de: Deserializer<'de>,
+    spans: Vec<Span>,
+    depth: usize
+}
+
+impl<'de> Annotator<'de> {
+    /// Reads something and labels the bytes it took up, reads that took none aren't labeled
+    fn mark<T, F, L>(&mut self, read: F, label: L) -> Result<T>
+    where
+        F: FnOnce(&mut Deserializer<'de>) -> Result<T>,
+        L: FnOnce(&T) -> String {
+        let start = self.de.input.position();
+        let value = read(&mut self.de)?;
+        if self.de.input.position() != start {
+            self.spans.push(Span { range: start..self.de.input.position(), depth: self.depth, label: label(&value) });
+        }
+        Ok(value)
+    }
+
+    fn string(&mut self, what: &str) -> Result<String> {
+        self.mark(|de| de.parse_string(), |s| format!("{} {}", what, quote(s)))
+    }
+
+    fn int(&mut self, what: &str) -> Result<i32> {
+        self.mark(|de| Ok(de.input.read_i32::<LittleEndian>()?), |v| format!("{} {}", what, v))
+    }
+
+    fn guid(&mut self, what: &str) -> Result<FGuid> {
+        self.mark(|de| de.parse_guid(), |guid| format!("{} {}", what, format_guid(guid)))
+    }
+
+    fn guid_flag(&mut self) -> Result<()> {
+        if self.mark(|de| Ok(de.input.read_u8()?), |flag| format!("guid flag {}", flag))? != 0 {
+            self.guid("guid")?;
+        }
+        Ok(())
+    }
+
+    fn value(&mut self, read: impl FnOnce(&mut Deserializer<'de>) -> Result<Value>) -> Result<()> {
+        self.mark(read, |value| format!("value {}", scalar_text(value)))?;
+        Ok(())
+    }
+
+    fn nested<F>(&mut self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut Self) -> Result<()> {
+        check_limit("nesting depth", self.de.options.max_depth as u64, self.depth as u64 + 1)?;
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn properties(&mut self) -> Result<()> {
+        loop {
+            let start = self.de.input.position();
+            let name = self.mark(|de| de.parse_string(), |name| match name.as_str() {
+                "None" => String::from("None"),
+                name => format!("name {}", quote(name))
+            })?;
+            if name == "None" {
+                return Ok(());
+            }
+            self.de.path.push(PathSegment::Field(name));
+            let result = self.property().map_err(|e| e.at(start, &self.de.path));
+            self.de.path.pop();
+            result?;
+        }
+    }
+
+    fn property(&mut self) -> Result<()> {
+        let type_name = self.string("type")?;
+        let size = self.int("size")?;
+        self.int("array index")?;
+
+        let property_type = PropertyType::from_name(&type_name);
+        let tag = match &property_type {
+            PropertyType::Struct => {
+                let struct_name = self.string("struct name")?;
+                Tag::Struct(struct_name, self.guid("struct guid")?)
+            },
+            PropertyType::Array => Tag::Array(self.string("element type")?),
+            PropertyType::Set => Tag::Set(self.string("element type")?),
+            PropertyType::Map => Tag::Map(self.string("key type")?, self.string("value type")?),
+            property_type => self.mark(|de| property_type.read_tag(de), |tag| match tag {
+                Tag::Bool(value) => format!("value {}", value),
+                Tag::Enum(enum_name) => format!("enum name {}", quote(enum_name)),
+                _ => String::new()
+            })?
+        };
+        self.guid_flag()?;
+
+        let start = self.de.input.position();
+        if size < 0 || size as u64 > self.de.remaining() {
+            return Err(Error::new(ErrorCode::UnexpectedEof));
+        }
+        let end = start + size as u64;
+        match (&property_type, &tag) {
+            (PropertyType::Struct, Tag::Struct(struct_name, _)) => self.struct_value(struct_name)?,
+            (PropertyType::Array, Tag::Array(element_type)) => self.array(element_type, size)?,
+            (PropertyType::Set, Tag::Set(element_type)) => {
+                let struct_name = self.de.item_struct(self.de.property_name(), element_type, Item::Element);
+                self.nested(|annotator| {
+                    annotator.items("removed", None, &[], |annotator, _| annotator.item(element_type, struct_name.as_deref(), None))?;
+                    annotator.items("length", Some(end), &[element_type], |annotator, size| annotator.item(element_type, struct_name.as_deref(), size))
+                })?
+            },
+            (PropertyType::Map, Tag::Map(key_type, value_type)) => {
+                let key_struct = self.de.item_struct(self.de.property_name(), key_type, Item::Key);
+                let value_struct = self.de.item_struct(self.de.property_name(), value_type, Item::Value);
+                self.nested(|annotator| {
+                    annotator.items("removed", None, &[], |annotator, _| annotator.item(key_type, key_struct.as_deref(), None))?;
+                    annotator.items("length", Some(end), &[key_type, value_type], |annotator, size| {
+                        annotator.item(key_type, key_struct.as_deref(), size)?;
+                        annotator.item(value_type, value_struct.as_deref(), size)
+                    })
+                })?
+            },
+            (PropertyType::Other(_), _) => {
+                // unknown types are skipped, so the layout of their values can be worked out
+                self.de.input.set_position(end);
+                self.spans.push(Span { range: start..end, depth: self.depth, label: format!("undecoded {} value", type_name) });
+            },
+            (property_type, tag) => self.value(|de| property_type.read_value(de, tag))?
+        }
+
+        let actual = self.de.input.position() - start;
+        if actual != size as u64 {
+            return Err(Error::new(ErrorCode::SizeMismatch { expected: size as i64, actual: actual as i64 }));
+        }
+        Ok(())
+    }
+
+    fn struct_value(&mut self, struct_name: &str) -> Result<()> {
+        match is_native(struct_name, &self.de.options.structs) {
+            true => {
+                self.mark(|de| de.read_struct_value(struct_name), |value| match value {
+                    // decoded by a registered codec
+                    StructValue::Properties(fields) => {
+                        let fields: Vec<String> = fields.iter().map(|field| format!("{} = {}", field.name, scalar_text(&field.value))).collect();
+                        format!("{} {{ {} }}", struct_name, fields.join(", "))
+                    },
+                    _ => format!("value {}", struct_scalar_text(value))
+                })?;
+                Ok(())
+            },
+            false => self.nested(|annotator| annotator.properties())
+        }
+    }
+
+    /// Reads the length of an array, set or map, `limit` names the option it's checked against
+    fn length(&mut self, what: &str, limit: &'static str, max: usize) -> Result<i32> {
+        let elements = self.int(what)?;
+        if elements < 0 {
+            return Err(Error::make_data(format!("Invalid {} {}", limit, elements)));
+        }
+        check_limit(limit, max as u64, elements as u64)?;
+        Ok(elements)
+    }
+
+    fn array(&mut self, element_type: &str, size: i32) -> Result<()> {
+        let elements = self.length("length", "array length", self.de.options.max_array_length)?;
+        match PropertyType::from_name(element_type) {
+            PropertyType::Struct => {
+                self.string("name")?;
+                self.string("type")?;
+                self.int("size")?;
+                self.int("array index")?;
+                let struct_name = self.string("struct name")?;
+                self.guid("struct guid")?;
+                self.guid_flag()?;
+                self.elements(elements, |annotator| annotator.struct_value(&struct_name))
+            },
+            PropertyType::Byte if holds_labels(size as u64, elements as usize) => self.elements(elements, |annotator| annotator.string("value").map(|_| ())),
+            PropertyType::Byte => {
+                if elements as u64 > self.de.remaining() {
+                    return Err(Error::new(ErrorCode::UnexpectedEof));
+                }
+                let start = self.de.input.position();
+                self.de.input.set_position(start + elements as u64);
+                self.spans.push(Span { range: start..start + elements as u64, depth: self.depth, label: format!("{} bytes", elements) });
+                Ok(())
+            },
+            property_type => self.elements(elements, |annotator| annotator.value(|de| property_type.read_element(de)))
+        }
+    }
+
+    /// The elements of a set or map, struct items know their size like in the value tree
+    fn items<F>(&mut self, what: &str, end: Option<u64>, types: &[&str], mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, Option<u64>) -> Result<()> {
+        let elements = self.length(what, "map length", self.de.options.max_map_length)?;
+        let size = end.and_then(|end| item_size(end.checked_sub(self.de.input.position())?, elements as usize, types));
+        self.elements(elements, |annotator| f(annotator, size))
+    }
+
+    /// An element of a set or map, structs without a registered struct are tried as properties and
+    /// then as a guid like in the value tree
+    fn item(&mut self, type_name: &str, struct_name: Option<&str>, size: Option<u64>) -> Result<()> {
+        match (PropertyType::from_name(type_name), struct_name) {
+            (PropertyType::Struct, Some(struct_name)) => self.struct_value(struct_name),
+            (PropertyType::Struct, None) => {
+                let (start, spans) = (self.de.input.position(), self.spans.len());
+                if self.nested(|annotator| annotator.properties()).is_err() {
+                    self.de.input.set_position(start);
+                    self.spans.truncate(spans);
+                    if size != Some(16) {
+                        return Err(self.de.unsupported_item());
+                    }
+                    self.guid("value")?;
+                }
+                Ok(())
+            },
+            (property_type, _) => self.value(|de| property_type.read_element(de))
+        }
+    }
+
+    fn elements<F>(&mut self, elements: i32, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut Self) -> Result<()> {
+        self.nested(|annotator| {
+            for index in 0..elements as usize {
+                let start = annotator.de.input.position();
+                annotator.spans.push(Span { range: start..start, depth: annotator.depth - 1, label: format!("[{}]", index) });
+                annotator.de.path.push(PathSegment::Index(index));
+                let result = f(annotator).map_err(|e| e.at(start, &annotator.de.path));
+                annotator.de.path.pop();
+                result?;
+            }
+            Ok(())
+        })
+    }
+}
+
+fn write_line(output: &mut String, offset: u64, bytes: &[u8], label: &str) {
+    let hex: Vec<String> = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+    let line = format!("{:08X}  {:<width$}  {}", offset, hex.join(" "), label, width = BYTES_PER_LINE * 3 - 1);
+    output.push_str(line.trim_end());
+    output.push('\n');
+}
+
+fn write_span(output: &mut String, data: &[u8], range: Range<usize>, label: &str, max_lines: usize) {
+    if range.is_empty() {
+        let _ = writeln!(output, "{:8}  {:width$}  {}", "", "", label, width = BYTES_PER_LINE * 3 - 1);
+        return;
+    }
+    for (line, start) in range.clone().step_by(BYTES_PER_LINE).enumerate() {
+        if line == max_lines {
+            let _ = writeln!(output, "{:8}  ... {} more bytes", "", range.end - start);
+            break;
+        }
+        let end = (start + BYTES_PER_LINE).min(range.end);
+        write_line(output, start as u64, &data[start..end], if line == 0 { label } else { "" });
+    }
+}
+
+/// Dumps the header and properties of a save, see the module docs. Returns the error that stopped
+/// the dump, if any. Compressed saves are decompressed first, offsets are into the decompressed data.
+pub fn annotate(data: Vec<u8>) -> (String, Option<Error>) {
+    annotate_with_options(data, DeserializerOptions::default())
+}
+
+/// Like [`annotate`], e.g. with higher limits
+pub fn annotate_with_options(data: Vec<u8>, options: DeserializerOptions) -> (String, Option<Error>) {
+    let (container, data) = match compression::decompress_with_options(data, &options) {
+        Ok(decompressed) => decompressed,
+        Err(err) => return (format!("!! {}\n", err), Some(err))
+    };
+    let mut input = Cursor::new(data);
+    let mut spans = Vec::new();
+    let result = header::de::from_bytes_with_options::<GvasHeader>(&mut input, options.clone()).and_then(|header| {
+        spans.push(Span { range: 0..input.position(), depth: 0, label: format!("header, {}", header.save_game_class_name) });
+        let mut annotator = Annotator { de: Deserializer::with_options(&mut input, options), spans: Vec::new(), depth: 0 };
+        let result = annotator.properties();
+        spans.append(&mut annotator.spans);
+        result
+    });
+
+    let data = input.into_inner();
+    let mut output = String::new();
+    if !container.is_raw() {
+        let _ = writeln!(output, "decompressed from {:?}", container);
+    }
+    for span in &spans {
+        let label = format!("{}{}", "    ".repeat(span.depth), span.label);
+        write_span(&mut output, &data, span.range.start as usize..span.range.end as usize, &label, MAX_LINES);
+    }
+
+    let parsed = spans.iter().map(|span| span.range.end).max().unwrap_or_default() as usize;
+    let error = result.err();
+    match &error {
+        Some(err) => {
+            let _ = writeln!(output, "!! {}", err);
+            write_span(&mut output, &data, parsed..data.len(), "!! unparsed", MAX_UNPARSED / BYTES_PER_LINE);
+        },
+        None => write_span(&mut output, &data, parsed..data.len(), "trailing bytes", MAX_LINES)
+    }
+    (output, error)
+}
@@ -0,0 +1,126 @@
+//! Property metadata passed through serde as the name of a newtype struct, e.g.
+//! `$gvas:property=EnumProperty;enum_name=EDifficulty`. The serializer writes the value as that
+//! property type and the deserializer checks the tag against it. For arrays the hint describes
+//! the elements, unless the property is `ArrayProperty` itself. A bare `label` entry writes enums
+//! in a `ByteProperty` by name, tagged with the name of the Rust enum, instead of by index.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+pub use serde;
+use serde::de::{DeserializeSeed, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::{Error, Result};
+
+pub const PREFIX: &str = "$gvas:";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Hint {
+    pub(crate) property: Option<&'static str>,
+    pub(crate) enum_name: Option<&'static str>,
+    pub(crate) struct_name: Option<&'static str>,
+    pub(crate) label: bool
+}
+
+impl Hint {
+    pub(crate) fn parse(name: &'static str) -> Option<Hint> {
+        let mut hint = Hint::default();
+        for entry in name.strip_prefix(PREFIX)?.split(';') {
+            match entry.split_once('=') {
+                Some(("property", value)) => hint.property = Some(value),
+                Some(("enum_name", value)) => hint.enum_name = Some(value),
+                Some(("struct_name", value)) => hint.struct_name = Some(value),
+                None if entry == "label" => hint.label = true,
+                _ => {}
+            }
+        }
+        Some(hint)
+    }
+
+    /// The hint that applies to the elements of a sequence
+    pub(crate) fn elements(self) -> Hint {
+        match self.property {
+            Some("ArrayProperty") => Hint::default(),
+            _ => self
+        }
+    }
+
+    /// Fails if the value is about to be written as another property type than the hint asks for
+    pub(crate) fn check(&self, type_name: &str) -> Result<()> {
+        match self.property {
+            Some(property) if property != type_name => Err(Error::make_data(format!("{} can't be written as {}", type_name, property))),
+            _ => Ok(())
+        }
+    }
+
+    /// Fails if a tag that was read doesn't match the hint, `element_type` is set for arrays
+    pub(crate) fn check_tag(&self, type_name: &str, element_type: Option<&str>, enum_name: Option<&str>, struct_name: Option<&str>) -> Result<()> {
+        let actual = match (self.property, element_type) {
+            (Some("ArrayProperty"), _) | (_, None) => type_name,
+            (_, Some(element_type)) => element_type
+        };
+        if let Some(property) = self.property.filter(|property| *property != actual) {
+            return Err(Error::make_data(format!("Expected {} but found {}", property, actual)));
+        }
+        if let Some(expected) = self.enum_name.filter(|expected| Some(*expected) != enum_name) {
+            return Err(Error::make_data(format!("Expected enum {} but found {}", expected, enum_name.unwrap_or("None"))));
+        }
+        if let Some(expected) = self.struct_name.filter(|expected| Some(*expected) != struct_name) {
+            return Err(Error::make_data(format!("Expected struct {} but found {}", expected, struct_name.unwrap_or("None"))));
+        }
+        Ok(())
+    }
+}
+
+/// Serializes a value as a newtype struct named after a hint
+pub struct Hinted<'a, T: ?Sized>(pub &'static str, pub &'a T);
+
+impl<'a, T> Serialize for Hinted<'a, T>
+where
+    T: ?Sized + Serialize {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer {
+        serializer.serialize_newtype_struct(self.0, self.1)
+    }
+}
+
+/// Deserializes a value as a newtype struct named after a hint
+pub struct HintedSeed<T>(&'static str, PhantomData<T>);
+
+impl<T> HintedSeed<T> {
+    pub fn new(hint: &'static str) -> Self {
+        HintedSeed(hint, PhantomData)
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for HintedSeed<T>
+where
+    T: Deserialize<'de> {
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> std::result::Result<T, D::Error>
+    where
+        D: Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(self.0, HintedVisitor(PhantomData))
+    }
+}
+
+struct HintedVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for HintedVisitor<T>
+where
+    T: Deserialize<'de> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hinted property")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> std::result::Result<T, D::Error>
+    where
+        D: Deserializer<'de> {
+        T::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,149 @@
+//! Index of the properties of a save, for decoding just the ones that are needed.
+//!
+//! Building the index reads the header and the property tags, skipping over the values. Single
+//! properties can then be decoded into typed values or into the value tree.
+
+use std::io::Cursor;
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use serde::de::DeserializeOwned;
+
+use crate::compression::Container;
+use crate::de::{Deserializer, DeserializerOptions};
+use crate::error::{Error, Result};
+use crate::header;
+use crate::path::{Path, Segment};
+use crate::types::GvasHeader;
+use crate::value::{is_native, Value};
+use crate::property::Tag;
+
+/// A property found while building the index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub path: Path,
+    pub type_name: String,
+    pub array_index: i32,
+    /// Offset of the property in the data, where its name starts
+    pub offset: u64,
+    /// Size of the value, not counting the tag
+    pub size: u64
+}
+
+impl IndexEntry {
+    pub fn name(&self) -> &str {
+        match self.path.segments().last() {
+            Some(Segment::Field(name) | Segment::Slot(name, _)) => name,
+            _ => ""
+        }
+    }
+}
+
+pub struct SaveIndex {
+    input: Cursor<Vec<u8>>,
+    options: DeserializerOptions,
+    container: Container,
+    header: GvasHeader,
+    entries: Vec<IndexEntry>
+}
+
+impl SaveIndex {
+    /// Indexes the top level properties
+    pub fn new(data: Vec<u8>) -> Result<Self> {
+        SaveIndex::with_options(data, false, DeserializerOptions::default())
+    }
+
+    /// With `nested` set, the properties of structs and of struct array elements are indexed too,
+    /// e.g. `Inventory[3].Count`. Compressed saves are decompressed first, offsets are into the
+    /// decompressed data.
+    pub fn with_options(data: Vec<u8>, nested: bool, options: DeserializerOptions) -> Result<Self> {
+        let (header, container, mut input) = header::de::from_save_bytes(data, options.clone())?;
+        let start = input.position();
+        options.check_total_size(&input).map_err(|e| e.at(start, &[]))?;
+
+        let mut entries = Vec::new();
+        let mut deserializer = Deserializer::with_options(&mut input, options.clone());
+        scan(&mut deserializer, &mut Vec::new(), nested, &mut entries).map_err(|e| e.at(start, &[]))?;
+        Ok(SaveIndex { input, options, container, header, entries })
+    }
+
+    pub fn container(&self) -> Container {
+        self.container
+    }
+
+    pub fn header(&self) -> &GvasHeader {
+        &self.header
+    }
+
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    pub fn get(&self, path: &str) -> Option<&IndexEntry> {
+        let path: Path = path.parse().ok()?;
+        self.entries.iter().find(|entry| entry.path == path)
+    }
+
+    fn offset(&self, path: &str) -> Result<u64> {
+        self.get(path).map(|entry| entry.offset).ok_or_else(|| Error::make_data(format!("No property {} in the index", path)))
+    }
+
+    /// Deserializes a single property into a typed value
+    pub fn decode<T>(&mut self, path: &str) -> Result<T>
+    where
+        T: DeserializeOwned {
+        let offset = self.offset(path)?;
+        self.input.set_position(offset);
+        let mut deserializer = Deserializer::for_property(&mut self.input, self.options.clone());
+        T::deserialize(&mut deserializer).map_err(|e| e.at(offset, &[]))
+    }
+
+    /// Reads a single property into the value tree
+    pub fn value(&mut self, path: &str) -> Result<Value> {
+        let offset = self.offset(path)?;
+        self.input.set_position(offset);
+        let mut deserializer = Deserializer::with_options(&mut self.input, self.options.clone());
+        deserializer.parse_string()?;
+        Ok(deserializer.read_property(offset).map_err(|e| e.at(offset, &[]))?.2)
+    }
+
+    /// The decompressed data
+    pub fn into_inner(self) -> Vec<u8> {
+        self.input.into_inner()
+    }
+}
+
+/// Records the properties of a property list, skipping their values unless `nested` is set
+fn scan(de: &mut Deserializer, prefix: &mut Vec<Segment>, nested: bool, entries: &mut Vec<IndexEntry>) -> Result<()> {
+    while let Some(tagged) = de.next_tag()? {
+        prefix.push(match tagged.array_index {
+            0 => Segment::Field(tagged.name),
+            array_index => Segment::Slot(tagged.name, array_index)
+        });
+        entries.push(IndexEntry {
+            path: Path::from_segments(prefix.clone()),
+            type_name: tagged.type_name,
+            array_index: tagged.array_index,
+            offset: tagged.start,
+            size: tagged.value.end - tagged.value.start
+        });
+
+        match tagged.tag {
+            Tag::Struct(struct_name, _) if nested && !is_native(&struct_name, &de.options.structs) => de.nested(|de| scan(de, prefix, nested, entries))?,
+            Tag::Array(element_type) if nested && element_type == "StructProperty" => {
+                let elements = de.input.read_i32::<LittleEndian>()?;
+                if !is_native(&de.struct_array_tag()?.struct_name, &de.options.structs) {
+                    for index in 0..elements.max(0) as usize {
+                        prefix.push(Segment::Index(index));
+                        let scanned = de.nested(|de| scan(de, prefix, nested, entries));
+                        prefix.pop();
+                        scanned?;
+                    }
+                }
+            },
+            _ => {}
+        }
+        de.input.set_position(tagged.value.end);
+        prefix.pop();
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! Lossless JSON representation of a save, built on the [`value`](crate::value) tree.
+//!
+//! A save is an object with the `header` (field names as in [`GvasHeader`](crate::types::GvasHeader))
+//! and the body `properties`. Each property records its name, its UE property type and the value:
+//!
+//! ```json
+//! { "name": "Gold", "type": "IntProperty", "value": 250 }
+//! { "name": "Difficulty", "type": "ByteProperty", "value": { "enum_name": "EDifficulty", "value": "EDifficulty::Hard" } }
+//! { "name": "Stats", "type": "StructProperty", "value": {
+//!     "struct_name": "PlayerStats", "guid": { "a": 0, "b": 0, "c": 0, "d": 0 },
+//!     "value": { "Properties": [ ... ] } } }
+//! { "name": "Scores", "type": "ArrayProperty", "value": { "element_type": "IntProperty", "value": { "Values": [ ... ] } } }
+//! ```
+//!
+//! `array_index` and the property `guid` are only present when set. Properties that couldn't be decoded
+//! have the type `Unknown` and keep the raw bytes of their tag and value, so nothing is lost when
+//! converting back. Converting a save to JSON and back reproduces it byte for byte, as long as its
+//! strings are encoded the way the engine writes them.
+
+use crate::error::{Error, Result};
+use crate::value::Save;
+
+pub fn to_string(save: &Save) -> Result<String> {
+    serde_json::to_string_pretty(save).map_err(|e| Error::make_data(e.to_string()))
+}
+
+pub fn from_str(s: &str) -> Result<Save> {
+    serde_json::from_str(s).map_err(|e| Error::make_data(e.to_string()))
+}
@@ -1,7 +1,33 @@
 #[macro_use]
 mod macros;
-mod error;
-pub use crate::de::from_bytes;
+pub mod error;
+mod fstring;
+#[doc(hidden)]
+pub mod hint;
+pub use crate::de::{from_bytes, from_bytes_with_options, from_bytes_with_diagnostics, DeserializerOptions};
+pub use crate::ser::{to_vec, to_vec_with_structs, to_writer, to_writer_with_structs};
+pub use crate::error::{Diagnostic, Error, ErrorCode, Result};
+pub use crate::diff::diff;
+#[cfg(feature = "derive")]
+pub use serde_gvas_derive::{GvasDeserialize, GvasSerialize};
 pub mod types;
+pub mod custom_version;
+pub mod engine_version;
 pub mod de;
-pub mod header;
\ No newline at end of file
+pub mod ser;
+pub mod header;
+pub mod value;
+pub mod property;
+pub mod structs;
+pub mod path;
+pub mod patch;
+pub mod index;
+pub mod events;
+pub mod hexdump;
+pub mod transform;
+pub mod text;
+pub mod diff;
+pub mod codegen;
+pub mod compression;
+#[cfg(feature = "json")]
+pub mod json;
@@ -24,45 +24,137 @@ macro_rules! deserialize_macro {
 }
 
 #[macro_export]
-macro_rules! unimplemented_deserialize {
+macro_rules! unsupported_deserialize {
     ($($method:ident),*) => {
         $(fn $method<V>(self, _: V) -> Result<V::Value> where
             V: Visitor<'de> {
-                unimplemented!()
+                Err(Error::unsupported(stringify!($method)))
             })*
     };
 }
 
 #[macro_export]
-macro_rules! unimplemented_serialize {
+macro_rules! unsupported_serialize {
     ($method:ident,$type:ty) => {
         fn $method(self, _: $type) -> Result<Self::Ok> {
-            unimplemented!()
+            Err(Error::unsupported(stringify!($method)))
         }
     };
 }
 
 #[macro_export]
-macro_rules! parse_number_property {
-    ($method:ident, $read_method:ident, $endianness:ty, $num:ty, $size:literal) => {
-        fn $method(&mut self) -> Result<$num> {
-            let value_size = self.input.read_i64::<LittleEndian>()?;
-            if value_size != $size {
-                return Err(Error::make_other(format!("Expected value size of {} got {} at position {}", $size, value_size, self.input.position())));
+macro_rules! unsupported_compound_serialize {
+    () => {
+        fn serialize_unit(self) -> Result<Self::Ok> {
+            Err(Error::unsupported("serialize_unit"))
+        }
+
+        fn serialize_newtype_variant<T>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _value: &T) -> Result<Self::Ok>
+        where
+            T: ?Sized + Serialize {
+            Err(Error::unsupported("serialize_newtype_variant"))
+        }
+
+        fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct> {
+            Err(Error::unsupported("serialize_tuple_struct"))
+        }
+
+        fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant> {
+            Err(Error::unsupported("serialize_tuple_variant"))
+        }
+
+        fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+            Err(Error::unsupported("serialize_map"))
+        }
+
+        fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant> {
+            Err(Error::unsupported("serialize_struct_variant"))
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! unsupported_option_serialize {
+    () => {
+        fn serialize_none(self) -> Result<Self::Ok> {
+            Err(Error::unsupported("serialize_none"))
+        }
+
+        fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok>
+        where
+            T: ?Sized + Serialize {
+            Err(Error::unsupported("serialize_some"))
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! unsupported_newtype_serialize {
+    () => {
+        fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str) -> Result<Self::Ok> {
+            Err(Error::unsupported("serialize_unit_variant"))
+        }
+
+        fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> Result<Self::Ok>
+        where
+            T: ?Sized + Serialize {
+            Err(Error::unsupported("serialize_newtype_struct"))
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! unsupported_tuple_serialize {
+    () => {
+        fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+            Err(Error::unsupported("serialize_tuple"))
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! serialize_number_property {
+    ($method:ident, $num:ty, $codec:ty) => {
+        fn $method(mut self, v: $num) -> Result<Self::Ok> {
+            match self.hint.struct_name {
+                // a number is all there is to a native struct like DateTime
+                Some(struct_name) => self.begin_struct(struct_name)?,
+                None => {
+                    self.hint.check(<$codec>::TYPE.name())?;
+                    self.ser.write_tag(self.name, <$codec>::TYPE.name(), self.sizes.value)?;
+                    self.ser.begin_value()?;
+                }
             }
-            self.input.read_exact(&mut [0u8; 1])?;
-            Ok(self.input.$read_method::<$endianness>()?)
+            <$codec>::write_value(&mut self.ser.output, &v)
         }
     };
+}
 
-    ($method:ident, $read_method:ident, $num:ty, $size:literal) => {
-        fn $method(&mut self) -> Result<$num> {
-            let value_size = self.input.read_i64::<LittleEndian>()?;
-            if value_size != $size {
-                return Err(Error::make_other(format!("Expected value size of {} got {} at position {}", $size, value_size, self.input.position())));
+#[macro_export]
+macro_rules! serialize_number_element {
+    ($method:ident, $num:ty, $codec:ty) => {
+        fn $method(self, v: $num) -> Result<Self::Ok> {
+            match self.hint.struct_name {
+                Some(struct_name) => {
+                    self.hint.check("StructProperty")?;
+                    self.array.start("StructProperty", Some(struct_name))?;
+                },
+                None => {
+                    self.hint.check(<$codec>::TYPE.name())?;
+                    self.array.start(<$codec>::TYPE.name(), None)?;
+                }
             }
-            self.input.read_exact(&mut [0u8; 1])?;
-            Ok(self.input.$read_method()?)
+            <$codec>::write_element(&mut self.array.ser.output, &v)
         }
-    }
-}
\ No newline at end of file
+    };
+}
+
+#[macro_export]
+macro_rules! serialize_number_value {
+    ($method:ident, $num:ty, $codec:ty, $variant:ident) => {
+        fn $method(self, v: $num) -> Result<Self::Ok> {
+            self.hint.check(<$codec>::TYPE.name())?;
+            Ok(Some(Value::$variant(v)))
+        }
+    };
+}
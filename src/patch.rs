@@ -0,0 +1,442 @@
+//! Reading and replacing single values of an encoded save without decoding the rest of it.
+//!
+//! The properties on the way to a [`Path`] are found by reading their tags and skipping over their
+//! values, so only the value being replaced is decoded. When the new value takes up a different
+//! number of bytes, e.g. a longer string, the sizes of every enclosing struct, array, set and map are
+//! fixed up. Structs registered in [`crate::structs`] have no tags to find their fields by, they are
+//! decoded as a whole to get at a field and encoded again to change it.
+
+use std::io::Cursor;
+use std::ops::Range;
+
+use byteorder::{ByteOrder, LittleEndian, ReadBytesExt};
+
+use crate::de::{Deserializer, DeserializerOptions};
+use crate::error::{Error, ErrorCode, Result};
+use crate::path::{self, cannot_address, check_type, map_key, missing, no_entry, out_of_range, same_key, Path, Segment};
+use crate::structs::Item;
+use crate::types::FGuid;
+use crate::value::{is_native, item_size, write_item, write_struct_value, write_value, ByteValue, StructValue, Value};
+use crate::property::{self, Tag};
+
+/// The tag of a property, read up to where its value starts
+pub(crate) struct Tagged {
+    pub(crate) name: String,
+    pub(crate) start: u64,
+    pub(crate) type_name: String,
+    pub(crate) size_at: u64,
+    pub(crate) array_index: i32,
+    pub(crate) tag: Tag,
+    /// Where the type specific part of the tag is, see [`Value::Unknown`]
+    pub(crate) tag_range: Range<u64>,
+    pub(crate) guid: Option<FGuid>,
+    pub(crate) value: Range<u64>
+}
+
+enum Leaf {
+    Property(Tagged),
+    Element { range: Range<u64>, element: Element }
+}
+
+/// What the elements of an array, set or map are, to read them one by one
+#[derive(Clone)]
+pub(crate) enum Element {
+    Byte,
+    /// The label of an enum byte, see [`holds_labels`]
+    Label,
+    Bare(String),
+    Struct(ArrayTag),
+    /// An element of a set or map, structs among them only have a struct when one is registered
+    /// for them, see [`crate::structs::StructRegistry::register_item`]. Last is the size of a struct
+    /// item when the size of the set or map tells, see [`crate::value::item_size`].
+    Item(String, Option<String>, Option<u64>)
+}
+
+/// The tag that precedes the elements of a struct array
+#[derive(Clone)]
+pub(crate) struct ArrayTag {
+    pub(crate) name: String,
+    pub(crate) size_at: u64,
+    pub(crate) size: i32,
+    pub(crate) array_index: i32,
+    pub(crate) struct_name: String,
+    pub(crate) guid: FGuid,
+    pub(crate) property_guid: Option<FGuid>
+}
+
+/// Whether an array of `elements` bytes taking up `size` holds enum labels instead, which only shows in the size
+pub(crate) fn holds_labels(size: u64, elements: usize) -> bool {
+    size != elements as u64 + 4
+}
+
+impl Element {
+    fn describe(&self) -> String {
+        match self {
+            Element::Byte | Element::Label => String::from("ByteProperty"),
+            Element::Bare(type_name) | Element::Item(type_name, None, _) => type_name.clone(),
+            Element::Item(_, Some(struct_name), _) => format!("StructProperty<{}>", struct_name),
+            Element::Struct(tag) => format!("StructProperty<{}>", tag.struct_name)
+        }
+    }
+}
+
+/// How the fields of a struct can be followed
+enum Fields {
+    Tagged,
+    /// Only by decoding the whole struct, see [`crate::structs`]
+    Registered,
+    Missing
+}
+
+/// Where a value was found, with the offsets of the sizes of everything enclosing it
+struct Target {
+    leaf: Leaf,
+    /// Where the segments inside a registered struct start when the leaf is one
+    inner: Option<usize>,
+    sizes: Vec<u64>
+}
+
+fn describe_tag(tagged: &Tagged) -> String {
+    match &tagged.tag {
+        Tag::Enum(enum_name) if enum_name != "None" => format!("{}<{}>", tagged.type_name, enum_name),
+        Tag::Struct(struct_name, _) => format!("StructProperty<{}>", struct_name),
+        Tag::Array(element_type) => format!("ArrayProperty<{}>", element_type),
+        _ => tagged.type_name.clone()
+    }
+}
+
+impl<'de> Deserializer<'de> {
+    /// Reads the next tag of a property list, `None` at its end
+    pub(crate) fn next_tag(&mut self) -> Result<Option<Tagged>> {
+        let start = self.input.position();
+        let name = self.parse_string()?;
+        if name == "None" {
+            return Ok(None);
+        }
+        let type_name = self.parse_string()?;
+        let size_at = self.input.position();
+        let size = self.input.read_i32::<LittleEndian>()?;
+        let array_index = self.input.read_i32::<LittleEndian>()?;
+        let tag_start = self.input.position();
+        let tag = self.read_tag(&type_name)?;
+        let tag_range = tag_start..self.input.position();
+        let guid = match self.input.read_u8()? {
+            0 => None,
+            _ => Some(self.parse_guid()?)
+        };
+
+        let value_start = self.input.position();
+        if size < 0 || size as u64 > self.remaining() {
+            return Err(Error::new(ErrorCode::UnexpectedEof));
+        }
+        Ok(Some(Tagged { name, start, type_name, size_at, array_index, tag, tag_range, guid, value: value_start..value_start + size as u64 }))
+    }
+
+    /// Reads the tag that precedes the elements of a struct array
+    pub(crate) fn struct_array_tag(&mut self) -> Result<ArrayTag> {
+        let name = self.parse_string()?;
+        let _type_name = self.parse_string()?;
+        let size_at = self.input.position();
+        let size = self.input.read_i32::<LittleEndian>()?;
+        let array_index = self.input.read_i32::<LittleEndian>()?;
+        let struct_name = self.parse_string()?;
+        let guid = self.parse_guid()?;
+        let property_guid = match self.input.read_u8()? {
+            0 => None,
+            _ => Some(self.parse_guid()?)
+        };
+        Ok(ArrayTag { name, size_at, size, array_index, struct_name, guid, property_guid })
+    }
+
+    /// Reads what precedes the elements of an array after its length, `size` is that of the whole array
+    pub(crate) fn array_element(&mut self, element_type: &str, size: u64, elements: usize) -> Result<Element> {
+        Ok(match element_type {
+            "StructProperty" => Element::Struct(self.struct_array_tag()?),
+            "ByteProperty" if holds_labels(size, elements) => Element::Label,
+            "ByteProperty" => Element::Byte,
+            _ => Element::Bare(String::from(element_type))
+        })
+    }
+
+    fn find_property(&mut self, name: &str, array_index: i32) -> Result<Option<Tagged>> {
+        while let Some(tagged) = self.next_tag()? {
+            if tagged.name == name && tagged.array_index == array_index {
+                return Ok(Some(tagged));
+            }
+            self.input.set_position(tagged.value.end);
+        }
+        Ok(None)
+    }
+
+    fn read_len(&mut self, kind: &str) -> Result<usize> {
+        let len = self.input.read_i32::<LittleEndian>()?;
+        if len < 0 {
+            return Err(Error::make_data(format!("Invalid {} length {}", kind, len)));
+        }
+        Ok(len as usize)
+    }
+
+    fn skip_element(&mut self, element: &Element) -> Result<()> {
+        match element {
+            Element::Byte => {
+                self.input.read_u8()?;
+            },
+            Element::Label => {
+                self.parse_string()?;
+            },
+            Element::Bare(type_name) => {
+                self.read_bare(type_name)?;
+            },
+            Element::Struct(tag) if is_native(&tag.struct_name, &self.options.structs) => {
+                self.read_struct_value(&tag.struct_name)?;
+            },
+            Element::Struct(_) => while let Some(tagged) = self.next_tag()? {
+                self.input.set_position(tagged.value.end);
+            },
+            Element::Item(type_name, struct_name, size) => {
+                self.read_item(type_name, struct_name.as_deref(), *size)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the element that starts at the input's position has fields, leaving the input there
+    fn element_fields(&mut self, element: &Element) -> Result<Fields> {
+        Ok(match element {
+            Element::Struct(ArrayTag { struct_name, .. }) | Element::Item(_, Some(struct_name), _) if self.options.structs.is_registered(struct_name) => Fields::Registered,
+            Element::Struct(ArrayTag { struct_name, .. }) | Element::Item(_, Some(struct_name), _) if !is_native(struct_name, &self.options.structs) => Fields::Tagged,
+            Element::Item(type_name, None, size) if type_name == "StructProperty" => {
+                let start = self.input.position();
+                let item = self.read_item(type_name, None, *size)?;
+                self.input.set_position(start);
+                match item {
+                    Value::Struct { value: StructValue::Properties(_), .. } => Fields::Tagged,
+                    _ => Fields::Missing
+                }
+            },
+            _ => Fields::Missing
+        })
+    }
+
+    /// Finds an element of the array whose length was just read, the path ends at its index
+    fn find_array_element(&mut self, element_type: &str, tagged: &Tagged, index: usize, path: &[Segment], sizes: &mut Vec<u64>) -> Result<Element> {
+        let elements = self.read_len("array")?;
+        if index >= elements {
+            return Err(out_of_range(path, elements));
+        }
+        let element = self.array_element(element_type, tagged.value.end - tagged.value.start, elements)?;
+        if let Element::Struct(tag) = &element {
+            sizes.push(tag.size_at);
+        }
+        match element {
+            Element::Byte => self.input.set_position(self.input.position() + index as u64),
+            _ => for _ in 0..index {
+                self.skip_element(&element)?;
+            }
+        }
+        Ok(element)
+    }
+
+    /// Finds an element of the set that ends at `end`, the path ends at its index
+    fn set_element(&mut self, name: &str, element_type: &str, end: u64, index: usize, path: &[Segment]) -> Result<Element> {
+        let struct_name = self.item_struct(name, element_type, Item::Element);
+        let removed = Element::Item(String::from(element_type), struct_name.clone(), None);
+        for _ in 0..self.read_len("set")? {
+            self.skip_element(&removed)?;
+        }
+        let elements = self.read_len("set")?;
+        if index >= elements {
+            return Err(out_of_range(path, elements));
+        }
+        let size = end.checked_sub(self.input.position()).and_then(|remaining| item_size(remaining, elements, &[element_type]));
+        let element = Element::Item(String::from(element_type), struct_name, size);
+        for _ in 0..index {
+            self.skip_element(&element)?;
+        }
+        Ok(element)
+    }
+
+    /// Finds the value of a map entry by its key in the map that ends at `end`, the path ends at the key
+    fn map_value(&mut self, name: &str, key_type: &str, value_type: &str, end: u64, path: &[Segment]) -> Result<Element> {
+        let key = map_key(key_type, &path[path.len() - 1])?;
+        let key_struct = self.item_struct(name, key_type, Item::Key);
+        let value_struct = self.item_struct(name, value_type, Item::Value);
+        let removed = Element::Item(String::from(key_type), key_struct.clone(), None);
+        for _ in 0..self.read_len("map")? {
+            self.skip_element(&removed)?;
+        }
+        let entries = self.read_len("map")?;
+        let size = end.checked_sub(self.input.position()).and_then(|remaining| item_size(remaining, entries, &[key_type, value_type]));
+        let value_element = Element::Item(String::from(value_type), value_struct, size);
+        for _ in 0..entries {
+            if same_key(&self.read_item(key_type, key_struct.as_deref(), size)?, &key) {
+                return Ok(value_element);
+            }
+            self.skip_element(&value_element)?;
+        }
+        Err(no_entry(path))
+    }
+
+    /// Follows the path from the start of a property list, see the module docs
+    fn locate(&mut self, path: &Path) -> Result<Target> {
+        let segments = path.segments();
+        let mut sizes = Vec::new();
+        let mut depth = 0;
+        loop {
+            let (name, array_index) = segments[depth].property().ok_or_else(|| cannot_address(&segments[..=depth], String::from("a property list")))?;
+            let tagged = self.find_property(name, array_index)?.ok_or_else(|| missing(&segments[..=depth]))?;
+            depth += 1;
+            let segment = match segments.get(depth) {
+                Some(segment) => segment,
+                None => return Ok(Target { leaf: Leaf::Property(tagged), inner: None, sizes })
+            };
+
+            let element = match (&tagged.tag, segment) {
+                (Tag::Struct(struct_name, _), segment) if segment.property().is_some() && self.options.structs.is_registered(struct_name) => {
+                    return Ok(Target { leaf: Leaf::Property(tagged), inner: Some(depth), sizes });
+                },
+                (Tag::Struct(struct_name, _), segment) if segment.property().is_some() && !is_native(struct_name, &self.options.structs) => {
+                    sizes.push(tagged.size_at);
+                    continue;
+                },
+                (Tag::Array(element_type), Segment::Index(index)) => {
+                    sizes.push(tagged.size_at);
+                    self.find_array_element(element_type, &tagged, *index, &segments[..=depth], &mut sizes)?
+                },
+                (Tag::Set(element_type), Segment::Index(index)) => {
+                    sizes.push(tagged.size_at);
+                    self.set_element(&tagged.name, element_type, tagged.value.end, *index, &segments[..=depth])?
+                },
+                (Tag::Map(key_type, value_type), Segment::Key(_) | Segment::Index(_)) => {
+                    sizes.push(tagged.size_at);
+                    self.map_value(&tagged.name, key_type, value_type, tagged.value.end, &segments[..=depth])?
+                },
+                _ => return Err(cannot_address(&segments[..=depth], describe_tag(&tagged)))
+            };
+            depth += 1;
+
+            let start = self.input.position();
+            let fields = match segments.get(depth) {
+                Some(segment) if segment.property().is_some() => self.element_fields(&element)?,
+                Some(_) => Fields::Missing,
+                None => {
+                    self.skip_element(&element)?;
+                    return Ok(Target { leaf: Leaf::Element { range: start..self.input.position(), element }, inner: None, sizes });
+                }
+            };
+            match fields {
+                Fields::Tagged => {},
+                Fields::Registered => {
+                    self.skip_element(&element)?;
+                    return Ok(Target { leaf: Leaf::Element { range: start..self.input.position(), element }, inner: Some(depth), sizes });
+                },
+                Fields::Missing => return Err(cannot_address(&segments[..=depth], element.describe()))
+            }
+        }
+    }
+
+    /// Decodes the value that was located
+    fn read_leaf(&mut self, leaf: &Leaf) -> Result<Value> {
+        match leaf {
+            Leaf::Property(tagged) => {
+                self.input.set_position(tagged.start);
+                self.parse_string()?;
+                Ok(self.read_property(tagged.start)?.2)
+            },
+            Leaf::Element { range, element } => {
+                self.input.set_position(range.start);
+                Ok(match element {
+                    Element::Byte => Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(self.input.read_u8()?) },
+                    Element::Label => Value::Byte { enum_name: String::from("None"), value: ByteValue::Label(self.parse_string()?) },
+                    Element::Bare(type_name) => self.read_bare(type_name)?,
+                    Element::Struct(tag) => Value::Struct { struct_name: tag.struct_name.clone(), guid: tag.guid, value: self.read_struct_value(&tag.struct_name)? },
+                    Element::Item(type_name, struct_name, size) => self.read_item(type_name, struct_name.as_deref(), *size)?
+                })
+            }
+        }
+    }
+}
+
+/// Locates a value and reads it, leaving the input where it was
+fn find(input: &mut Cursor<Vec<u8>>, path: &Path, options: &DeserializerOptions) -> Result<(Target, Value)> {
+    let start = input.position();
+    let mut deserializer = Deserializer::with_options(input, options.clone());
+    let found = deserializer.locate(path).and_then(|target| {
+        let value = deserializer.read_leaf(&target.leaf)?;
+        Ok((target, value))
+    });
+    input.set_position(start);
+    found
+}
+
+/// Reads the value at `path` from the properties starting at the input's position, e.g. right after the header
+pub fn get(input: &mut Cursor<Vec<u8>>, path: &Path) -> Result<Value> {
+    get_with_options(input, path, &DeserializerOptions::default())
+}
+
+/// Like [`get`], with the limits and registered structs of `options`
+pub fn get_with_options(input: &mut Cursor<Vec<u8>>, path: &Path, options: &DeserializerOptions) -> Result<Value> {
+    let (target, value) = find(input, path, options)?;
+    match target.inner {
+        Some(from) => Ok(path::get_in(&value, path, from)?.clone()),
+        None => Ok(value)
+    }
+}
+
+/// Replaces the value at `path` with one of the same type, like [`crate::path::set`] does on the
+/// value tree, and returns the old one. The input keeps its position.
+pub fn set(input: &mut Cursor<Vec<u8>>, path: &Path, value: Value) -> Result<Value> {
+    set_with_options(input, path, value, &DeserializerOptions::default())
+}
+
+/// Like [`set`], with the limits and registered structs of `options`
+pub fn set_with_options(input: &mut Cursor<Vec<u8>>, path: &Path, value: Value, options: &DeserializerOptions) -> Result<Value> {
+    let (target, leaf) = find(input, path, options)?;
+    let (old, value) = match target.inner {
+        Some(from) => {
+            let mut edited = leaf.clone();
+            (path::set_in(&mut edited, path, from, value)?, edited)
+        },
+        None => {
+            check_type(path.segments(), &leaf, &value)?;
+            (leaf, value)
+        }
+    };
+
+    let mut sizes = target.sizes;
+    let mut bytes = Vec::new();
+    let range = match target.leaf {
+        Leaf::Property(tagged) => {
+            if let Value::Bool(value) = value {
+                // bools live in the tag, right after the size and array index
+                input.get_mut()[tagged.size_at as usize + 8] = value as u8;
+                return Ok(old);
+            }
+            write_value(&mut bytes, &value, &options.structs)?;
+            sizes.push(tagged.size_at);
+            tagged.value
+        },
+        Leaf::Element { range, element } => {
+            match (element, value) {
+                (Element::Byte, Value::Byte { value: ByteValue::Byte(byte), .. }) => bytes.push(byte),
+                (Element::Struct(tag), Value::Struct { value, .. }) => write_struct_value(&mut bytes, &tag.struct_name, &value, &options.structs)?,
+                (Element::Item(..), value) => write_item(&mut bytes, &value, &options.structs)?,
+                (_, value) => property::write_element(&mut bytes, &value)?
+            }
+            range
+        }
+    };
+
+    let data = input.get_mut();
+    let delta = bytes.len() as i64 - (range.end - range.start) as i64;
+    let sizes = sizes.into_iter().map(|size_at| {
+        let size = LittleEndian::read_i32(&data[size_at as usize..]) as i64 + delta;
+        let size = i32::try_from(size).map_err(|_| Error::make_data(format!("Setting {} makes a size overflow", path)))?;
+        Ok((size_at as usize, size))
+    }).collect::<Result<Vec<_>>>()?;
+    for (size_at, size) in sizes {
+        LittleEndian::write_i32(&mut data[size_at..], size);
+    }
+    data.splice(range.start as usize..range.end as usize, bytes);
+    Ok(old)
+}
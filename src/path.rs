@@ -0,0 +1,587 @@
+//! Paths addressing values in the property tree, e.g. `PlayerData.Inventory[3].Count`.
+//!
+//! Property names are separated by dots and array elements are indexed in brackets. Names that
+//! don't fit between dots are quoted, `Flags."Quest_01.Done"`, and slots of static arrays other
+//! than the first follow the name in parentheses, `Scores(1)`. Map values are addressed by their
+//! key written as in [`crate::text`], `Flags["Quest_01"]`, integer keys also fit in brackets
+//! without anything else. Elements of sets are addressed by their position, `Seen[0]`.
+
+use std::fmt;
+use std::mem;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::property::{self, PropertyType, Tag};
+use crate::value::{ArrayValue, ByteValue, Property, Save, StructValue, Value};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Segment {
+    Field(String),
+    /// Slot of a static array, the property with that name and array index
+    Slot(String, i32),
+    Index(usize),
+    /// Key of a map entry as text
+    Key(String)
+}
+
+impl Segment {
+    /// Name and array index of the property the segment stands for
+    pub(crate) fn property(&self) -> Option<(&str, i32)> {
+        match self {
+            Segment::Field(name) => Some((name, 0)),
+            Segment::Slot(name, array_index) => Some((name, *array_index)),
+            _ => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Path(Vec<Segment>);
+
+impl Path {
+    pub fn segments(&self) -> &[Segment] {
+        &self.0
+    }
+
+    pub(crate) fn from_segments(segments: Vec<Segment>) -> Path {
+        Path(segments)
+    }
+
+    fn split(&self) -> (&[Segment], &Segment) {
+        let (last, parent) = self.0.split_last().expect("paths aren't empty");
+        (parent, last)
+    }
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Path> {
+        let invalid = |msg: &str| Error::make_data(format!("Invalid path {}: {}", s, msg));
+        let mut segments = Vec::new();
+        let mut rest = s;
+        while segments.is_empty() || !rest.is_empty() {
+            if let Some(inner) = rest.strip_prefix('[') {
+                let (key, after) = bracketed(inner).ok_or_else(|| invalid("expected ']'"))?;
+                let key = key.trim();
+                if key.is_empty() {
+                    return Err(invalid("expected an index or a key"));
+                }
+                segments.push(match key.parse() {
+                    Ok(index) => Segment::Index(index),
+                    Err(_) => Segment::Key(String::from(key))
+                });
+                rest = after;
+                continue;
+            }
+
+            if !segments.is_empty() {
+                rest = rest.strip_prefix('.').ok_or_else(|| invalid("expected '.' or '['"))?;
+            }
+            let name = match rest.strip_prefix('"') {
+                Some(quoted) => {
+                    let (name, after) = unquote(quoted).ok_or_else(|| invalid("unterminated name"))?;
+                    rest = after;
+                    name
+                },
+                None => {
+                    let end = rest.find(['.', '[', '(']).unwrap_or(rest.len());
+                    if end == 0 {
+                        return Err(invalid("expected a name"));
+                    }
+                    let name = String::from(&rest[..end]);
+                    rest = &rest[end..];
+                    name
+                }
+            };
+            match rest.strip_prefix('(') {
+                Some(inner) => {
+                    let end = inner.find(')').ok_or_else(|| invalid("expected ')'"))?;
+                    let array_index = inner[..end].trim().parse().map_err(|_| invalid("expected a slot"))?;
+                    rest = &inner[end + 1..];
+                    segments.push(match array_index {
+                        0 => Segment::Field(name),
+                        array_index => Segment::Slot(name, array_index)
+                    });
+                },
+                None => segments.push(Segment::Field(name))
+            }
+        }
+        Ok(Path(segments))
+    }
+}
+
+/// Reads a name up to the closing quote, returns it with the text after the quote
+fn unquote(s: &str) -> Option<(String, &str)> {
+    let mut name = String::new();
+    let mut chars = s.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((name, &s[index + 1..])),
+            '\\' => name.push(chars.next()?.1),
+            c => name.push(c)
+        }
+    }
+    None
+}
+
+/// Splits at the closing bracket, skipping over brackets in quoted strings
+fn bracketed(s: &str) -> Option<(&str, &str)> {
+    let mut rest = s;
+    loop {
+        let end = rest.find(['"', ']'])?;
+        match rest[end..].starts_with(']') {
+            true => {
+                let end = s.len() - rest.len() + end;
+                return Some((&s[..end], &s[end + 1..]));
+            },
+            false => rest = unquote(&rest[end + 1..])?.1
+        }
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&format_segments(&self.0))
+    }
+}
+
+fn format_segments(segments: &[Segment]) -> String {
+    let mut formatted = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Field(name) | Segment::Slot(name, _) => {
+                if !formatted.is_empty() {
+                    formatted.push('.');
+                }
+                match name.is_empty() || name.contains(['.', '[', ']', '(', ')', '"', '\\']) {
+                    true => formatted.push_str(&format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))),
+                    false => formatted.push_str(name)
+                }
+                if let Segment::Slot(_, array_index) = segment {
+                    formatted.push_str(&format!("({})", array_index));
+                }
+            },
+            Segment::Index(index) => formatted.push_str(&format!("[{}]", index)),
+            Segment::Key(key) => formatted.push_str(&format!("[{}]", key))
+        }
+    }
+    formatted
+}
+
+/// What a path prefix resolved to, elements of struct arrays aren't values of their own
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Properties(&'a [Property]),
+    Element(&'a StructValue),
+    Value(&'a Value)
+}
+
+enum NodeMut<'a> {
+    Properties(&'a mut Vec<Property>),
+    Element(&'a mut StructValue),
+    Value(&'a mut Value)
+}
+
+impl<'a> Node<'a> {
+    fn properties(self) -> Option<&'a [Property]> {
+        match self {
+            Node::Properties(properties) => Some(properties),
+            Node::Element(StructValue::Properties(properties)) | Node::Value(Value::Struct { value: StructValue::Properties(properties), .. }) => Some(properties),
+            _ => None
+        }
+    }
+
+    fn kind(self) -> String {
+        match self {
+            Node::Properties(_) => String::from("a property list"),
+            Node::Element(_) => String::from("a struct element"),
+            Node::Value(value) => describe(value)
+        }
+    }
+}
+
+impl<'a> NodeMut<'a> {
+    fn properties(self) -> Option<&'a mut Vec<Property>> {
+        match self {
+            NodeMut::Properties(properties) | NodeMut::Element(StructValue::Properties(properties))
+            | NodeMut::Value(Value::Struct { value: StructValue::Properties(properties), .. }) => Some(properties),
+            _ => None
+        }
+    }
+
+    fn kind(&self) -> String {
+        match self {
+            NodeMut::Properties(properties) => Node::Properties(properties).kind(),
+            NodeMut::Element(element) => Node::Element(element).kind(),
+            NodeMut::Value(value) => Node::Value(value).kind()
+        }
+    }
+}
+
+/// The type of a value as written in the text format, e.g. `ArrayProperty<IntProperty>`
+pub(crate) fn describe(value: &Value) -> String {
+    match value {
+        Value::Byte { enum_name, .. } | Value::Enum { enum_name, .. } if enum_name != "None" => format!("{}<{}>", value.type_name(), enum_name),
+        Value::Struct { struct_name, .. } => format!("StructProperty<{}>", struct_name),
+        Value::Array { element_type, value: ArrayValue::Structs { struct_name, .. } } => format!("ArrayProperty<{}<{}>>", element_type, struct_name),
+        Value::Array { element_type, .. } => format!("ArrayProperty<{}>", element_type),
+        Value::Set { element_type, .. } => format!("SetProperty<{}>", element_type),
+        Value::Map { key_type, value_type, .. } => format!("MapProperty<{}, {}>", key_type, value_type),
+        Value::Unknown { type_name, .. } => format!("undecoded {}", type_name),
+        _ => String::from(value.type_name())
+    }
+}
+
+pub(crate) fn cannot_address(path: &[Segment], kind: String) -> Error {
+    Error::make_data(format!("Can't address {} in {}", format_segments(path), kind))
+}
+
+pub(crate) fn missing(path: &[Segment]) -> Error {
+    Error::make_data(format!("No property {}", format_segments(path)))
+}
+
+pub(crate) fn out_of_range(path: &[Segment], len: usize) -> Error {
+    Error::make_data(format!("Index out of range in {}, the array has {} elements", format_segments(path), len))
+}
+
+fn mismatch(path: &[Segment], expected: &str, value: &Value) -> Error {
+    Error::make_data(format!("Can't set {} of type {} to {}", format_segments(path), expected, describe(value)))
+}
+
+pub(crate) fn no_entry(path: &[Segment]) -> Error {
+    Error::make_data(format!("No entry {}", format_segments(path)))
+}
+
+/// Parses the key of a map entry, integer keys may come as an index
+pub(crate) fn map_key(key_type: &str, segment: &Segment) -> Result<Value> {
+    let text = match segment {
+        Segment::Index(index) => index.to_string(),
+        Segment::Key(text) => text.clone(),
+        _ => return Err(Error::make_data(format!("{} isn't a map key", format_segments(std::slice::from_ref(segment)))))
+    };
+    PropertyType::from_name(key_type).parse_text(&text, &Tag::Empty)
+}
+
+/// Keys are compared by their text so that NaNs can be found
+pub(crate) fn same_key(a: &Value, b: &Value) -> bool {
+    property::text(a).is_some_and(|text| property::text(b) == Some(text))
+}
+
+fn entry_position(entries: &[(Value, Value)], key: &Value) -> Option<usize> {
+    entries.iter().position(|(other, _)| same_key(other, key))
+}
+
+fn step<'a>(node: Node<'a>, segment: &Segment, path: &[Segment]) -> Result<Node<'a>> {
+    if let Some((name, array_index)) = segment.property() {
+        let properties = node.properties().ok_or_else(|| cannot_address(path, node.kind()))?;
+        return properties.iter().find(|property| property.name == name && property.array_index == array_index)
+            .map(|property| Node::Value(&property.value)).ok_or_else(|| missing(path));
+    }
+    match (node, segment) {
+        (Node::Value(Value::Array { value: ArrayValue::Values(values), .. } | Value::Set { elements: values, .. }), Segment::Index(index)) => {
+            values.get(*index).map(Node::Value).ok_or_else(|| out_of_range(path, values.len()))
+        },
+        (Node::Value(Value::Array { value: ArrayValue::Structs { elements, .. }, .. }), Segment::Index(index)) => {
+            elements.get(*index).map(Node::Element).ok_or_else(|| out_of_range(path, elements.len()))
+        },
+        (Node::Value(Value::Map { key_type, entries, .. }), segment) => {
+            let key = map_key(key_type, segment)?;
+            entry_position(entries, &key).map(|position| Node::Value(&entries[position].1)).ok_or_else(|| no_entry(path))
+        },
+        (node, _) => Err(cannot_address(path, node.kind()))
+    }
+}
+
+fn step_mut<'a>(node: NodeMut<'a>, segment: &Segment, path: &[Segment]) -> Result<NodeMut<'a>> {
+    if let Some((name, array_index)) = segment.property() {
+        let kind = node.kind();
+        let properties = node.properties().ok_or_else(|| cannot_address(path, kind))?;
+        return properties.iter_mut().find(|property| property.name == name && property.array_index == array_index)
+            .map(|property| NodeMut::Value(&mut property.value)).ok_or_else(|| missing(path));
+    }
+    match (node, segment) {
+        (NodeMut::Value(Value::Array { value: ArrayValue::Values(values), .. } | Value::Set { elements: values, .. }), Segment::Index(index)) => {
+            let len = values.len();
+            values.get_mut(*index).map(NodeMut::Value).ok_or_else(|| out_of_range(path, len))
+        },
+        (NodeMut::Value(Value::Array { value: ArrayValue::Structs { elements, .. }, .. }), Segment::Index(index)) => {
+            let len = elements.len();
+            elements.get_mut(*index).map(NodeMut::Element).ok_or_else(|| out_of_range(path, len))
+        },
+        (NodeMut::Value(Value::Map { key_type, entries, .. }), segment) => {
+            let key = map_key(key_type, segment)?;
+            let position = entry_position(entries, &key).ok_or_else(|| no_entry(path))?;
+            Ok(NodeMut::Value(&mut entries[position].1))
+        },
+        (node, _) => Err(cannot_address(path, node.kind()))
+    }
+}
+
+/// Follows `segments[from..]`, the ones before lead to `node` and only show in errors
+fn resolve<'a>(mut node: Node<'a>, segments: &[Segment], from: usize) -> Result<Node<'a>> {
+    for depth in from..segments.len() {
+        node = step(node, &segments[depth], &segments[..=depth])?;
+    }
+    Ok(node)
+}
+
+fn resolve_mut<'a>(mut node: NodeMut<'a>, segments: &[Segment], from: usize) -> Result<NodeMut<'a>> {
+    for depth in from..segments.len() {
+        node = step_mut(node, &segments[depth], &segments[..=depth])?;
+    }
+    Ok(node)
+}
+
+fn not_a_value(path: &Path) -> Error {
+    Error::make_data(format!("{} is a struct element, address its properties instead", path))
+}
+
+pub fn get<'a>(properties: &'a [Property], path: &Path) -> Result<&'a Value> {
+    get_within(Node::Properties(properties), path, 0)
+}
+
+/// Gets the value at `path` inside `value`, which the first `from` segments led to
+pub(crate) fn get_in<'a>(value: &'a Value, path: &Path, from: usize) -> Result<&'a Value> {
+    get_within(Node::Value(value), path, from)
+}
+
+fn get_within<'a>(node: Node<'a>, path: &Path, from: usize) -> Result<&'a Value> {
+    match resolve(node, path.segments(), from)? {
+        Node::Value(value) => Ok(value),
+        _ => Err(not_a_value(path))
+    }
+}
+
+pub fn get_mut<'a>(properties: &'a mut Vec<Property>, path: &Path) -> Result<&'a mut Value> {
+    match resolve_mut(NodeMut::Properties(properties), path.segments(), 0)? {
+        NodeMut::Value(value) => Ok(value),
+        _ => Err(not_a_value(path))
+    }
+}
+
+/// Fails unless `value` can take the place of `old`, i.e. has the same type, enum or struct name
+pub(crate) fn check_type(path: &[Segment], old: &Value, value: &Value) -> Result<()> {
+    let same = match (old, value) {
+        (Value::Byte { enum_name: a, value: old }, Value::Byte { enum_name: b, value: new }) => {
+            a == b && mem::discriminant(old) == mem::discriminant(new)
+        },
+        (Value::Enum { enum_name: a, .. }, Value::Enum { enum_name: b, .. }) => a == b,
+        (Value::Struct { struct_name: a, .. }, Value::Struct { struct_name: b, .. }) => a == b,
+        (Value::Array { element_type: a, value: old }, Value::Array { element_type: b, value: new }) => {
+            a == b && mem::discriminant(old) == mem::discriminant(new)
+        },
+        (Value::Set { element_type: a, .. }, Value::Set { element_type: b, .. }) => a == b,
+        (Value::Map { key_type: a_key, value_type: a_value, .. }, Value::Map { key_type: b_key, value_type: b_value, .. }) => a_key == b_key && a_value == b_value,
+        _ => old.type_name() == value.type_name()
+    };
+    match same {
+        true => Ok(()),
+        false => Err(mismatch(path, &describe(old), value))
+    }
+}
+
+fn byte_element(path: &[Segment], value: Value) -> Result<u8> {
+    match value {
+        Value::Byte { value: ByteValue::Byte(byte), .. } => Ok(byte),
+        value => Err(mismatch(path, "ByteProperty", &value))
+    }
+}
+
+fn struct_element(path: &[Segment], struct_name: &str, value: Value) -> Result<StructValue> {
+    match value {
+        Value::Struct { struct_name: name, value, .. } if name == struct_name => Ok(value),
+        value => Err(mismatch(path, &format!("StructProperty<{}>", struct_name), &value))
+    }
+}
+
+/// Replaces the value at `path` with one of the same type and returns the old one
+pub fn set(properties: &mut Vec<Property>, path: &Path, value: Value) -> Result<Value> {
+    set_within(NodeMut::Properties(properties), path, 0, value)
+}
+
+/// Replaces the value at `path` inside `value`, which the first `from` segments led to
+pub(crate) fn set_in(value: &mut Value, path: &Path, from: usize, new: Value) -> Result<Value> {
+    set_within(NodeMut::Value(value), path, from, new)
+}
+
+fn set_within(node: NodeMut, path: &Path, from: usize, value: Value) -> Result<Value> {
+    let (parent, last) = path.split();
+    match (resolve_mut(node, parent, from)?, last) {
+        (NodeMut::Value(Value::Array { value: ArrayValue::Bytes(bytes), .. }), Segment::Index(index)) => {
+            let len = bytes.len();
+            let slot = bytes.get_mut(*index).ok_or_else(|| out_of_range(path.segments(), len))?;
+            let old = mem::replace(slot, byte_element(path.segments(), value)?);
+            Ok(Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(old) })
+        },
+        (NodeMut::Value(Value::Array { value: ArrayValue::Structs { struct_name, guid, elements, .. }, .. }), Segment::Index(index)) => {
+            let len = elements.len();
+            let slot = elements.get_mut(*index).ok_or_else(|| out_of_range(path.segments(), len))?;
+            let old = mem::replace(slot, struct_element(path.segments(), struct_name, value)?);
+            Ok(Value::Struct { struct_name: struct_name.clone(), guid: *guid, value: old })
+        },
+        (node, last) => match step_mut(node, last, path.segments())? {
+            NodeMut::Value(slot) => {
+                check_type(path.segments(), slot, &value)?;
+                Ok(mem::replace(slot, value))
+            },
+            _ => Err(not_a_value(path))
+        }
+    }
+}
+
+/// Adds a property to a struct, an element to an array or set at the given index or an entry to a map
+pub fn insert(properties: &mut Vec<Property>, path: &Path, value: Value) -> Result<()> {
+    let (parent, last) = path.split();
+    let node = resolve_mut(NodeMut::Properties(properties), parent, 0)?;
+    let kind = node.kind();
+    if let Some((name, array_index)) = last.property() {
+        let properties = node.properties().ok_or_else(|| cannot_address(path.segments(), kind))?;
+        if properties.iter().any(|property| property.name == name && property.array_index == array_index) {
+            return Err(Error::make_data(format!("{} already exists", path)));
+        }
+        properties.push(Property { name: String::from(name), array_index, guid: None, value });
+        return Ok(());
+    }
+    match (node, last) {
+        (NodeMut::Value(Value::Array { element_type, value: array }), Segment::Index(index)) => {
+            let len = array_len(array);
+            if *index > len {
+                return Err(out_of_range(path.segments(), len));
+            }
+            match array {
+                ArrayValue::Bytes(bytes) => bytes.insert(*index, byte_element(path.segments(), value)?),
+                ArrayValue::Values(values) => {
+                    match values.first() {
+                        Some(first) => check_type(path.segments(), first, &value)?,
+                        None if value.type_name() != element_type => return Err(mismatch(path.segments(), element_type, &value)),
+                        None => {}
+                    }
+                    values.insert(*index, value);
+                },
+                ArrayValue::Structs { struct_name, elements, .. } => elements.insert(*index, struct_element(path.segments(), struct_name, value)?)
+            }
+        },
+        (NodeMut::Value(Value::Set { element_type, elements, .. }), Segment::Index(index)) => {
+            if *index > elements.len() {
+                return Err(out_of_range(path.segments(), elements.len()));
+            }
+            match elements.first() {
+                Some(first) => check_type(path.segments(), first, &value)?,
+                None if value.type_name() != element_type => return Err(mismatch(path.segments(), element_type, &value)),
+                None => {}
+            }
+            elements.insert(*index, value);
+        },
+        (NodeMut::Value(Value::Map { key_type, value_type, entries, .. }), last) => {
+            let key = map_key(key_type, last)?;
+            if entry_position(entries, &key).is_some() {
+                return Err(Error::make_data(format!("{} already exists", path)));
+            }
+            match entries.first() {
+                Some((_, first)) => check_type(path.segments(), first, &value)?,
+                None if value.type_name() != value_type => return Err(mismatch(path.segments(), value_type, &value)),
+                None => {}
+            }
+            entries.push((key, value));
+        },
+        _ => return Err(cannot_address(path.segments(), kind))
+    }
+    Ok(())
+}
+
+/// Takes the property, array or set element or map entry at `path` out of the tree
+pub fn remove(properties: &mut Vec<Property>, path: &Path) -> Result<Value> {
+    let (parent, last) = path.split();
+    let node = resolve_mut(NodeMut::Properties(properties), parent, 0)?;
+    let kind = node.kind();
+    if let Some((name, array_index)) = last.property() {
+        let properties = node.properties().ok_or_else(|| cannot_address(path.segments(), kind))?;
+        let position = properties.iter().position(|property| property.name == name && property.array_index == array_index)
+            .ok_or_else(|| missing(path.segments()))?;
+        return Ok(properties.remove(position).value);
+    }
+    match (node, last) {
+        (NodeMut::Value(Value::Array { value: array, .. }), Segment::Index(index)) => {
+            let len = array_len(array);
+            if *index >= len {
+                return Err(out_of_range(path.segments(), len));
+            }
+            Ok(match array {
+                ArrayValue::Bytes(bytes) => Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(bytes.remove(*index)) },
+                ArrayValue::Values(values) => values.remove(*index),
+                ArrayValue::Structs { struct_name, guid, elements, .. } => {
+                    Value::Struct { struct_name: struct_name.clone(), guid: *guid, value: elements.remove(*index) }
+                }
+            })
+        },
+        (NodeMut::Value(Value::Set { elements, .. }), Segment::Index(index)) => match *index < elements.len() {
+            true => Ok(elements.remove(*index)),
+            false => Err(out_of_range(path.segments(), elements.len()))
+        },
+        (NodeMut::Value(Value::Map { key_type, entries, .. }), last) => {
+            let key = map_key(key_type, last)?;
+            let position = entry_position(entries, &key).ok_or_else(|| no_entry(path.segments()))?;
+            Ok(entries.remove(position).1)
+        },
+        _ => Err(cannot_address(path.segments(), kind))
+    }
+}
+
+fn array_len(array: &ArrayValue) -> usize {
+    match array {
+        ArrayValue::Bytes(bytes) => bytes.len(),
+        ArrayValue::Values(values) => values.len(),
+        ArrayValue::Structs { elements, .. } => elements.len()
+    }
+}
+
+/// Parses text as a value of the same type as `like`, for editing saves from the command line.
+/// Strings are taken as they are and enum values get the enum name prepended when it's missing.
+/// Other scalars are written as in [`crate::text`], e.g. soft objects as two quoted strings.
+pub fn parse_value(like: &Value, text: &str) -> Result<Value> {
+    fn number<T: FromStr>(text: &str) -> Result<T> {
+        text.trim().parse().map_err(|_| Error::make_data(format!("Invalid number {}", text)))
+    }
+    Ok(match like {
+        Value::Byte { enum_name, value: ByteValue::Byte(_) } => Value::Byte { enum_name: enum_name.clone(), value: ByteValue::Byte(number(text)?) },
+        Value::Byte { enum_name, value: ByteValue::Label(_) } => Value::Byte { enum_name: enum_name.clone(), value: ByteValue::Label(enum_label(enum_name, text)) },
+        Value::Enum { enum_name, .. } => Value::Enum { enum_name: enum_name.clone(), value: enum_label(enum_name, text) },
+        Value::Str(_) => Value::Str(String::from(text)),
+        Value::Name(_) => Value::Name(String::from(text)),
+        Value::Object(_) => Value::Object(String::from(text)),
+        Value::Struct { struct_name, guid, value: StructValue::DateTime(_) } => Value::Struct { struct_name: struct_name.clone(), guid: *guid, value: StructValue::DateTime(number(text)?) },
+        Value::Struct { struct_name, guid, value: StructValue::Timespan(_) } => Value::Struct { struct_name: struct_name.clone(), guid: *guid, value: StructValue::Timespan(number(text)?) },
+        like if property::text(like).is_some() => PropertyType::from_name(like.type_name()).parse_text(text, &Tag::Empty)?,
+        _ => return Err(Error::make_data(format!("{} can't be parsed from text", describe(like))))
+    })
+}
+
+fn enum_label(enum_name: &str, text: &str) -> String {
+    match enum_name == "None" || text.contains("::") {
+        true => String::from(text),
+        false => format!("{}::{}", enum_name, text)
+    }
+}
+
+impl Save {
+    pub fn get(&self, path: &str) -> Result<&Value> {
+        get(&self.properties, &path.parse()?)
+    }
+
+    pub fn get_mut(&mut self, path: &str) -> Result<&mut Value> {
+        get_mut(&mut self.properties, &path.parse()?)
+    }
+
+    pub fn set(&mut self, path: &str, value: Value) -> Result<Value> {
+        set(&mut self.properties, &path.parse()?, value)
+    }
+
+    pub fn insert(&mut self, path: &str, value: Value) -> Result<()> {
+        insert(&mut self.properties, &path.parse()?, value)
+    }
+
+    pub fn remove(&mut self, path: &str) -> Result<Value> {
+        remove(&mut self.properties, &path.parse()?)
+    }
+}
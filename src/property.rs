@@ -0,0 +1,597 @@
+//! Property types and how their values are encoded.
+//!
+//! A property type shows up in three places: the type specific part of a property tag, the value
+//! of a tagged property, and the elements of an array. [`PropertyCodec`] covers all three for one
+//! scalar type, so they can't disagree, along with its [`Value`] and text forms. The value tree,
+//! the text format, paths and the hex dump all go through `with_codec!`. Structs, arrays, sets and maps hold other properties and
+//! are read by the deserializers themselves, only their tags are read here.
+
+use std::fmt;
+use std::io::Write;
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::de::Deserializer;
+use crate::error::{Error, ErrorCode, Result};
+use crate::fstring::write_fstring;
+use crate::text::{quote, read_quoted};
+use crate::types::FGuid;
+use crate::value::{ByteValue, Value};
+
+/// Runs `$body` with `$codec` naming the codec of a scalar property type, or `$other` for the rest
+macro_rules! with_codec {
+    ($property_type:expr, $codec:ident => $body:expr, _ => $other:expr) => {
+        with_codec!(@match $property_type, $codec => $body, $other,
+            Bool => BoolProperty, Int8 => Int8Property, Byte => ByteProperty, Int16 => Int16Property,
+            UInt16 => UInt16Property, Int => IntProperty, UInt32 => UInt32Property, Int64 => Int64Property,
+            UInt64 => UInt64Property, Float => FloatProperty, Double => DoubleProperty, Str => StrProperty,
+            Name => NameProperty, Object => ObjectProperty, SoftObject => SoftObjectProperty, Enum => EnumProperty)
+    };
+    (@match $property_type:expr, $codec:ident => $body:expr, $other:expr, $($variant:ident => $implementor:ident),*) => {
+        match $property_type {
+            $($crate::property::PropertyType::$variant => {
+                type $codec = $crate::property::$implementor;
+                $body
+            },)*
+            _ => $other
+        }
+    };
+}
+pub(crate) use with_codec;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PropertyType {
+    Bool,
+    Int8,
+    Byte,
+    Int16,
+    UInt16,
+    Int,
+    UInt32,
+    Int64,
+    UInt64,
+    Float,
+    Double,
+    Str,
+    Name,
+    Object,
+    SoftObject,
+    Enum,
+    Struct,
+    Array,
+    Set,
+    Map,
+    /// A type this crate doesn't know
+    Other(String)
+}
+
+impl PropertyType {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "BoolProperty" => PropertyType::Bool,
+            "Int8Property" => PropertyType::Int8,
+            "ByteProperty" => PropertyType::Byte,
+            "Int16Property" => PropertyType::Int16,
+            "UInt16Property" => PropertyType::UInt16,
+            "IntProperty" => PropertyType::Int,
+            "UInt32Property" => PropertyType::UInt32,
+            "Int64Property" => PropertyType::Int64,
+            "UInt64Property" => PropertyType::UInt64,
+            "FloatProperty" => PropertyType::Float,
+            "DoubleProperty" => PropertyType::Double,
+            "StrProperty" => PropertyType::Str,
+            "NameProperty" => PropertyType::Name,
+            "ObjectProperty" => PropertyType::Object,
+            "SoftObjectProperty" => PropertyType::SoftObject,
+            "EnumProperty" => PropertyType::Enum,
+            "StructProperty" => PropertyType::Struct,
+            "ArrayProperty" => PropertyType::Array,
+            "SetProperty" => PropertyType::Set,
+            "MapProperty" => PropertyType::Map,
+            _ => PropertyType::Other(String::from(name))
+        }
+    }
+
+    /// The name stored in tags, e.g. `IntProperty`
+    pub fn name(&self) -> &str {
+        match self {
+            PropertyType::Bool => "BoolProperty",
+            PropertyType::Int8 => "Int8Property",
+            PropertyType::Byte => "ByteProperty",
+            PropertyType::Int16 => "Int16Property",
+            PropertyType::UInt16 => "UInt16Property",
+            PropertyType::Int => "IntProperty",
+            PropertyType::UInt32 => "UInt32Property",
+            PropertyType::Int64 => "Int64Property",
+            PropertyType::UInt64 => "UInt64Property",
+            PropertyType::Float => "FloatProperty",
+            PropertyType::Double => "DoubleProperty",
+            PropertyType::Str => "StrProperty",
+            PropertyType::Name => "NameProperty",
+            PropertyType::Object => "ObjectProperty",
+            PropertyType::SoftObject => "SoftObjectProperty",
+            PropertyType::Enum => "EnumProperty",
+            PropertyType::Struct => "StructProperty",
+            PropertyType::Array => "ArrayProperty",
+            PropertyType::Set => "SetProperty",
+            PropertyType::Map => "MapProperty",
+            PropertyType::Other(name) => name
+        }
+    }
+
+    /// Reads the type specific part of a tag, between the array index and the guid flag
+    pub fn read_tag(&self, de: &mut Deserializer) -> Result<Tag> {
+        match self {
+            PropertyType::Struct => Ok(Tag::Struct(de.parse_string()?, de.parse_guid()?)),
+            PropertyType::Array => Ok(Tag::Array(de.parse_string()?)),
+            PropertyType::Set => Ok(Tag::Set(de.parse_string()?)),
+            PropertyType::Map => Ok(Tag::Map(de.parse_string()?, de.parse_string()?)),
+            property_type => with_codec!(property_type, C => C::read_tag(de), _ => Ok(Tag::Empty))
+        }
+    }
+}
+
+impl PropertyType {
+    /// Reads the value of a tagged property of a scalar type
+    pub(crate) fn read_value(&self, de: &mut Deserializer, tag: &Tag) -> Result<Value> {
+        with_codec!(self, C => Ok(C::to_value(C::read_value(de, tag)?, tag)), _ => Err(unknown(self)))
+    }
+
+    /// Reads a scalar value without a tag, like an array element
+    pub(crate) fn read_element(&self, de: &mut Deserializer) -> Result<Value> {
+        with_codec!(self, C => Ok(C::to_value(C::read_element(de)?, &Tag::Empty)), _ => Err(unknown(self)))
+    }
+
+    /// Parses the text form of a scalar value, see [`text`]
+    pub(crate) fn parse_text(&self, text: &str, tag: &Tag) -> Result<Value> {
+        with_codec!(self, C => match C::parse_text(text.trim()) {
+            Some(value) => Ok(C::to_value(value, tag)),
+            None => Err(Error::make_data(format!("Invalid {} {}", self, text)))
+        }, _ => Err(unknown(self)))
+    }
+}
+
+impl fmt::Display for PropertyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Type specific data stored in a property tag, before the value itself
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Empty,
+    /// Bools are stored entirely in their tag
+    Bool(bool),
+    /// Enum name of an `EnumProperty` or `ByteProperty`, `None` for plain bytes
+    Enum(String),
+    Struct(String, FGuid),
+    Array(String),
+    Set(String),
+    Map(String, String)
+}
+
+impl Tag {
+    pub fn write<W: Write>(&self, output: &mut W) -> Result<()> {
+        match self {
+            Tag::Empty => {},
+            Tag::Bool(value) => output.write_u8(*value as u8)?,
+            Tag::Enum(name) | Tag::Array(name) | Tag::Set(name) => write_fstring(output, name)?,
+            Tag::Struct(struct_name, guid) => {
+                write_fstring(output, struct_name)?;
+                for part in [guid.a, guid.b, guid.c, guid.d] {
+                    output.write_u32::<LittleEndian>(part)?;
+                }
+            },
+            Tag::Map(key_type, value_type) => {
+                write_fstring(output, key_type)?;
+                write_fstring(output, value_type)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Encoding of one scalar property type, see the module docs
+pub trait PropertyCodec {
+    type Value;
+    const TYPE: PropertyType;
+
+    /// Reads the type specific part of a tag, most types have none
+    fn read_tag(_de: &mut Deserializer) -> Result<Tag> {
+        Ok(Tag::Empty)
+    }
+
+    /// Reads the value of a tagged property, whose tag was read by [`PropertyCodec::read_tag`]
+    fn read_value(de: &mut Deserializer, _tag: &Tag) -> Result<Self::Value> {
+        Self::read_element(de)
+    }
+
+    /// Reads a value without a tag of its own, like an array element
+    fn read_element(de: &mut Deserializer) -> Result<Self::Value>;
+
+    fn write_value<W: Write>(output: &mut W, value: &Self::Value) -> Result<()> {
+        Self::write_element(output, value)
+    }
+
+    fn write_element<W: Write>(output: &mut W, value: &Self::Value) -> Result<()>;
+
+    /// Wraps a value read with `tag`, which is [`Tag::Empty`] for elements
+    fn to_value(value: Self::Value, tag: &Tag) -> Value;
+
+    /// The reverse of [`PropertyCodec::to_value`], `None` for values of other types
+    fn from_value(value: &Value) -> Option<&Self::Value>;
+
+    /// Text form used by [`crate::text`], the hex dump and diffs
+    fn text(value: &Self::Value) -> String;
+
+    fn parse_text(text: &str) -> Option<Self::Value>;
+}
+
+pub struct BoolProperty;
+
+impl PropertyCodec for BoolProperty {
+    type Value = bool;
+    const TYPE: PropertyType = PropertyType::Bool;
+
+    fn read_tag(de: &mut Deserializer) -> Result<Tag> {
+        Ok(Tag::Bool(de.input.read_u8()? != 0))
+    }
+
+    fn read_value(_de: &mut Deserializer, tag: &Tag) -> Result<bool> {
+        match tag {
+            Tag::Bool(value) => Ok(*value),
+            _ => Err(Error::make_data(String::from("BoolProperty without its value in the tag")))
+        }
+    }
+
+    fn read_element(de: &mut Deserializer) -> Result<bool> {
+        Ok(de.input.read_u8()? != 0)
+    }
+
+    /// The value is written with the tag, see [`Tag::Bool`]
+    fn write_value<W: Write>(_output: &mut W, _value: &bool) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_element<W: Write>(output: &mut W, value: &bool) -> Result<()> {
+        Ok(output.write_u8(*value as u8)?)
+    }
+
+    fn to_value(value: bool, _tag: &Tag) -> Value {
+        Value::Bool(value)
+    }
+
+    fn from_value(value: &Value) -> Option<&bool> {
+        match value {
+            Value::Bool(value) => Some(value),
+            _ => None
+        }
+    }
+
+    fn text(value: &bool) -> String {
+        value.to_string()
+    }
+
+    fn parse_text(text: &str) -> Option<bool> {
+        text.parse().ok()
+    }
+}
+
+macro_rules! number_codec {
+    ($implementor:ident, $variant:ident, $num:ty, $read_method:ident, $write_method:ident $(, $endianness:ty)?) => {
+        pub struct $implementor;
+
+        impl PropertyCodec for $implementor {
+            type Value = $num;
+            const TYPE: PropertyType = PropertyType::$variant;
+
+            fn read_element(de: &mut Deserializer) -> Result<$num> {
+                Ok(de.input.$read_method$(::<$endianness>)?()?)
+            }
+
+            fn write_element<W: Write>(output: &mut W, value: &$num) -> Result<()> {
+                Ok(output.$write_method$(::<$endianness>)?(*value)?)
+            }
+
+            fn to_value(value: $num, _tag: &Tag) -> Value {
+                Value::$variant(value)
+            }
+
+            fn from_value(value: &Value) -> Option<&$num> {
+                match value {
+                    Value::$variant(value) => Some(value),
+                    _ => None
+                }
+            }
+
+            fn text(value: &$num) -> String {
+                value.number_text()
+            }
+
+            fn parse_text(text: &str) -> Option<$num> {
+                <$num>::parse_number(text)
+            }
+        }
+    };
+}
+
+/// Text form of numbers, NaNs with unusual bits keep them as `NaN:7FC00001` like in JSON
+trait NumberText: Sized {
+    fn number_text(&self) -> String;
+    fn parse_number(text: &str) -> Option<Self>;
+}
+
+macro_rules! int_text {
+    ($($num:ty),*) => {
+        $(impl NumberText for $num {
+            fn number_text(&self) -> String {
+                self.to_string()
+            }
+
+            fn parse_number(text: &str) -> Option<$num> {
+                text.parse().ok()
+            }
+        })*
+    };
+}
+
+int_text!(i8, i16, u16, i32, u32, i64, u64);
+
+macro_rules! float_text {
+    ($float:ty, $bits:ty) => {
+        impl NumberText for $float {
+            fn number_text(&self) -> String {
+                match self.is_nan() && self.to_bits() != <$float>::NAN.to_bits() {
+                    true => format!("NaN:{:X}", self.to_bits()),
+                    // debug formatting keeps the shortest text that parses back to the same value
+                    false => format!("{:?}", self)
+                }
+            }
+
+            fn parse_number(text: &str) -> Option<$float> {
+                match text.strip_prefix("NaN:") {
+                    Some(bits) => Some(<$float>::from_bits(<$bits>::from_str_radix(bits, 16).ok()?)).filter(|value| value.is_nan()),
+                    None => text.parse().ok()
+                }
+            }
+        }
+    };
+}
+
+float_text!(f32, u32);
+float_text!(f64, u64);
+
+number_codec!(Int8Property, Int8, i8, read_i8, write_i8);
+number_codec!(Int16Property, Int16, i16, read_i16, write_i16, LittleEndian);
+number_codec!(UInt16Property, UInt16, u16, read_u16, write_u16, LittleEndian);
+number_codec!(IntProperty, Int, i32, read_i32, write_i32, LittleEndian);
+number_codec!(UInt32Property, UInt32, u32, read_u32, write_u32, LittleEndian);
+number_codec!(Int64Property, Int64, i64, read_i64, write_i64, LittleEndian);
+number_codec!(UInt64Property, UInt64, u64, read_u64, write_u64, LittleEndian);
+number_codec!(FloatProperty, Float, f32, read_f32, write_f32, LittleEndian);
+number_codec!(DoubleProperty, Double, f64, read_f64, write_f64, LittleEndian);
+
+/// A plain byte, or the name of an enum value when the tag names an enum. Array elements are
+/// plain bytes, arrays of enum labels are told apart by their size.
+pub struct ByteProperty;
+
+impl PropertyCodec for ByteProperty {
+    type Value = ByteValue;
+    const TYPE: PropertyType = PropertyType::Byte;
+
+    fn read_tag(de: &mut Deserializer) -> Result<Tag> {
+        Ok(Tag::Enum(de.parse_string()?))
+    }
+
+    fn read_value(de: &mut Deserializer, tag: &Tag) -> Result<ByteValue> {
+        match tag {
+            Tag::Enum(enum_name) if enum_name != "None" => Ok(ByteValue::Label(de.parse_string()?)),
+            _ => Self::read_element(de)
+        }
+    }
+
+    fn read_element(de: &mut Deserializer) -> Result<ByteValue> {
+        Ok(ByteValue::Byte(de.input.read_u8()?))
+    }
+
+    fn write_element<W: Write>(output: &mut W, value: &ByteValue) -> Result<()> {
+        match value {
+            ByteValue::Byte(value) => output.write_u8(*value)?,
+            ByteValue::Label(label) => write_fstring(output, label)?
+        }
+        Ok(())
+    }
+
+    fn to_value(value: ByteValue, tag: &Tag) -> Value {
+        Value::Byte { enum_name: enum_name(tag), value }
+    }
+
+    fn from_value(value: &Value) -> Option<&ByteValue> {
+        match value {
+            Value::Byte { value, .. } => Some(value),
+            _ => None
+        }
+    }
+
+    fn text(value: &ByteValue) -> String {
+        match value {
+            ByteValue::Byte(value) => value.to_string(),
+            ByteValue::Label(label) => quote(label)
+        }
+    }
+
+    fn parse_text(text: &str) -> Option<ByteValue> {
+        match text.starts_with('"') {
+            true => quoted(text).map(ByteValue::Label),
+            false => text.parse().ok().map(ByteValue::Byte)
+        }
+    }
+}
+
+macro_rules! string_codec {
+    ($implementor:ident, $variant:ident) => {
+        pub struct $implementor;
+
+        impl PropertyCodec for $implementor {
+            type Value = String;
+            const TYPE: PropertyType = PropertyType::$variant;
+
+            fn read_element(de: &mut Deserializer) -> Result<String> {
+                de.parse_string()
+            }
+
+            fn write_element<W: Write>(output: &mut W, value: &String) -> Result<()> {
+                write_fstring(output, value)
+            }
+
+            fn to_value(value: String, _tag: &Tag) -> Value {
+                Value::$variant(value)
+            }
+
+            fn from_value(value: &Value) -> Option<&String> {
+                match value {
+                    Value::$variant(value) => Some(value),
+                    _ => None
+                }
+            }
+
+            fn text(value: &String) -> String {
+                quote(value)
+            }
+
+            fn parse_text(text: &str) -> Option<String> {
+                quoted(text)
+            }
+        }
+    };
+}
+
+string_codec!(StrProperty, Str);
+string_codec!(NameProperty, Name);
+string_codec!(ObjectProperty, Object);
+
+/// The name of an enum value, the enum itself is only named in the tag of a property
+pub struct EnumProperty;
+
+impl PropertyCodec for EnumProperty {
+    type Value = String;
+    const TYPE: PropertyType = PropertyType::Enum;
+
+    fn read_tag(de: &mut Deserializer) -> Result<Tag> {
+        Ok(Tag::Enum(de.parse_string()?))
+    }
+
+    fn read_element(de: &mut Deserializer) -> Result<String> {
+        de.parse_string()
+    }
+
+    fn write_element<W: Write>(output: &mut W, value: &String) -> Result<()> {
+        write_fstring(output, value)
+    }
+
+    fn to_value(value: String, tag: &Tag) -> Value {
+        Value::Enum { enum_name: enum_name(tag), value }
+    }
+
+    fn from_value(value: &Value) -> Option<&String> {
+        match value {
+            Value::Enum { value, .. } => Some(value),
+            _ => None
+        }
+    }
+
+    fn text(value: &String) -> String {
+        quote(value)
+    }
+
+    fn parse_text(text: &str) -> Option<String> {
+        quoted(text)
+    }
+}
+
+/// Asset path and sub path, as laid out by UE4
+pub struct SoftObjectProperty;
+
+impl PropertyCodec for SoftObjectProperty {
+    type Value = [String; 2];
+    const TYPE: PropertyType = PropertyType::SoftObject;
+
+    fn read_element(de: &mut Deserializer) -> Result<[String; 2]> {
+        Ok([de.parse_string()?, de.parse_string()?])
+    }
+
+    fn write_element<W: Write>(output: &mut W, value: &[String; 2]) -> Result<()> {
+        write_fstring(output, &value[0])?;
+        write_fstring(output, &value[1])
+    }
+
+    fn to_value(value: [String; 2], _tag: &Tag) -> Value {
+        Value::SoftObject(value)
+    }
+
+    fn from_value(value: &Value) -> Option<&[String; 2]> {
+        match value {
+            Value::SoftObject(value) => Some(value),
+            _ => None
+        }
+    }
+
+    /// Both strings quoted, separated by a space
+    fn text(value: &[String; 2]) -> String {
+        format!("{} {}", quote(&value[0]), quote(&value[1]))
+    }
+
+    fn parse_text(text: &str) -> Option<[String; 2]> {
+        let (path, rest) = read_quoted(text)?;
+        Some([path, quoted(rest)?])
+    }
+}
+
+/// Enum name of an element or a tagged property, elements don't have one
+fn enum_name(tag: &Tag) -> String {
+    match tag {
+        Tag::Enum(enum_name) => enum_name.clone(),
+        _ => String::from("None")
+    }
+}
+
+/// A single quoted string and nothing else
+fn quoted(text: &str) -> Option<String> {
+    match read_quoted(text)? {
+        (s, rest) if rest.trim().is_empty() => Some(s),
+        _ => None
+    }
+}
+
+/// Runs `$body` with `$codec` naming the codec of a scalar value, or `$other` for other values
+macro_rules! with_value_codec {
+    ($value:expr, $codec:ident, $inner:ident => $body:expr, _ => $other:expr) => {
+        with_codec!(&PropertyType::from_name($value.type_name()), $codec => match $codec::from_value($value) {
+            Some($inner) => $body,
+            None => $other
+        }, _ => $other)
+    };
+}
+
+/// Writes a scalar value without its tag, like an array element
+pub(crate) fn write_element<W: Write>(output: &mut W, value: &Value) -> Result<()> {
+    with_value_codec!(value, C, inner => C::write_element(output, inner),
+        _ => Err(Error::make_data(format!("{} can't be an array element", value.type_name()))))
+}
+
+/// Writes the value of a tagged scalar property, the rest of it is in the tag
+pub(crate) fn write_value<W: Write>(output: &mut W, value: &Value) -> Result<()> {
+    with_value_codec!(value, C, inner => C::write_value(output, inner),
+        _ => Err(Error::make_data(format!("{} isn't a scalar", value.type_name()))))
+}
+
+/// Text form of a scalar value, `None` for structs, arrays and the other containers
+pub(crate) fn text(value: &Value) -> Option<String> {
+    with_value_codec!(value, C, inner => Some(C::text(inner)), _ => None)
+}
+
+/// The error for a type without a codec
+pub(crate) fn unknown(property_type: &PropertyType) -> Error {
+    Error::new(ErrorCode::UnknownPropertyType(property_type.name().into()))
+}
@@ -0,0 +1,900 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+use serde::ser::{self, Impossible};
+use serde::Serialize;
+
+use crate::error::{Result, Error};
+use crate::fstring::write_fstring;
+use crate::hint::Hint;
+use crate::property::{BoolProperty, ByteProperty, DoubleProperty, FloatProperty, Int16Property, Int64Property, Int8Property, IntProperty, PropertyCodec, Tag,
+    UInt16Property, UInt32Property, UInt64Property};
+use crate::structs::{StructCodec, StructRegistry};
+use crate::types::FGuid;
+use crate::value::{ArrayValue, ByteValue, Property, StructValue, Value};
+
+/// Keeps track of how much was written so far
+struct Output<W> {
+    inner: W,
+    written: u64
+}
+
+impl<W: Write> Write for Output<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A property that is being measured, where its value starts and the elements if it's a struct array
+struct Open {
+    index: usize,
+    value_start: Option<u64>,
+    elements_start: Option<u64>
+}
+
+/// Sizes of every property below the one being measured, in the order they're written
+#[derive(Default)]
+struct Measurement {
+    open: Vec<Open>,
+    sizes: Vec<Sizes>
+}
+
+#[derive(Default, Clone, Copy)]
+struct Sizes {
+    value: i64,
+    elements: i64
+}
+
+/// Writes save game properties without ever seeking back. Sizes that go into tags are
+/// found by serializing each top level property into a sink first, so nothing is buffered.
+/// That one pass measures the nested properties too, the real pass takes their sizes in order.
+pub struct Serializer<W> {
+    output: Output<W>,
+    measurement: Option<Measurement>,
+    measured: VecDeque<Sizes>,
+    structs: StructRegistry
+}
+
+impl<W: Write> Serializer<W> {
+    pub fn new(output: W) -> Self {
+        Serializer::with_structs(output, StructRegistry::default())
+    }
+
+    /// Writes the structs in `structs` with their codecs, see [`crate::structs`]
+    pub fn with_structs(output: W, structs: StructRegistry) -> Self {
+        Serializer { output: Output { inner: output, written: 0 }, measurement: None, measured: VecDeque::new(), structs }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.output.inner
+    }
+
+    fn write_string(&mut self, s: &str) -> Result<()> {
+        write_fstring(&mut self.output, s)
+    }
+
+    fn write_tag(&mut self, name: &str, type_name: &str, size: i64) -> Result<()> {
+        let size = i32::try_from(size).map_err(|_| Error::make_data(format!("{} {} is too big", type_name, name)))?;
+        self.write_string(name)?;
+        self.write_string(type_name)?;
+        self.output.write_i32::<LittleEndian>(size)?;
+        // array index
+        self.output.write_i32::<LittleEndian>(0)?;
+        Ok(())
+    }
+
+    /// Ends a tag, everything after this counts towards the value size
+    fn begin_value(&mut self) -> Result<()> {
+        self.output.write_u8(0)?;
+        if let Some(open) = self.measurement.as_mut().and_then(|measurement| measurement.open.last_mut()) {
+            open.value_start.get_or_insert(self.output.written);
+        }
+        Ok(())
+    }
+
+    /// Ends the inner tag of a struct array, everything after this counts towards the elements size
+    fn begin_elements(&mut self) {
+        if let Some(open) = self.measurement.as_mut().and_then(|measurement| measurement.open.last_mut()) {
+            open.elements_start.get_or_insert(self.output.written);
+        }
+    }
+
+    fn serialize_property<T>(&mut self, name: &str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize {
+        if let Some(measurement) = &mut self.measurement {
+            // while measuring only the length of the tags matters, not what's in them
+            measurement.open.push(Open { index: measurement.sizes.len(), value_start: None, elements_start: None });
+            measurement.sizes.push(Sizes::default());
+            value.serialize(PropertySerializer { ser: self, name, sizes: Sizes::default(), hint: Hint::default() })?;
+
+            let end = self.output.written;
+            if let Some(measurement) = &mut self.measurement {
+                if let Some(open) = measurement.open.pop() {
+                    measurement.sizes[open.index] = Sizes {
+                        value: (end - open.value_start.unwrap_or(end)) as i64,
+                        elements: (end - open.elements_start.unwrap_or(end)) as i64
+                    };
+                }
+            }
+            return Ok(());
+        }
+
+        if self.measured.is_empty() {
+            self.measured = measure(name, value, &self.structs)?.into();
+        }
+        let sizes = self.measured.pop_front().unwrap_or_default();
+        value.serialize(PropertySerializer { ser: self, name, sizes, hint: Hint::default() })
+    }
+}
+
+/// Sizes of a property and of all properties nested in it, in the order they're written
+fn measure<T>(name: &str, value: &T, structs: &StructRegistry) -> Result<Vec<Sizes>>
+where
+    T: ?Sized + Serialize {
+    let mut serializer = Serializer { measurement: Some(Measurement::default()), ..Serializer::with_structs(io::sink(), structs.clone()) };
+    serializer.serialize_property(name, value)?;
+    Ok(serializer.measurement.unwrap_or_default().sizes)
+}
+
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: Serialize {
+    to_vec_with_structs(value, &StructRegistry::default())
+}
+
+pub fn to_vec_with_structs<T>(value: &T, structs: &StructRegistry) -> Result<Vec<u8>>
+where
+    T: Serialize {
+    let mut output = Vec::new();
+    to_writer_with_structs(&mut output, value, structs)?;
+    Ok(output)
+}
+
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<()>
+where
+    W: Write,
+    T: Serialize {
+    to_writer_with_structs(writer, value, &StructRegistry::default())
+}
+
+pub fn to_writer_with_structs<W, T>(writer: W, value: &T, structs: &StructRegistry) -> Result<()>
+where
+    W: Write,
+    T: Serialize {
+    let mut serializer = Serializer::with_structs(writer, structs.clone());
+    value.serialize(&mut serializer)
+}
+
+impl<'a, W: Write> ser::Serializer for &'a mut Serializer<W> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = StructSerializer<'a, W>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    unsupported_serialize!(serialize_bool, bool);
+    unsupported_serialize!(serialize_i8, i8);
+    unsupported_serialize!(serialize_i16, i16);
+    unsupported_serialize!(serialize_i32, i32);
+    unsupported_serialize!(serialize_i64, i64);
+    unsupported_serialize!(serialize_u8, u8);
+    unsupported_serialize!(serialize_u16, u16);
+    unsupported_serialize!(serialize_u32, u32);
+    unsupported_serialize!(serialize_u64, u64);
+    unsupported_serialize!(serialize_f32, f32);
+    unsupported_serialize!(serialize_f64, f64);
+    unsupported_serialize!(serialize_char, char);
+    unsupported_serialize!(serialize_str, &str);
+    unsupported_serialize!(serialize_bytes, &[u8]);
+    unsupported_serialize!(serialize_unit_struct, &'static str);
+    unsupported_compound_serialize!();
+    unsupported_option_serialize!();
+    unsupported_newtype_serialize!();
+    unsupported_tuple_serialize!();
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(Error::unsupported("serialize_seq"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(StructSerializer { ser: self, top_level: true, custom: None })
+    }
+}
+
+pub struct StructSerializer<'a, W> {
+    ser: &'a mut Serializer<W>,
+    top_level: bool,
+    /// Fields of a struct with a registered codec, collected as properties to be encoded at the end
+    custom: Option<(StructCodec, Vec<Property>)>
+}
+
+impl<'a, W: Write> StructSerializer<'a, W> {
+    fn value(ser: &'a mut Serializer<W>, struct_name: &str) -> Self {
+        let custom = ser.structs.codec(struct_name).map(|codec| (codec.clone(), Vec::new()));
+        StructSerializer { ser, top_level: false, custom }
+    }
+}
+
+impl<'a, W: Write> ser::SerializeStruct for StructSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize {
+        match &mut self.custom {
+            Some((_, fields)) => {
+                if let Some(value) = value.serialize(ValueSerializer { name: key, hint: Hint::default(), element: false })? {
+                    fields.push(Property { name: String::from(key), array_index: 0, guid: None, value });
+                }
+                Ok(())
+            },
+            None => self.ser.serialize_property(key, value)
+        }
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        if let Some((codec, fields)) = self.custom {
+            let mut encoded = Vec::new();
+            codec.encode(&fields, &mut encoded)?;
+            self.ser.output.write_all(&encoded)?;
+            return Ok(());
+        }
+        self.ser.write_string("None")?;
+        if self.top_level {
+            self.ser.output.write_i32::<LittleEndian>(0)?;
+        }
+        Ok(())
+    }
+}
+
+struct PropertySerializer<'a, 'n, W> {
+    ser: &'a mut Serializer<W>,
+    name: &'n str,
+    sizes: Sizes,
+    hint: Hint
+}
+
+impl<'a, 'n, W: Write> PropertySerializer<'a, 'n, W> {
+    /// `EnumProperty`, or `ByteProperty` tagged with an enum, holding the name of a value of the enum
+    fn write_label(self, type_name: &str, enum_name: Option<&str>, label: &str) -> Result<()> {
+        let enum_name = enum_name.ok_or_else(|| Error::make_data(format!("{} {} needs an enum name", type_name, self.name)))?;
+        self.ser.write_tag(self.name, type_name, self.sizes.value)?;
+        self.ser.write_string(enum_name)?;
+        self.ser.begin_value()?;
+        self.ser.write_string(label)
+    }
+
+    /// Writes the tag of a `StructProperty`, the value that follows is the struct
+    fn begin_struct(&mut self, struct_name: &str) -> Result<()> {
+        self.hint.check("StructProperty")?;
+        self.ser.write_tag(self.name, "StructProperty", self.sizes.value)?;
+        self.ser.write_string(struct_name)?;
+        self.ser.output.write_all(&[0u8; 16])?; // guid
+        self.ser.begin_value()
+    }
+}
+
+impl<'a, 'n, W: Write> ser::Serializer for PropertySerializer<'a, 'n, W> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = ArraySerializer<'a, 'n, W>;
+    type SerializeTuple = StringsSerializer<'a, W>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = StructSerializer<'a, W>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    serialize_number_property!(serialize_i8, i8, Int8Property);
+    serialize_number_property!(serialize_i16, i16, Int16Property);
+    serialize_number_property!(serialize_u16, u16, UInt16Property);
+    serialize_number_property!(serialize_i32, i32, IntProperty);
+    serialize_number_property!(serialize_u32, u32, UInt32Property);
+    serialize_number_property!(serialize_i64, i64, Int64Property);
+    serialize_number_property!(serialize_u64, u64, UInt64Property);
+    serialize_number_property!(serialize_f32, f32, FloatProperty);
+    serialize_number_property!(serialize_f64, f64, DoubleProperty);
+    unsupported_serialize!(serialize_char, char);
+    unsupported_serialize!(serialize_unit_struct, &'static str);
+    unsupported_compound_serialize!();
+
+    /// A field that is `None` isn't written at all
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize {
+        value.serialize(self)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
+        self.hint.elements().check("ByteProperty")?;
+        self.ser.write_tag(self.name, "ArrayProperty", self.sizes.value)?;
+        self.ser.write_string("ByteProperty")?;
+        self.ser.begin_value()?;
+        self.ser.output.write_i32::<LittleEndian>(v.len() as i32)?;
+        self.ser.output.write_all(v)?;
+        Ok(())
+    }
+
+    /// Only a `SoftObjectProperty`, as its asset path and sub path
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        if self.hint.property != Some("SoftObjectProperty") || len != 2 {
+            return Err(Error::unsupported("serialize_tuple"));
+        }
+        self.ser.write_tag(self.name, "SoftObjectProperty", self.sizes.value)?;
+        self.ser.begin_value()?;
+        Ok(StringsSerializer { ser: self.ser })
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok> {
+        self.hint.check(BoolProperty::TYPE.name())?;
+        // the value is part of the tag
+        self.ser.write_tag(self.name, BoolProperty::TYPE.name(), 0)?;
+        Tag::Bool(v).write(&mut self.ser.output)?;
+        self.ser.begin_value()
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
+        self.hint.check("ByteProperty")?;
+        if let Some(enum_name) = self.hint.enum_name {
+            return Err(Error::make_data(format!("ByteProperty {} of enum {} holds a name, not a number", self.name, enum_name)));
+        }
+        self.ser.write_tag(self.name, "ByteProperty", self.sizes.value)?;
+        Tag::Enum(String::from("None")).write(&mut self.ser.output)?;
+        self.ser.begin_value()?;
+        ByteProperty::write_value(&mut self.ser.output, &ByteValue::Byte(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        match self.hint.property.unwrap_or("StrProperty") {
+            type_name @ ("StrProperty" | "NameProperty" | "ObjectProperty") => {
+                self.ser.write_tag(self.name, type_name, self.sizes.value)?;
+                self.ser.begin_value()?;
+                self.ser.write_string(v)
+            },
+            type_name @ ("EnumProperty" | "ByteProperty") => {
+                let enum_name = self.hint.enum_name;
+                self.write_label(type_name, enum_name, v)
+            },
+            type_name => Err(Error::make_data(format!("StrProperty can't be written as {}", type_name)))
+        }
+    }
+
+    fn serialize_unit_variant(self, name: &'static str, variant_index: u32, variant: &'static str) -> Result<Self::Ok> {
+        match (self.hint.property.unwrap_or("EnumProperty"), self.hint.enum_name) {
+            // an enum without a name in the tag is stored as its index
+            ("ByteProperty", None) if !self.hint.label => {
+                let v = u8::try_from(variant_index).map_err(|_| Error::make_data(format!("{}::{} doesn't fit in a byte", name, variant)))?;
+                self.serialize_u8(v)
+            },
+            (type_name @ ("EnumProperty" | "ByteProperty"), enum_name) => self.write_label(type_name, Some(enum_name.unwrap_or(name)), variant),
+            (type_name, _) => Err(Error::make_data(format!("EnumProperty can't be written as {}", type_name)))
+        }
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize {
+        match Hint::parse(name) {
+            Some(hint) => value.serialize(PropertySerializer { hint, ..self }),
+            None => value.serialize(self)
+        }
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        match len {
+            Some(len) => Ok(ArraySerializer { ser: self.ser, name: self.name, sizes: self.sizes, len, started: false, hint: self.hint.elements() }),
+            None => Err(Error::make_data(String::from("Length of the sequence must be known upfront!")))
+        }
+    }
+
+    fn serialize_struct(mut self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        let struct_name = self.hint.struct_name.unwrap_or(name);
+        self.begin_struct(struct_name)?;
+        Ok(StructSerializer::value(self.ser, struct_name))
+    }
+}
+
+/// The array tag and, for struct arrays, the inner tag are written once the first
+/// element shows what type the elements are.
+pub struct ArraySerializer<'a, 'n, W> {
+    ser: &'a mut Serializer<W>,
+    name: &'n str,
+    sizes: Sizes,
+    len: usize,
+    started: bool,
+    hint: Hint
+}
+
+impl<'a, 'n, W: Write> ArraySerializer<'a, 'n, W> {
+    fn start(&mut self, element_type: &str, struct_name: Option<&str>) -> Result<()> {
+        if self.started {
+            return Ok(());
+        }
+        self.started = true;
+
+        self.ser.write_tag(self.name, "ArrayProperty", self.sizes.value)?;
+        self.ser.write_string(element_type)?;
+        self.ser.begin_value()?;
+        self.ser.output.write_i32::<LittleEndian>(self.len as i32)?;
+
+        if let Some(struct_name) = struct_name {
+            self.ser.write_tag(self.name, "StructProperty", self.sizes.elements)?;
+            self.ser.write_string(struct_name)?;
+            self.ser.output.write_all(&[0u8; 16])?; // guid
+            self.ser.output.write_u8(0)?;
+            self.ser.begin_elements();
+        }
+        Ok(())
+    }
+}
+
+impl<'a, 'n, W: Write> ser::SerializeSeq for ArraySerializer<'a, 'n, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize {
+        let hint = self.hint;
+        value.serialize(ElementSerializer { array: self, hint })
+    }
+
+    /// Empty arrays take their element type from the hint
+    fn end(mut self) -> Result<Self::Ok> {
+        if self.started {
+            return Ok(());
+        }
+        match (self.hint.property, self.hint.struct_name) {
+            (Some("StructProperty") | None, Some(struct_name)) => self.start("StructProperty", Some(struct_name)),
+            (Some("StructProperty"), None) => Err(Error::make_data(format!("Can't tell the struct name of empty array {}", self.name))),
+            (Some(element_type), _) => self.start(element_type, None),
+            (None, None) => Err(Error::make_data(format!("Can't tell the element type of empty array {}", self.name)))
+        }
+    }
+}
+
+struct ElementSerializer<'s, 'a, 'n, W> {
+    array: &'s mut ArraySerializer<'a, 'n, W>,
+    hint: Hint
+}
+
+impl<'s, 'a, 'n, W: Write> ser::Serializer for ElementSerializer<'s, 'a, 'n, W> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = StringsSerializer<'s, W>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = StructSerializer<'s, W>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    serialize_number_element!(serialize_i8, i8, Int8Property);
+    serialize_number_element!(serialize_i16, i16, Int16Property);
+    serialize_number_element!(serialize_u16, u16, UInt16Property);
+    serialize_number_element!(serialize_i32, i32, IntProperty);
+    serialize_number_element!(serialize_u32, u32, UInt32Property);
+    serialize_number_element!(serialize_i64, i64, Int64Property);
+    serialize_number_element!(serialize_u64, u64, UInt64Property);
+    serialize_number_element!(serialize_f32, f32, FloatProperty);
+    serialize_number_element!(serialize_f64, f64, DoubleProperty);
+    unsupported_serialize!(serialize_char, char);
+    unsupported_serialize!(serialize_bytes, &[u8]);
+    unsupported_serialize!(serialize_unit_struct, &'static str);
+    unsupported_compound_serialize!();
+
+    /// The element count is already written, so there's no leaving an element out
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Err(Error::make_data(format!("Array {} can't hold None", self.array.name)))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize {
+        value.serialize(self)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
+        self.hint.check(ByteProperty::TYPE.name())?;
+        self.array.start(ByteProperty::TYPE.name(), None)?;
+        ByteProperty::write_element(&mut self.array.ser.output, &ByteValue::Byte(v))
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok> {
+        self.hint.check(BoolProperty::TYPE.name())?;
+        self.array.start(BoolProperty::TYPE.name(), None)?;
+        BoolProperty::write_element(&mut self.array.ser.output, &v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        // array tags have no room for an enum name, so enum elements are plain names
+        match self.hint.property.unwrap_or("StrProperty") {
+            element_type @ ("StrProperty" | "NameProperty" | "ObjectProperty" | "EnumProperty") => {
+                self.array.start(element_type, None)?;
+                self.array.ser.write_string(v)
+            },
+            element_type => Err(Error::make_data(format!("StrProperty can't be written as {}", element_type)))
+        }
+    }
+
+    fn serialize_unit_variant(self, name: &'static str, variant_index: u32, variant: &'static str) -> Result<Self::Ok> {
+        match self.hint.property.unwrap_or("EnumProperty") {
+            "ByteProperty" if self.hint.label => {
+                self.array.start("ByteProperty", None)?;
+                self.array.ser.write_string(variant)
+            },
+            "ByteProperty" => {
+                let v = u8::try_from(variant_index).map_err(|_| Error::make_data(format!("{}::{} doesn't fit in a byte", name, variant)))?;
+                self.serialize_u8(v)
+            },
+            "EnumProperty" => self.serialize_str(variant),
+            element_type => Err(Error::make_data(format!("EnumProperty can't be written as {}", element_type)))
+        }
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize {
+        match Hint::parse(name) {
+            Some(hint) => value.serialize(ElementSerializer { hint, ..self }),
+            None => value.serialize(self)
+        }
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        if self.hint.property != Some("SoftObjectProperty") || len != 2 {
+            return Err(Error::unsupported("serialize_tuple"));
+        }
+        self.array.start("SoftObjectProperty", None)?;
+        Ok(StringsSerializer { ser: &mut *self.array.ser })
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(Error::unsupported("serialize_seq"))
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        self.hint.check("StructProperty")?;
+        let struct_name = self.hint.struct_name.unwrap_or(name);
+        self.array.start("StructProperty", Some(struct_name))?;
+        Ok(StructSerializer::value(&mut *self.array.ser, struct_name))
+    }
+}
+
+/// Writes each element of a tuple as a bare string
+pub struct StringsSerializer<'a, W> {
+    ser: &'a mut Serializer<W>
+}
+
+impl<'a, W: Write> ser::SerializeTuple for StringsSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize {
+        value.serialize(StringSerializer { ser: &mut *self.ser })
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(())
+    }
+}
+
+struct StringSerializer<'a, W> {
+    ser: &'a mut Serializer<W>
+}
+
+impl<'a, W: Write> ser::Serializer for StringSerializer<'a, W> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    unsupported_serialize!(serialize_bool, bool);
+    unsupported_serialize!(serialize_i8, i8);
+    unsupported_serialize!(serialize_i16, i16);
+    unsupported_serialize!(serialize_i32, i32);
+    unsupported_serialize!(serialize_i64, i64);
+    unsupported_serialize!(serialize_u8, u8);
+    unsupported_serialize!(serialize_u16, u16);
+    unsupported_serialize!(serialize_u32, u32);
+    unsupported_serialize!(serialize_u64, u64);
+    unsupported_serialize!(serialize_f32, f32);
+    unsupported_serialize!(serialize_f64, f64);
+    unsupported_serialize!(serialize_char, char);
+    unsupported_serialize!(serialize_bytes, &[u8]);
+    unsupported_serialize!(serialize_unit_struct, &'static str);
+    unsupported_compound_serialize!();
+    unsupported_option_serialize!();
+    unsupported_newtype_serialize!();
+    unsupported_tuple_serialize!();
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        self.ser.write_string(v)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(Error::unsupported("serialize_seq"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(Error::unsupported("serialize_struct"))
+    }
+}
+
+/// Turns a field of a struct with a registered codec into a value of the value tree, for the codec to
+/// encode. `None` leaves the field out.
+struct ValueSerializer<'n> {
+    name: &'n str,
+    hint: Hint,
+    /// Enum values in arrays don't name their enum
+    element: bool
+}
+
+impl<'n> ValueSerializer<'n> {
+    fn label(self, type_name: &str, enum_name: Option<&str>, label: &str) -> Result<Option<Value>> {
+        let enum_name = match (self.element, enum_name) {
+            (true, _) => "None",
+            (false, Some(enum_name)) => enum_name,
+            (false, None) => return Err(Error::make_data(format!("{} {} needs an enum name", type_name, self.name)))
+        };
+        let (enum_name, label) = (String::from(enum_name), String::from(label));
+        Ok(Some(match type_name {
+            "ByteProperty" => Value::Byte { enum_name, value: ByteValue::Label(label) },
+            _ => Value::Enum { enum_name, value: label }
+        }))
+    }
+}
+
+impl<'n> ser::Serializer for ValueSerializer<'n> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    type SerializeSeq = ValueArraySerializer<'n>;
+    type SerializeTuple = ValueStringsSerializer;
+    type SerializeTupleStruct = Impossible<Option<Value>, Error>;
+    type SerializeTupleVariant = Impossible<Option<Value>, Error>;
+    type SerializeMap = Impossible<Option<Value>, Error>;
+    type SerializeStruct = ValueStructSerializer;
+    type SerializeStructVariant = Impossible<Option<Value>, Error>;
+
+    serialize_number_value!(serialize_i8, i8, Int8Property, Int8);
+    serialize_number_value!(serialize_i16, i16, Int16Property, Int16);
+    serialize_number_value!(serialize_u16, u16, UInt16Property, UInt16);
+    serialize_number_value!(serialize_i32, i32, IntProperty, Int);
+    serialize_number_value!(serialize_u32, u32, UInt32Property, UInt32);
+    serialize_number_value!(serialize_f32, f32, FloatProperty, Float);
+    serialize_number_value!(serialize_f64, f64, DoubleProperty, Double);
+    unsupported_serialize!(serialize_char, char);
+    unsupported_serialize!(serialize_unit_struct, &'static str);
+    unsupported_compound_serialize!();
+
+    fn serialize_none(self) -> Result<Self::Ok> {
+        Ok(None)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize {
+        value.serialize(self)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
+        if self.hint.struct_name == Some("Timespan") {
+            return Ok(Some(Value::Struct { struct_name: String::from("Timespan"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Timespan(v) }));
+        }
+        self.hint.check(Int64Property::TYPE.name())?;
+        Ok(Some(Value::Int64(v)))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
+        if self.hint.struct_name == Some("DateTime") {
+            return Ok(Some(Value::Struct { struct_name: String::from("DateTime"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::DateTime(v) }));
+        }
+        self.hint.check(UInt64Property::TYPE.name())?;
+        Ok(Some(Value::UInt64(v)))
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok> {
+        self.hint.check(BoolProperty::TYPE.name())?;
+        Ok(Some(Value::Bool(v)))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
+        self.hint.check("ByteProperty")?;
+        if let Some(enum_name) = self.hint.enum_name {
+            return Err(Error::make_data(format!("ByteProperty {} of enum {} holds a name, not a number", self.name, enum_name)));
+        }
+        Ok(Some(Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(v) }))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
+        self.hint.elements().check("ByteProperty")?;
+        Ok(Some(Value::Array { element_type: String::from("ByteProperty"), value: ArrayValue::Bytes(v.to_vec()) }))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok> {
+        Ok(Some(match self.hint.property.unwrap_or("StrProperty") {
+            "StrProperty" => Value::Str(String::from(v)),
+            "NameProperty" => Value::Name(String::from(v)),
+            "ObjectProperty" => Value::Object(String::from(v)),
+            type_name @ ("EnumProperty" | "ByteProperty") => {
+                let enum_name = self.hint.enum_name;
+                return self.label(type_name, enum_name, v);
+            },
+            type_name => return Err(Error::make_data(format!("StrProperty can't be written as {}", type_name)))
+        }))
+    }
+
+    fn serialize_unit_variant(self, name: &'static str, variant_index: u32, variant: &'static str) -> Result<Self::Ok> {
+        match (self.hint.property.unwrap_or("EnumProperty"), self.hint.enum_name) {
+            ("ByteProperty", None) if !self.hint.label => {
+                let v = u8::try_from(variant_index).map_err(|_| Error::make_data(format!("{}::{} doesn't fit in a byte", name, variant)))?;
+                self.serialize_u8(v)
+            },
+            (type_name @ ("EnumProperty" | "ByteProperty"), enum_name) => self.label(type_name, Some(enum_name.unwrap_or(name)), variant),
+            (type_name, _) => Err(Error::make_data(format!("EnumProperty can't be written as {}", type_name)))
+        }
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Self::Ok>
+    where
+        T: ?Sized + Serialize {
+        match Hint::parse(name) {
+            Some(hint) => value.serialize(ValueSerializer { hint, ..self }),
+            None => value.serialize(self)
+        }
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        if self.hint.property != Some("SoftObjectProperty") || len != 2 {
+            return Err(Error::unsupported("serialize_tuple"));
+        }
+        Ok(ValueStringsSerializer { strings: Vec::new() })
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(ValueArraySerializer { name: self.name, hint: self.hint.elements(), elements: Vec::with_capacity(len.unwrap_or(0)) })
+    }
+
+    fn serialize_struct(self, name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        self.hint.check("StructProperty")?;
+        Ok(ValueStructSerializer { struct_name: self.hint.struct_name.unwrap_or(name), fields: Vec::new() })
+    }
+}
+
+struct ValueArraySerializer<'n> {
+    name: &'n str,
+    hint: Hint,
+    elements: Vec<Value>
+}
+
+impl<'n> ser::SerializeSeq for ValueArraySerializer<'n> {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize {
+        match value.serialize(ValueSerializer { name: self.name, hint: self.hint, element: true })? {
+            Some(value) => self.elements.push(value),
+            None => return Err(Error::make_data(format!("Array {} can't hold None", self.name)))
+        }
+        Ok(())
+    }
+
+    /// Laid out like the value tree reads arrays, empty arrays take their element type from the hint
+    fn end(self) -> Result<Self::Ok> {
+        let element_type = match (self.elements.first(), self.hint.property, self.hint.struct_name) {
+            (Some(value), _, _) => String::from(value.type_name()),
+            (None, Some("StructProperty") | None, Some(_)) => String::from("StructProperty"),
+            (None, Some("StructProperty"), None) => return Err(Error::make_data(format!("Can't tell the struct name of empty array {}", self.name))),
+            (None, Some(element_type), _) => String::from(element_type),
+            (None, None, None) => return Err(Error::make_data(format!("Can't tell the element type of empty array {}", self.name)))
+        };
+        let value = match element_type.as_str() {
+            "StructProperty" => {
+                let mut struct_name = self.hint.struct_name.map(String::from);
+                let mut elements = Vec::with_capacity(self.elements.len());
+                for element in self.elements {
+                    match element {
+                        Value::Struct { struct_name: name, value, .. } => {
+                            struct_name.get_or_insert(name);
+                            elements.push(value);
+                        },
+                        value => return Err(Error::make_data(format!("Array {} mixes structs and {}", self.name, value.type_name())))
+                    }
+                }
+                ArrayValue::Structs {
+                    name: String::from(self.name), array_index: 0, property_guid: None, struct_name: struct_name.unwrap_or_default(),
+                    guid: FGuid::new(0, 0, 0, 0), elements
+                }
+            },
+            "ByteProperty" if self.elements.iter().all(|element| matches!(element, Value::Byte { value: ByteValue::Byte(_), .. })) => {
+                ArrayValue::Bytes(self.elements.iter().filter_map(|element| match element {
+                    Value::Byte { value: ByteValue::Byte(byte), .. } => Some(*byte),
+                    _ => None
+                }).collect())
+            },
+            _ => ArrayValue::Values(self.elements)
+        };
+        Ok(Some(Value::Array { element_type, value }))
+    }
+}
+
+/// Collects the asset path and sub path of a `SoftObjectProperty`
+struct ValueStringsSerializer {
+    strings: Vec<String>
+}
+
+impl ser::SerializeTuple for ValueStringsSerializer {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize {
+        match value.serialize(ValueSerializer { name: "", hint: Hint::default(), element: true })? {
+            Some(Value::Str(string)) => self.strings.push(string),
+            _ => return Err(Error::make_data(String::from("SoftObjectProperty holds two strings")))
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        match <[String; 2]>::try_from(self.strings) {
+            Ok(paths) => Ok(Some(Value::SoftObject(paths))),
+            Err(_) => Err(Error::make_data(String::from("SoftObjectProperty holds two strings")))
+        }
+    }
+}
+
+struct ValueStructSerializer {
+    struct_name: &'static str,
+    fields: Vec<Property>
+}
+
+impl ser::SerializeStruct for ValueStructSerializer {
+    type Ok = Option<Value>;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize {
+        if let Some(value) = value.serialize(ValueSerializer { name: key, hint: Hint::default(), element: false })? {
+            self.fields.push(Property { name: String::from(key), array_index: 0, guid: None, value });
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok> {
+        Ok(Some(Value::Struct { struct_name: String::from(self.struct_name), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Properties(self.fields) }))
+    }
+}
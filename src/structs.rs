@@ -0,0 +1,155 @@
+//! Structs that games serialize natively, with a layout of their own instead of tagged properties.
+//!
+//! Registering a decoder and an encoder for a struct name in a [`StructRegistry`] makes readers and
+//! writers given that registry use them for values of that struct, as properties and as elements of
+//! arrays, sets and maps. Readers take it as [`crate::DeserializerOptions::structs`], writers as an argument
+//! of their `_with_structs` variant. The decoder turns the raw bytes into fields, which show up like
+//! the properties of any other struct: in the value tree, as the fields of a type deserialized with
+//! serde, in paths and in the text format.
+//!
+//! Nothing in a save tells which struct the struct elements of a set or map are, they are read as
+//! properties unless [`StructRegistry::register_item`] names their struct. Elements that aren't
+//! properties are only taken for a guid when the size of the set or map leaves them 16 bytes each.
+//!
+//! ```
+//! use std::io::Cursor;
+//!
+//! use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+//! use serde_gvas::{value, DeserializerOptions, Error};
+//! use serde_gvas::structs::{Item, StructRegistry};
+//! use serde_gvas::value::{Property, Value};
+//!
+//! let mut structs = StructRegistry::new();
+//! // an item id followed by a count
+//! structs.register("ItemRecord", |input| {
+//!     let id = input.read_u32::<LittleEndian>()?;
+//!     let count = input.read_u16::<LittleEndian>()?;
+//!     Ok(vec![
+//!         Property { name: String::from("Id"), array_index: 0, guid: None, value: Value::UInt32(id) },
+//!         Property { name: String::from("Count"), array_index: 0, guid: None, value: Value::UInt16(count) }
+//!     ])
+//! }, |fields, output| {
+//!     match fields {
+//!         [Property { value: Value::UInt32(id), .. }, Property { value: Value::UInt16(count), .. }] => {
+//!             output.write_u32::<LittleEndian>(*id)?;
+//!             output.write_u16::<LittleEndian>(*count)?;
+//!             Ok(())
+//!         },
+//!         _ => Err(Error::make_data(String::from("ItemRecord needs an Id and a Count")))
+//!     }
+//! });
+//! // the values of the map `Stash` hold item records too
+//! structs.register_item("Stash", Item::Value, "ItemRecord");
+//!
+//! # let data = b"\x05\0\0\0None\0".to_vec();
+//! let options = DeserializerOptions { structs: structs.clone(), ..Default::default() };
+//! let (properties, _) = value::from_bytes_with_diagnostics(&mut Cursor::new(data), options)?;
+//! let data = value::to_vec_with_structs(&properties, &structs)?;
+//! # Ok::<(), Error>(())
+//! ```
+
+use std::fmt;
+use std::io::Cursor;
+use std::sync::Arc;
+
+use crate::error::Result;
+use crate::value::Property;
+
+pub type Decode = dyn Fn(&mut Cursor<Vec<u8>>) -> Result<Vec<Property>> + Send + Sync;
+pub type Encode = dyn Fn(&[Property], &mut Vec<u8>) -> Result<()> + Send + Sync;
+
+#[derive(Clone)]
+pub(crate) struct StructCodec {
+    decode: Arc<Decode>,
+    encode: Arc<Encode>
+}
+
+impl StructCodec {
+    /// Reads one value from the input's position
+    pub(crate) fn decode(&self, input: &mut Cursor<Vec<u8>>) -> Result<Vec<Property>> {
+        (self.decode)(input)
+    }
+
+    pub(crate) fn encode(&self, fields: &[Property], output: &mut Vec<u8>) -> Result<()> {
+        (self.encode)(fields, output)
+    }
+}
+
+impl PartialEq for StructCodec {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.decode, &other.decode) && Arc::ptr_eq(&self.encode, &other.encode)
+    }
+}
+
+impl Eq for StructCodec {}
+
+/// What a struct element of a set or map is, see [`StructRegistry::register_item`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Item {
+    /// An element of a set
+    Element,
+    Key,
+    Value
+}
+
+/// Codecs of natively serialized structs by struct name, cheap to clone
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct StructRegistry {
+    codecs: Arc<Vec<(String, StructCodec)>>,
+    items: Arc<Vec<(String, Item, String)>>
+}
+
+impl StructRegistry {
+    pub fn new() -> Self {
+        StructRegistry::default()
+    }
+
+    /// Decodes and encodes values of `struct_name` with the given functions, replacing what was
+    /// registered for it before. This takes precedence over the built in `DateTime`, `Timespan`
+    /// and `Guid` structs.
+    pub fn register<D, E>(&mut self, struct_name: &str, decode: D, encode: E)
+    where
+        D: Fn(&mut Cursor<Vec<u8>>) -> Result<Vec<Property>> + Send + Sync + 'static,
+        E: Fn(&[Property], &mut Vec<u8>) -> Result<()> + Send + Sync + 'static {
+        let codec = StructCodec { decode: Arc::new(decode), encode: Arc::new(encode) };
+        let codecs = Arc::make_mut(&mut self.codecs);
+        codecs.retain(|(name, _)| name != struct_name);
+        codecs.push((String::from(struct_name), codec));
+    }
+
+    /// Goes back to reading `struct_name` as tagged properties, returns whether it was registered
+    pub fn unregister(&mut self, struct_name: &str) -> bool {
+        let len = self.codecs.len();
+        Arc::make_mut(&mut self.codecs).retain(|(name, _)| name != struct_name);
+        self.codecs.len() != len
+    }
+
+    pub fn is_registered(&self, struct_name: &str) -> bool {
+        self.codec(struct_name).is_some()
+    }
+
+    /// Reads the struct elements of the set or map property `property` as `struct_name`, which can
+    /// be a registered struct, a built in one or a struct with properties
+    pub fn register_item(&mut self, property: &str, item: Item, struct_name: &str) {
+        let items = Arc::make_mut(&mut self.items);
+        items.retain(|(name, known, _)| name != property || *known != item);
+        items.push((String::from(property), item, String::from(struct_name)));
+    }
+
+    pub(crate) fn codec(&self, struct_name: &str) -> Option<&StructCodec> {
+        self.codecs.iter().find(|(name, _)| name == struct_name).map(|(_, codec)| codec)
+    }
+
+    pub(crate) fn item_struct(&self, property: &str, item: Item) -> Option<&str> {
+        self.items.iter().find(|(name, known, _)| name == property && *known == item).map(|(_, _, struct_name)| struct_name.as_str())
+    }
+}
+
+impl fmt::Debug for StructRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StructRegistry")
+            .field("codecs", &self.codecs.iter().map(|(name, _)| name).collect::<Vec<_>>())
+            .field("items", &self.items)
+            .finish()
+    }
+}
@@ -0,0 +1,733 @@
+//! Indentation based text format for saves, meant for reviewing changes in version control.
+//!
+//! Every property takes one line, `Name: Type = value`. Structs and arrays continue in an indented
+//! block below, array elements start with `-`:
+//!
+//! ```text
+//! header:
+//!     save_game_file_version = 2
+//!     ...
+//! properties:
+//!     Gold: IntProperty = 250
+//!     Difficulty: ByteProperty<EDifficulty> = "EDifficulty::Hard"
+//!     Stats: StructProperty<PlayerStats>
+//!         Level: IntProperty = 12
+//!     Scores: ArrayProperty<IntProperty>
+//!         - 10
+//!         - 20
+//!     Checksum: ArrayProperty<ByteProperty> = [0A FF 3C]
+//! ```
+//!
+//! Set elements start with `-` like array elements, removed elements with `~`. Map entries are a `-`
+//! followed by a `key` and a `value`, removed keys start with `~`. Structs in sets and maps are
+//! written like the elements of struct arrays:
+//!
+//! ```text
+//!     Seen: SetProperty<NameProperty>
+//!         - "Cave"
+//!     Owners: MapProperty<StructProperty, StrProperty>
+//!         -
+//!             key = 00000001-00000002-00000003-00000004
+//!             value = "Bob"
+//!         -
+//!             key:
+//!                 Id: IntProperty = 2
+//!             value = "Alice"
+//! ```
+//!
+//! A property guid follows the type as `@GUID` and a non zero array index follows the name as `Name[1]`.
+//! The tag in front of struct array elements has its name, array index and guid as further type
+//! arguments when they differ from the property, e.g. `ArrayProperty<StructProperty<Point>, Inner, 2>`.
+//! Properties that couldn't be decoded are kept as `Unknown<Type> = [tag bytes] [value bytes]`.
+//! Lines starting with `#` are comments. Like JSON, the text converts back to the exact bytes it was
+//! made from.
+
+use crate::compression::Container;
+use crate::custom_version;
+use crate::error::{Error, Result};
+use crate::property::{self, PropertyType, Tag};
+use crate::types::{FCustomVersion, FEngineVersion, FGuid, GvasHeader};
+use crate::value::{ArrayValue, Property, Save, StructValue, Value};
+
+const ZERO_GUID: FGuid = FGuid::new(0, 0, 0, 0);
+
+pub fn to_string(save: &Save) -> String {
+    let mut printer = Printer { output: String::new() };
+    printer.header(&save.header);
+    printer.line(0, "properties:");
+    printer.properties(&save.properties, 1);
+    printer.output
+}
+
+pub fn from_str(s: &str) -> Result<Save> {
+    let lines = s.lines().enumerate().filter_map(|(index, line)| {
+        let text = line.trim_start();
+        match text.is_empty() || text.starts_with('#') {
+            true => None,
+            false => Some(Line { number: index + 1, indent: line.len() - text.len(), text: text.trim_end() })
+        }
+    }).collect();
+    let mut parser = Parser { lines, position: 0 };
+
+    let mut header = None;
+    let mut properties = None;
+    while let Some(line) = parser.lines.get(parser.position).copied() {
+        parser.position += 1;
+        match (line.indent, line.text) {
+            (0, "header:") => header = Some(parser.header(&line)?),
+            (0, "properties:") => properties = Some(parser.properties(0)?),
+            _ => return Err(line.error("Expected header: or properties:"))
+        }
+    }
+    match (header, properties) {
+        (Some(header), Some(properties)) => Ok(Save { header, properties, container: Container::Raw }),
+        _ => Err(Error::make_data(String::from("Missing header or properties")))
+    }
+}
+
+pub(crate) fn format_guid(guid: &FGuid) -> String {
+    format!("{:08X}-{:08X}-{:08X}-{:08X}", guid.a, guid.b, guid.c, guid.d)
+}
+
+fn parse_guid(s: &str) -> Option<FGuid> {
+    let mut parts = s.split('-').map(|part| match part.len() {
+        8 => u32::from_str_radix(part, 16).ok(),
+        _ => None
+    });
+    let guid = FGuid::new(parts.next()??, parts.next()??, parts.next()??, parts.next()??);
+    match parts.next() {
+        None => Some(guid),
+        Some(_) => None
+    }
+}
+
+pub(crate) fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '/')
+}
+
+/// Names are only left bare when they can't be confused with the rest of the line
+fn name_text(name: &str) -> String {
+    match !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        true => String::from(name),
+        false => quote(name)
+    }
+}
+
+fn arg_text(arg: &str) -> String {
+    match !arg.is_empty() && arg.chars().all(is_word_char) {
+        true => String::from(arg),
+        false => quote(arg)
+    }
+}
+
+fn hex_text(bytes: &[u8]) -> String {
+    let bytes: Vec<String> = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+    format!("[{}]", bytes.join(" "))
+}
+
+fn struct_spec(struct_name: &str, guid: &FGuid) -> String {
+    match *guid == ZERO_GUID {
+        true => format!("StructProperty<{}>", arg_text(struct_name)),
+        false => format!("StructProperty<{}, {}>", arg_text(struct_name), format_guid(guid))
+    }
+}
+
+fn type_spec(name: &str, value: &Value) -> String {
+    match value {
+        Value::Byte { enum_name, .. } | Value::Enum { enum_name, .. } if enum_name == "None" => String::from(value.type_name()),
+        Value::Byte { enum_name, .. } | Value::Enum { enum_name, .. } => format!("{}<{}>", value.type_name(), arg_text(enum_name)),
+        Value::Struct { struct_name, guid, .. } => struct_spec(struct_name, guid),
+        Value::Array { value: ArrayValue::Structs { name: inner_name, array_index, property_guid, struct_name, guid, .. }, .. } => {
+            let mut args = vec![struct_spec(struct_name, guid), arg_text(inner_name), array_index.to_string()];
+            if let Some(property_guid) = property_guid {
+                args.push(format_guid(property_guid));
+            }
+            // trailing arguments are left out while they match the property
+            let keep = match (property_guid, *array_index, inner_name == name) {
+                (Some(_), _, _) => 4,
+                (None, 0, true) => 1,
+                (None, 0, false) => 2,
+                (None, _, _) => 3
+            };
+            format!("ArrayProperty<{}>", args[..keep].join(", "))
+        },
+        Value::Array { element_type, .. } => format!("ArrayProperty<{}>", arg_text(element_type)),
+        Value::Set { element_type, .. } => format!("SetProperty<{}>", arg_text(element_type)),
+        Value::Map { key_type, value_type, .. } => format!("MapProperty<{}, {}>", arg_text(key_type), arg_text(value_type)),
+        Value::Unknown { type_name, .. } => format!("Unknown<{}>", arg_text(type_name)),
+        _ => String::from(value.type_name())
+    }
+}
+
+pub(crate) fn scalar_text(value: &Value) -> String {
+    property::text(value).unwrap_or_default()
+}
+
+pub(crate) fn struct_scalar_text(value: &StructValue) -> String {
+    match value {
+        StructValue::DateTime(ticks) => ticks.to_string(),
+        StructValue::Timespan(ticks) => ticks.to_string(),
+        StructValue::Guid(guid) => format_guid(guid),
+        StructValue::Properties(_) => String::new()
+    }
+}
+
+struct Printer {
+    output: String
+}
+
+impl Printer {
+    fn line(&mut self, depth: usize, line: &str) {
+        for _ in 0..depth {
+            self.output.push_str("    ");
+        }
+        self.output.push_str(line);
+        self.output.push('\n');
+    }
+
+    fn header(&mut self, header: &GvasHeader) {
+        let engine = &header.engine_version;
+        self.line(0, "header:");
+        self.line(1, &format!("file_type_tag = {}", header.file_type_tag));
+        self.line(1, &format!("save_game_file_version = {}", header.save_game_file_version));
+        self.line(1, &format!("package_file_ue4_version = {}", header.package_file_ue4_version));
+        if let Some(version) = header.package_file_ue5_version {
+            self.line(1, &format!("package_file_ue5_version = {}", version));
+        }
+        self.line(1, &format!("engine_version = {}.{}.{} {} {}", engine.major, engine.minor, engine.patch, engine.change_list, quote(&engine.branch)));
+        self.line(1, &format!("custom_version_format = {}", header.custom_version_format));
+        self.line(1, "custom_versions:");
+        for version in &header.custom_versions {
+            // known versions are written by name, as long as the name leads back to the same guid
+            let key = match version.info() {
+                Some(info) if custom_version::find(info.name).map(|info| info.guid) == Some(version.key) => String::from(info.name),
+                _ => format_guid(&version.key)
+            };
+            self.line(2, &format!("{} = {}", key, version.version));
+        }
+        self.line(1, &format!("save_game_class_name = {}", quote(&header.save_game_class_name)));
+    }
+
+    fn properties(&mut self, properties: &[Property], depth: usize) {
+        for property in properties {
+            let mut head = name_text(&property.name);
+            if property.array_index != 0 {
+                head.push_str(&format!("[{}]", property.array_index));
+            }
+            head.push_str(": ");
+            head.push_str(&type_spec(&property.name, &property.value));
+            if let Some(guid) = &property.guid {
+                head.push_str(&format!(" @{}", format_guid(guid)));
+            }
+            self.value(&head, &property.value, depth);
+        }
+    }
+
+    fn value(&mut self, head: &str, value: &Value, depth: usize) {
+        match value {
+            Value::Struct { value: StructValue::Properties(properties), .. } => {
+                self.line(depth, head);
+                self.properties(properties, depth + 1);
+            },
+            Value::Struct { value, .. } => self.line(depth, &format!("{} = {}", head, struct_scalar_text(value))),
+            Value::Array { value: ArrayValue::Bytes(bytes), .. } => self.line(depth, &format!("{} = {}", head, hex_text(bytes))),
+            Value::Array { value: ArrayValue::Values(values), .. } => {
+                self.line(depth, head);
+                for value in values {
+                    self.line(depth + 1, &format!("- {}", scalar_text(value)));
+                }
+            },
+            Value::Array { value: ArrayValue::Structs { elements, .. }, .. } => {
+                self.line(depth, head);
+                for element in elements {
+                    match element {
+                        StructValue::Properties(properties) => {
+                            self.line(depth + 1, "-");
+                            self.properties(properties, depth + 2);
+                        },
+                        _ => self.line(depth + 1, &format!("- {}", struct_scalar_text(element)))
+                    }
+                }
+            },
+            Value::Unknown { tag, data, .. } => self.line(depth, &format!("{} = {} {}", head, hex_text(tag), hex_text(data))),
+            Value::Set { removed, elements, .. } => {
+                self.line(depth, head);
+                for element in removed {
+                    self.item(depth + 1, "~", element);
+                }
+                for element in elements {
+                    self.item(depth + 1, "-", element);
+                }
+            },
+            Value::Map { removed, entries, .. } => {
+                self.line(depth, head);
+                for key in removed {
+                    self.item(depth + 1, "~", key);
+                }
+                for (key, value) in entries {
+                    self.line(depth + 1, "-");
+                    self.item(depth + 2, "key", key);
+                    self.item(depth + 2, "value", value);
+                }
+            },
+            _ => self.line(depth, &format!("{} = {}", head, scalar_text(value)))
+        }
+    }
+}
+
+impl Printer {
+    /// An element of a set or map, `marker` is `-` or `~` for elements and `key` or `value` in entries
+    fn item(&mut self, depth: usize, marker: &str, value: &Value) {
+        let (text, properties) = match value {
+            Value::Struct { value: StructValue::Properties(properties), .. } => (None, Some(properties)),
+            Value::Struct { value, .. } => (Some(struct_scalar_text(value)), None),
+            value => (Some(scalar_text(value)), None)
+        };
+        match (text, marker) {
+            (Some(text), "-" | "~") => self.line(depth, &format!("{} {}", marker, text)),
+            (Some(text), _) => self.line(depth, &format!("{} = {}", marker, text)),
+            (None, "-" | "~") => self.line(depth, marker),
+            (None, _) => self.line(depth, &format!("{}:", marker))
+        }
+        if let Some(properties) = properties {
+            self.properties(properties, depth + 1);
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Line<'a> {
+    number: usize,
+    indent: usize,
+    text: &'a str
+}
+
+impl<'a> Line<'a> {
+    fn error(&self, msg: &str) -> Error {
+        Error::make_data(format!("Line {}: {}", self.number, msg))
+    }
+
+    fn scanner(&self) -> Scanner<'a> {
+        Scanner { text: self.text, line: *self }
+    }
+}
+
+/// Reads the pieces of a single line
+struct Scanner<'a> {
+    text: &'a str,
+    line: Line<'a>
+}
+
+/// A type with its generic arguments, e.g. `ArrayProperty<StructProperty<Vector>>`
+struct Spec {
+    name: String,
+    args: Vec<Spec>
+}
+
+impl Spec {
+    fn arg(&self, index: usize) -> Option<&str> {
+        self.args.get(index).map(|arg| arg.name.as_str())
+    }
+}
+
+impl<'a> Scanner<'a> {
+    fn eat(&mut self, c: char) -> bool {
+        self.text = self.text.trim_start();
+        match self.text.strip_prefix(c) {
+            Some(rest) => {
+                self.text = rest;
+                true
+            },
+            None => false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(self.line.error(&format!("Expected '{}'", c)))
+        }
+    }
+
+    fn word(&mut self) -> Result<&'a str> {
+        self.text = self.text.trim_start();
+        let end = self.text.find(|c: char| !is_word_char(c)).unwrap_or(self.text.len());
+        if end == 0 {
+            return Err(self.line.error("Expected a name"));
+        }
+        let (word, rest) = self.text.split_at(end);
+        self.text = rest;
+        Ok(word)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.text = self.text.trim_start();
+        match self.text.starts_with('"') {
+            true => self.quoted(),
+            false => Ok(String::from(self.word()?))
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String> {
+        self.expect('"')?;
+        let mut s = String::new();
+        let mut chars = self.text.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.text = &self.text[index + 1..];
+                    return Ok(s);
+                },
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => s.push('\n'),
+                    Some('r') => s.push('\r'),
+                    Some('t') => s.push('\t'),
+                    Some('u') => {
+                        let rest = &self.text[index + 2..];
+                        let end = rest.find('}').ok_or_else(|| self.line.error("Unterminated escape"))?;
+                        let c = rest.strip_prefix('{').and_then(|code| u32::from_str_radix(&code[..end - 1], 16).ok()).and_then(char::from_u32);
+                        s.push(c.ok_or_else(|| self.line.error("Invalid escape"))?);
+                        for _ in 0..=end {
+                            chars.next();
+                        }
+                    },
+                    Some(c) => s.push(c),
+                    None => break
+                },
+                c => s.push(c)
+            }
+        }
+        Err(self.line.error("Unterminated string"))
+    }
+
+    fn spec(&mut self) -> Result<Spec> {
+        let name = self.string()?;
+        let mut args = Vec::new();
+        if self.eat('<') {
+            loop {
+                args.push(self.spec()?);
+                if self.eat('>') {
+                    break;
+                }
+                self.expect(',')?;
+            }
+        }
+        Ok(Spec { name, args })
+    }
+
+    fn end(&self) -> Result<()> {
+        match self.text.trim().is_empty() {
+            true => Ok(()),
+            false => Err(self.line.error(&format!("Unexpected {}", self.text.trim())))
+        }
+    }
+}
+
+struct Parser<'a> {
+    lines: Vec<Line<'a>>,
+    position: usize
+}
+
+impl<'a> Parser<'a> {
+    /// Calls `f` for every line of the block nested below a line indented by `parent`
+    fn block<F>(&mut self, parent: usize, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, Line<'a>) -> Result<()> {
+        let indent = match self.lines.get(self.position) {
+            Some(line) if line.indent > parent => line.indent,
+            _ => return Ok(())
+        };
+        while let Some(line) = self.lines.get(self.position).copied() {
+            if line.indent <= parent {
+                break;
+            }
+            if line.indent != indent {
+                return Err(line.error("Unexpected indentation"));
+            }
+            self.position += 1;
+            f(self, line)?;
+        }
+        Ok(())
+    }
+
+    fn header(&mut self, line: &Line<'a>) -> Result<GvasHeader> {
+        let mut fields: Vec<(&str, Line)> = Vec::new();
+        let mut custom_versions = Vec::new();
+        self.block(line.indent, |parser, line| {
+            if line.text == "custom_versions:" {
+                return parser.block(line.indent, |_, line| {
+                    let (key, version) = line.text.split_once('=').ok_or_else(|| line.error("Expected key = version"))?;
+                    let key = key.trim();
+                    let key = match parse_guid(key) {
+                        Some(guid) => guid,
+                        None => custom_version::find(key).ok_or_else(|| line.error(&format!("Unknown custom version {}", key)))?.guid
+                    };
+                    let version = version.trim().parse().map_err(|_| line.error("Invalid version"))?;
+                    custom_versions.push(FCustomVersion { key, version });
+                    Ok(())
+                });
+            }
+            let (key, _) = line.text.split_once('=').ok_or_else(|| line.error("Expected key = value"))?;
+            fields.push((key.trim(), line));
+            Ok(())
+        })?;
+
+        let value = |name: &str| -> Result<Scanner> {
+            let (_, line) = fields.iter().find(|(key, _)| *key == name).ok_or_else(|| Error::make_data(format!("Missing header field {}", name)))?;
+            let mut scanner = line.scanner();
+            scanner.text = &scanner.text[scanner.text.find('=').unwrap_or(0) + 1..];
+            Ok(scanner)
+        };
+        let number = |name: &str| -> Result<i32> {
+            let scanner = value(name)?;
+            scanner.text.trim().parse().map_err(|_| scanner.line.error(&format!("Invalid {}", name)))
+        };
+
+        let mut engine = value("engine_version")?;
+        let version = engine.word()?;
+        let mut parts = version.split('.').map(|part| part.parse::<u16>().ok());
+        let (major, minor, patch) = match (parts.next().flatten(), parts.next().flatten(), parts.next().flatten(), parts.next()) {
+            (Some(major), Some(minor), Some(patch), None) => (major, minor, patch),
+            _ => return Err(engine.line.error("Invalid engine version"))
+        };
+        let change_list = engine.word()?.parse().map_err(|_| engine.line.error("Invalid change list"))?;
+        let branch = engine.string()?;
+        engine.end()?;
+
+        let mut class_name = value("save_game_class_name")?;
+        let save_game_class_name = class_name.string()?;
+        class_name.end()?;
+
+        Ok(GvasHeader {
+            file_type_tag: number("file_type_tag")?,
+            save_game_file_version: number("save_game_file_version")?,
+            package_file_ue4_version: number("package_file_ue4_version")?,
+            package_file_ue5_version: match fields.iter().any(|(key, _)| *key == "package_file_ue5_version") {
+                true => Some(number("package_file_ue5_version")?),
+                false => None
+            },
+            engine_version: FEngineVersion { major, minor, patch, change_list, branch },
+            custom_version_format: number("custom_version_format")?,
+            custom_versions,
+            save_game_class_name
+        })
+    }
+
+    fn properties(&mut self, parent: usize) -> Result<Vec<Property>> {
+        let mut properties = Vec::new();
+        self.block(parent, |parser, line| {
+            properties.push(parser.property(line)?);
+            Ok(())
+        })?;
+        Ok(properties)
+    }
+
+    fn property(&mut self, line: Line<'a>) -> Result<Property> {
+        let mut scanner = line.scanner();
+        let name = scanner.string()?;
+        let mut array_index = 0;
+        if scanner.eat('[') {
+            let end = scanner.text.find(']').ok_or_else(|| line.error("Expected ']'"))?;
+            array_index = scanner.text[..end].trim().parse().map_err(|_| line.error("Invalid array index"))?;
+            scanner.text = &scanner.text[end + 1..];
+        }
+        scanner.expect(':')?;
+        let spec = scanner.spec()?;
+        let guid = match scanner.eat('@') {
+            true => Some(parse_guid(scanner.word()?).ok_or_else(|| line.error("Invalid guid"))?),
+            false => None
+        };
+        let text = match scanner.eat('=') {
+            true => Some(scanner.text.trim()),
+            false => {
+                scanner.end()?;
+                None
+            }
+        };
+        let value = self.value(&line, &name, &spec, text)?;
+        Ok(Property { name, array_index, guid, value })
+    }
+
+    fn value(&mut self, line: &Line<'a>, name: &str, spec: &Spec, text: Option<&'a str>) -> Result<Value> {
+        let required = |arg: Option<&str>| arg.map(String::from).ok_or_else(|| line.error(&format!("{} needs a type argument", spec.name)));
+
+        match spec.name.as_str() {
+            "StructProperty" => {
+                let struct_name = required(spec.arg(0))?;
+                let guid = struct_guid(line, spec)?;
+                let value = self.struct_value(line, &struct_name, text)?;
+                Ok(Value::Struct { struct_name, guid, value })
+            },
+            "ArrayProperty" => {
+                let element = spec.args.first().ok_or_else(|| line.error("ArrayProperty needs a type argument"))?;
+                let value = match (element.name.as_str(), text) {
+                    ("StructProperty", None) => {
+                        let struct_name = required(element.arg(0))?;
+                        let mut elements = Vec::new();
+                        self.block(line.indent, |parser, line| {
+                            let text = line.text.strip_prefix('-').ok_or_else(|| line.error("Expected '-'"))?.trim();
+                            elements.push(parser.struct_value(&line, &struct_name, (!text.is_empty()).then_some(text))?);
+                            Ok(())
+                        })?;
+                        ArrayValue::Structs {
+                            name: String::from(spec.arg(1).unwrap_or(name)),
+                            array_index: match spec.arg(2) {
+                                Some(array_index) => array_index.parse().map_err(|_| line.error("Invalid array index"))?,
+                                None => 0
+                            },
+                            property_guid: match spec.arg(3) {
+                                Some(guid) => Some(parse_guid(guid).ok_or_else(|| line.error("Invalid guid"))?),
+                                None => None
+                            },
+                            guid: struct_guid(line, element)?,
+                            struct_name,
+                            elements
+                        }
+                    },
+                    ("ByteProperty", Some(text)) => ArrayValue::Bytes(hex_bytes(line, text)?),
+                    (_, None) => {
+                        let mut values = Vec::new();
+                        self.block(line.indent, |_, line| {
+                            let text = line.text.strip_prefix('-').ok_or_else(|| line.error("Expected '-'"))?.trim();
+                            values.push(bare_value(&line, &element.name, text)?);
+                            Ok(())
+                        })?;
+                        ArrayValue::Values(values)
+                    },
+                    (_, Some(_)) => return Err(line.error("Only byte arrays can be written on one line"))
+                };
+                Ok(Value::Array { element_type: element.name.clone(), value })
+            },
+            "SetProperty" => {
+                let element_type = required(spec.arg(0))?;
+                let (mut removed, mut elements) = (Vec::new(), Vec::new());
+                self.block(line.indent, |parser, line| {
+                    let (items, text) = match (line.text.strip_prefix('-'), line.text.strip_prefix('~')) {
+                        (Some(text), _) => (&mut elements, text),
+                        (_, Some(text)) => (&mut removed, text),
+                        _ => return Err(line.error("Expected '-' or '~'"))
+                    };
+                    items.push(parser.item(&line, &element_type, text)?);
+                    Ok(())
+                })?;
+                Ok(Value::Set { element_type, removed, elements })
+            },
+            "MapProperty" => {
+                let key_type = required(spec.arg(0))?;
+                let value_type = required(spec.arg(1))?;
+                let (mut removed, mut entries) = (Vec::new(), Vec::new());
+                self.block(line.indent, |parser, line| {
+                    if let Some(text) = line.text.strip_prefix('~') {
+                        removed.push(parser.item(&line, &key_type, text)?);
+                        return Ok(());
+                    }
+                    if line.text != "-" {
+                        return Err(line.error("Expected '-' or '~'"));
+                    }
+                    let (mut key, mut value) = (None, None);
+                    parser.block(line.indent, |parser, line| {
+                        let (name, text) = line.text.split_once('=').unwrap_or((line.text.strip_suffix(':').unwrap_or(line.text), ""));
+                        match name.trim() {
+                            "key" if key.is_none() => key = Some(parser.item(&line, &key_type, text)?),
+                            "value" if value.is_none() => value = Some(parser.item(&line, &value_type, text)?),
+                            _ => return Err(line.error("Expected one key and one value"))
+                        }
+                        Ok(())
+                    })?;
+                    match (key, value) {
+                        (Some(key), Some(value)) => entries.push((key, value)),
+                        _ => return Err(line.error("Expected a key and a value"))
+                    }
+                    Ok(())
+                })?;
+                Ok(Value::Map { key_type, value_type, removed, entries })
+            },
+            "Unknown" => {
+                let type_name = required(spec.arg(0))?;
+                let text = required_text(line, text)?;
+                let end = text.find(']').ok_or_else(|| line.error("Expected tag and value bytes"))?;
+                Ok(Value::Unknown { type_name, tag: hex_bytes(line, &text[..=end])?, data: hex_bytes(line, &text[end + 1..])? })
+            },
+            type_name => {
+                // the only scalar tags with an argument are enum names
+                let tag = spec.arg(0).map_or(Tag::Empty, |enum_name| Tag::Enum(String::from(enum_name)));
+                PropertyType::from_name(type_name).parse_text(required_text(line, text)?, &tag).map_err(|err| line.error(&err.to_string()))
+            }
+        }
+    }
+
+    fn struct_value(&mut self, line: &Line<'a>, struct_name: &str, text: Option<&str>) -> Result<StructValue> {
+        let invalid = || line.error(&format!("Invalid {}", struct_name));
+        match (struct_name, text) {
+            ("DateTime", Some(text)) => Ok(StructValue::DateTime(text.parse().map_err(|_| invalid())?)),
+            ("Timespan", Some(text)) => Ok(StructValue::Timespan(text.parse().map_err(|_| invalid())?)),
+            ("Guid", Some(text)) => Ok(StructValue::Guid(parse_guid(text).ok_or_else(invalid)?)),
+            ("DateTime" | "Timespan" | "Guid", None) => Err(line.error(&format!("Missing {} value", struct_name))),
+            (_, None) => Ok(StructValue::Properties(self.properties(line.indent)?)),
+            (_, Some(_)) => Err(line.error(&format!("{} is written as a block", struct_name)))
+        }
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// An element of a set or map, structs without text have their properties in a block
+    fn item(&mut self, line: &Line<'a>, type_name: &str, text: &str) -> Result<Value> {
+        let text = text.trim();
+        match (PropertyType::from_name(type_name), text.is_empty()) {
+            (PropertyType::Struct, true) => {
+                let value = StructValue::Properties(self.properties(line.indent)?);
+                Ok(Value::Struct { struct_name: String::new(), guid: ZERO_GUID, value })
+            },
+            (PropertyType::Struct, false) => {
+                let value = self.struct_value(line, "Guid", Some(text))?;
+                Ok(Value::Struct { struct_name: String::from("Guid"), guid: ZERO_GUID, value })
+            },
+            _ => bare_value(line, type_name, text)
+        }
+    }
+}
+
+fn required_text<'a>(line: &Line, text: Option<&'a str>) -> Result<&'a str> {
+    text.ok_or_else(|| line.error("Expected '= value'"))
+}
+
+fn struct_guid(line: &Line, spec: &Spec) -> Result<FGuid> {
+    match spec.arg(1) {
+        Some(guid) => parse_guid(guid).ok_or_else(|| line.error("Invalid guid")),
+        None => Ok(ZERO_GUID)
+    }
+}
+
+fn hex_bytes(line: &Line, text: &str) -> Result<Vec<u8>> {
+    let inner = text.trim().strip_prefix('[').and_then(|text| text.strip_suffix(']')).ok_or_else(|| line.error("Expected [bytes]"))?;
+    inner.split_whitespace().map(|byte| u8::from_str_radix(byte, 16).map_err(|_| line.error(&format!("Invalid byte {}", byte)))).collect()
+}
+
+/// Parses a value that isn't a struct or an array, as found in array elements
+fn bare_value(line: &Line, type_name: &str, text: &str) -> Result<Value> {
+    PropertyType::from_name(type_name).parse_text(text, &Tag::Empty).map_err(|err| line.error(&err.to_string()))
+}
+
+/// Reads a quoted string from the start of `text`, returning it with the rest of the text
+pub(crate) fn read_quoted(text: &str) -> Option<(String, &str)> {
+    let mut scanner = Scanner { text, line: Line { number: 0, indent: 0, text } };
+    let s = scanner.quoted().ok()?;
+    Some((s, scanner.text))
+}
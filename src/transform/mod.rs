@@ -0,0 +1,210 @@
+//! Game specific layers (encryption, obfuscation, extra headers) wrapped around the GVAS stream
+
+use crate::error::{Error, Result};
+
+/// A reversible step between the bytes on disk and the GVAS stream.
+/// `decode` runs before the header deserializer, `encode` after the serializer.
+pub trait SaveTransform {
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>>;
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Transforms applied in order when decoding and in reverse when encoding
+#[derive(Default)]
+pub struct Pipeline {
+    transforms: Vec<Box<dyn SaveTransform>>
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Pipeline::default()
+    }
+
+    /// Appends a step that runs after the previous ones when decoding
+    pub fn then<T: SaveTransform + 'static>(mut self, transform: T) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+}
+
+impl SaveTransform for Pipeline {
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut data = data.to_vec();
+        for transform in &self.transforms {
+            data = transform.decode(&data)?;
+        }
+        Ok(data)
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut data = data.to_vec();
+        for transform in self.transforms.iter().rev() {
+            data = transform.encode(&data)?;
+        }
+        Ok(data)
+    }
+}
+
+/// XORs the data with a repeating key
+pub struct Xor {
+    key: Vec<u8>
+}
+
+impl Xor {
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Xor { key: key.into() }
+    }
+}
+
+impl SaveTransform for Xor {
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        if self.key.is_empty() {
+            return Ok(data.to_vec());
+        }
+        Ok(data.iter().zip(self.key.iter().cycle()).map(|(b, k)| b ^ k).collect())
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        self.decode(data)
+    }
+}
+
+/// Drops a fixed prefix when decoding and writes it back when encoding
+pub struct SkipPrefix {
+    prefix: Vec<u8>
+}
+
+impl SkipPrefix {
+    /// Skips `len` bytes, encoding writes zeros in their place
+    pub fn new(len: usize) -> Self {
+        SkipPrefix { prefix: vec![0; len] }
+    }
+
+    pub fn with_bytes(prefix: impl Into<Vec<u8>>) -> Self {
+        SkipPrefix { prefix: prefix.into() }
+    }
+}
+
+impl SaveTransform for SkipPrefix {
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        match data.get(self.prefix.len()..) {
+            Some(rest) => Ok(rest.to_vec()),
+            None => Err(Error::make_data(format!("Expected a prefix of {} bytes, got {}", self.prefix.len(), data.len())))
+        }
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut output = self.prefix.clone();
+        output.extend_from_slice(data);
+        Ok(output)
+    }
+}
+
+#[cfg(feature = "encryption")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AesMode {
+    Ecb,
+    Cbc { iv: [u8; 16] }
+}
+
+#[cfg(feature = "encryption")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AesPadding {
+    /// Data must already be a multiple of the block size
+    None,
+    /// Pads with zeros when encoding, decoding keeps them since the parser stops at the end marker anyway
+    Zero,
+    Pkcs7
+}
+
+/// AES with a 128, 192 or 256 bit key
+#[cfg(feature = "encryption")]
+pub struct Aes {
+    key: Vec<u8>,
+    mode: AesMode,
+    padding: AesPadding
+}
+
+#[cfg(feature = "encryption")]
+impl Aes {
+    pub fn new(key: &[u8], mode: AesMode, padding: AesPadding) -> Result<Self> {
+        match key.len() {
+            16 | 24 | 32 => Ok(Aes { key: key.to_vec(), mode, padding }),
+            len => Err(Error::make_data(format!("Invalid AES key length {}", len)))
+        }
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl SaveTransform for Aes {
+    fn decode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        if !data.len().is_multiple_of(16) {
+            return Err(Error::make_data(format!("Encrypted data of {} bytes isn't a multiple of the block size", data.len())));
+        }
+        match self.key.len() {
+            16 => cipher::decrypt::<aes::Aes128>(&self.key, self.mode, self.padding, data),
+            24 => cipher::decrypt::<aes::Aes192>(&self.key, self.mode, self.padding, data),
+            _ => cipher::decrypt::<aes::Aes256>(&self.key, self.mode, self.padding, data)
+        }
+    }
+
+    fn encode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        if self.padding == AesPadding::None && !data.len().is_multiple_of(16) {
+            return Err(Error::make_data(format!("Data of {} bytes isn't a multiple of the block size", data.len())));
+        }
+        Ok(match self.key.len() {
+            16 => cipher::encrypt::<aes::Aes128>(&self.key, self.mode, self.padding, data),
+            24 => cipher::encrypt::<aes::Aes192>(&self.key, self.mode, self.padding, data),
+            _ => cipher::encrypt::<aes::Aes256>(&self.key, self.mode, self.padding, data)
+        })
+    }
+}
+
+#[cfg(feature = "encryption")]
+mod cipher {
+    use aes::cipher::block_padding::{NoPadding, Padding, Pkcs7, ZeroPadding};
+    use aes::cipher::{BlockCipher, BlockDecrypt, BlockDecryptMut, BlockEncrypt, BlockEncryptMut, KeyInit, KeyIvInit};
+    use aes::cipher::consts::U16;
+
+    use super::{AesMode, AesPadding};
+    use crate::error::{Error, Result};
+
+    pub(super) fn encrypt<C>(key: &[u8], mode: AesMode, padding: AesPadding, data: &[u8]) -> Vec<u8>
+        where C: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit
+    {
+        match padding {
+            AesPadding::None => encrypt_padded::<C, NoPadding>(key, mode, data),
+            AesPadding::Zero => encrypt_padded::<C, ZeroPadding>(key, mode, data),
+            AesPadding::Pkcs7 => encrypt_padded::<C, Pkcs7>(key, mode, data)
+        }
+    }
+
+    fn encrypt_padded<C, P>(key: &[u8], mode: AesMode, data: &[u8]) -> Vec<u8>
+        where C: BlockCipher<BlockSize = U16> + BlockEncrypt + KeyInit, P: Padding<U16>
+    {
+        // the key length was checked in Aes::new
+        match mode {
+            AesMode::Ecb => ecb::Encryptor::<C>::new_from_slice(key).unwrap().encrypt_padded_vec_mut::<P>(data),
+            AesMode::Cbc { iv } => cbc::Encryptor::<C>::new_from_slices(key, &iv).unwrap().encrypt_padded_vec_mut::<P>(data)
+        }
+    }
+
+    pub(super) fn decrypt<C>(key: &[u8], mode: AesMode, padding: AesPadding, data: &[u8]) -> Result<Vec<u8>>
+        where C: BlockCipher<BlockSize = U16> + BlockDecrypt + KeyInit
+    {
+        match padding {
+            AesPadding::Pkcs7 => decrypt_padded::<C, Pkcs7>(key, mode, data).map_err(|_| Error::make_data(String::from("Invalid PKCS#7 padding"))),
+            // zero padding is kept, see AesPadding::Zero
+            AesPadding::None | AesPadding::Zero => Ok(decrypt_padded::<C, NoPadding>(key, mode, data).unwrap())
+        }
+    }
+
+    fn decrypt_padded<C, P>(key: &[u8], mode: AesMode, data: &[u8]) -> std::result::Result<Vec<u8>, aes::cipher::block_padding::UnpadError>
+        where C: BlockCipher<BlockSize = U16> + BlockDecrypt + KeyInit, P: Padding<U16>
+    {
+        match mode {
+            AesMode::Ecb => ecb::Decryptor::<C>::new_from_slice(key).unwrap().decrypt_padded_vec_mut::<P>(data),
+            AesMode::Cbc { iv } => cbc::Decryptor::<C>::new_from_slices(key, &iv).unwrap().decrypt_padded_vec_mut::<P>(data)
+        }
+    }
+}
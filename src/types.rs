@@ -1,8 +1,15 @@
 use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
 
-use serde::{Deserialize, Serialize};
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+use crate::custom_version::{self, CustomVersionInfo};
+use crate::engine_version::EngineVersion;
+use crate::hint::HintedSeed;
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct FEngineVersion {
     pub major: u16,
     pub minor: u16,
@@ -11,7 +18,7 @@ pub struct FEngineVersion {
     pub branch: String
 }
 
-#[derive(Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct FGuid {
     pub a: u32,
     pub b: u32,
@@ -20,7 +27,7 @@ pub struct FGuid {
 }
 
 impl FGuid {
-    pub fn new(a: u32, b: u32, c: u32, d: u32) -> Self {
+    pub const fn new(a: u32, b: u32, c: u32, d: u32) -> Self {
         FGuid { a, b, c, d }
     }
 }
@@ -31,19 +38,372 @@ impl Debug for FGuid {
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct FCustomVersion {
     pub key: FGuid,
     pub version: i32
 }
 
-#[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
+impl FCustomVersion {
+    pub fn info(&self) -> Option<&'static CustomVersionInfo> {
+        custom_version::find_by_guid(&self.key)
+    }
+
+    pub fn name(&self) -> Option<&'static str> {
+        self.info().map(|info| info.name)
+    }
+
+    pub fn version_name(&self) -> Option<&'static str> {
+        self.info().and_then(|info| info.version_name(self.version))
+    }
+}
+
+impl Debug for FCustomVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("FCustomVersion");
+        match self.name() {
+            Some(name) => s.field("name", &name),
+            None => s.field("key", &self.key)
+        };
+        s.field("version", &self.version);
+        if let Some(version_name) = self.version_name() {
+            s.field("version_name", &version_name);
+        }
+        s.finish()
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct GvasHeader {
     pub file_type_tag: i32,
     pub save_game_file_version: i32,
     pub package_file_ue4_version: i32,
+    /// Only present when `save_game_file_version` is 3 or above (UE5 saves)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_file_ue5_version: Option<i32>,
     pub engine_version: FEngineVersion,
     pub custom_version_format: i32,
     pub custom_versions: Vec<FCustomVersion>,
     pub save_game_class_name: String
-}
\ No newline at end of file
+}
+
+const GVAS_HEADER_FIELDS: &[&str] = &["file_type_tag", "save_game_file_version", "package_file_ue4_version", "package_file_ue5_version",
+    "engine_version", "custom_version_format", "custom_versions", "save_game_class_name"];
+
+#[derive(Deserialize)]
+struct GvasHeaderFields {
+    file_type_tag: i32,
+    save_game_file_version: i32,
+    package_file_ue4_version: i32,
+    #[serde(default)]
+    package_file_ue5_version: Option<i32>,
+    engine_version: FEngineVersion,
+    custom_version_format: i32,
+    custom_versions: Vec<FCustomVersion>,
+    save_game_class_name: String
+}
+
+struct GvasHeaderVisitor;
+
+impl<'de> Visitor<'de> for GvasHeaderVisitor {
+    type Value = GvasHeader;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a GVAS header")
+    }
+
+    // the binary layout has no field names, so the UE5 package version is read depending on the save game file version.
+    // Older saves read it as a unit, which takes no bytes, so the fields after it keep their place in error paths.
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error> where
+        A: SeqAccess<'de> {
+        let file_type_tag = next_header_field(&mut seq, 0)?;
+        let save_game_file_version = next_header_field(&mut seq, 1)?;
+        let package_file_ue4_version = next_header_field(&mut seq, 2)?;
+        let package_file_ue5_version = if save_game_file_version >= 3 {
+            Some(next_header_field(&mut seq, 3)?)
+        } else {
+            next_header_field::<_, ()>(&mut seq, 3)?;
+            None
+        };
+        Ok(GvasHeader {
+            file_type_tag,
+            save_game_file_version,
+            package_file_ue4_version,
+            package_file_ue5_version,
+            engine_version: next_header_field(&mut seq, 4)?,
+            custom_version_format: next_header_field(&mut seq, 5)?,
+            custom_versions: next_header_field(&mut seq, 6)?,
+            save_game_class_name: next_header_field(&mut seq, 7)?
+        })
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error> where
+        A: MapAccess<'de> {
+        let fields = GvasHeaderFields::deserialize(de::value::MapAccessDeserializer::new(map))?;
+        Ok(GvasHeader {
+            file_type_tag: fields.file_type_tag,
+            save_game_file_version: fields.save_game_file_version,
+            package_file_ue4_version: fields.package_file_ue4_version,
+            package_file_ue5_version: fields.package_file_ue5_version,
+            engine_version: fields.engine_version,
+            custom_version_format: fields.custom_version_format,
+            custom_versions: fields.custom_versions,
+            save_game_class_name: fields.save_game_class_name
+        })
+    }
+}
+
+fn next_header_field<'de, A, T>(seq: &mut A, index: usize) -> Result<T, A::Error> where
+    A: SeqAccess<'de>,
+    T: Deserialize<'de> {
+    seq.next_element()?.ok_or_else(|| de::Error::missing_field(GVAS_HEADER_FIELDS[index]))
+}
+
+impl<'de> Deserialize<'de> for GvasHeader {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        deserializer.deserialize_struct("GvasHeader", GVAS_HEADER_FIELDS, GvasHeaderVisitor)
+    }
+}
+
+impl GvasHeader {
+    /// Builds a header the way the given engine release writes it, with the base `SaveGame` class as save game class.
+    pub fn for_engine(engine: EngineVersion) -> Self {
+        GvasHeader {
+            file_type_tag: 0x53415647, // "GVAS"
+            save_game_file_version: engine.save_game_file_version(),
+            package_file_ue4_version: engine.package_file_ue4_version(),
+            package_file_ue5_version: engine.package_file_ue5_version(),
+            engine_version: engine.engine_version(),
+            custom_version_format: 3,
+            custom_versions: engine.custom_versions(),
+            save_game_class_name: String::from("/Script/Engine.SaveGame")
+        }
+    }
+
+    /// Looks up a custom version by its engine name, e.g. `"FUE5ReleaseStreamObjectVersion"`.
+    pub fn custom_version(&self, name: &str) -> Option<&FCustomVersion> {
+        custom_version::find(name).and_then(|info| self.custom_version_by_guid(&info.guid))
+    }
+
+    pub fn custom_version_by_guid(&self, guid: &FGuid) -> Option<&FCustomVersion> {
+        self.custom_versions.iter().find(|version| &version.key == guid)
+    }
+}
+
+/// Implements serde for a wrapper as a newtype struct named after a hint, see [`crate::hint`]
+macro_rules! hinted_newtype {
+    ($wrapper:ident, $inner:ty, $hint:literal) => {
+        impl Serialize for $wrapper {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where
+                S: Serializer {
+                serializer.serialize_newtype_struct($hint, &self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $wrapper {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+                D: Deserializer<'de> {
+                HintedSeed::<$inner>::new($hint).deserialize(deserializer).map($wrapper)
+            }
+        }
+    };
+}
+
+/// A string stored as a `NameProperty`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FName(pub String);
+
+hinted_newtype!(FName, String, "$gvas:property=NameProperty");
+
+/// The path of an object stored as an `ObjectProperty`, e.g. `/Game/Maps/Level.Level:PersistentLevel.Actor_0`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ObjectPath(pub String);
+
+hinted_newtype!(ObjectPath, String, "$gvas:property=ObjectProperty");
+
+/// A `DateTime` struct, in ticks of 100 nanoseconds since the start of year 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FDateTime(pub u64);
+
+hinted_newtype!(FDateTime, u64, "$gvas:struct_name=DateTime");
+
+/// A `Timespan` struct, in ticks of 100 nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FTimespan(pub i64);
+
+hinted_newtype!(FTimespan, i64, "$gvas:struct_name=Timespan");
+
+/// The bytes of an `ArrayProperty` of `ByteProperty`, written in one go instead of element by element
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ByteArray(pub Vec<u8>);
+
+const BYTE_ARRAY_HINT: &str = "$gvas:property=ByteProperty";
+
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Serialize for Bytes<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where
+        S: Serializer {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+impl Serialize for ByteArray {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where
+        S: Serializer {
+        serializer.serialize_newtype_struct(BYTE_ARRAY_HINT, &Bytes(&self.0))
+    }
+}
+
+struct ByteArrayVisitor;
+
+impl<'de> Visitor<'de> for ByteArrayVisitor {
+    type Value = ByteArray;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where
+        D: Deserializer<'de> {
+        deserializer.deserialize_byte_buf(self)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> where
+        E: de::Error {
+        Ok(ByteArray(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> where
+        E: de::Error {
+        Ok(ByteArray(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error> where
+        A: SeqAccess<'de> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(ByteArray(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteArray {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(BYTE_ARRAY_HINT, ByteArrayVisitor)
+    }
+}
+
+/// A `SoftObjectProperty`, laid out as in UE4: the asset path and a sub path within the asset
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SoftObjectPath {
+    pub asset_path: String,
+    pub sub_path: String
+}
+
+const SOFT_OBJECT_HINT: &str = "$gvas:property=SoftObjectProperty";
+
+impl Serialize for SoftObjectPath {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where
+        S: Serializer {
+        serializer.serialize_newtype_struct(SOFT_OBJECT_HINT, &(&self.asset_path, &self.sub_path))
+    }
+}
+
+impl<'de> Deserialize<'de> for SoftObjectPath {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        let (asset_path, sub_path) = HintedSeed::<(String, String)>::new(SOFT_OBJECT_HINT).deserialize(deserializer)?;
+        Ok(SoftObjectPath { asset_path, sub_path })
+    }
+}
+
+/// A unit-only enum stored as a `ByteProperty` tagged with the enum, holding the name of the value.
+/// The enum name in the tag is the serde name of `E`, so the enum may need `#[serde(rename = "...")]`.
+/// Bytes holding the index of the value, with `None` as enum name, can be read as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EnumByte<E>(pub E);
+
+const ENUM_BYTE_HINT: &str = "$gvas:property=ByteProperty;label";
+
+impl<E: Serialize> Serialize for EnumByte<E> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where
+        S: Serializer {
+        serializer.serialize_newtype_struct(ENUM_BYTE_HINT, &self.0)
+    }
+}
+
+impl<'de, E: Deserialize<'de>> Deserialize<'de> for EnumByte<E> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        HintedSeed::<E>::new(ENUM_BYTE_HINT).deserialize(deserializer).map(EnumByte)
+    }
+}
+
+/// Names the UE struct a [`Struct`] is written as, implemented by [`struct_name!`](crate::struct_name)
+pub trait StructName {
+    /// The hint the value is wrapped in, e.g. `$gvas:struct_name=Vector`
+    const HINT: &'static str;
+}
+
+/// Declares a marker type naming a UE struct, for use with [`Struct`]:
+/// `struct_name!(pub ItemName = "InventoryItem");`
+#[macro_export]
+macro_rules! struct_name {
+    ($vis:vis $marker:ident = $name:literal) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        $vis struct $marker;
+
+        impl $crate::types::StructName for $marker {
+            const HINT: &'static str = concat!("$gvas:struct_name=", $name);
+        }
+    };
+}
+
+/// A struct written and checked as the UE struct `N` names, whatever the serde name of `T` is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Struct<N, T> {
+    pub value: T,
+    name: PhantomData<N>
+}
+
+impl<N, T> Struct<N, T> {
+    pub fn new(value: T) -> Self {
+        Struct { value, name: PhantomData }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<N, T> Deref for Struct<N, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<N, T> DerefMut for Struct<N, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<N: StructName, T: Serialize> Serialize for Struct<N, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where
+        S: Serializer {
+        serializer.serialize_newtype_struct(N::HINT, &self.value)
+    }
+}
+
+impl<'de, N: StructName, T: Deserialize<'de>> Deserialize<'de> for Struct<N, T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where
+        D: Deserializer<'de> {
+        HintedSeed::<T>::new(N::HINT).deserialize(deserializer).map(Struct::new)
+    }
+}
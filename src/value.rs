@@ -0,0 +1,709 @@
+use std::io::{Cursor, Read, Write};
+use std::mem;
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use serde::{Deserialize, Serialize};
+
+use crate::compression::{self, Container};
+use crate::de::{check_limit, Deserializer, DeserializerOptions};
+use crate::error::{Diagnostic, Error, ErrorCode, PathSegment, Result};
+use crate::fstring::write_fstring;
+use crate::header;
+use crate::patch::{ArrayTag, Element};
+use crate::property::{self, PropertyType, Tag};
+use crate::structs::{Item, StructRegistry};
+use crate::types::{FGuid, GvasHeader};
+
+/// A whole save file, header and body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Save {
+    pub header: GvasHeader,
+    pub properties: Vec<Property>,
+    /// How the file was compressed, [`Save::to_vec`] compresses it the same way
+    #[serde(default, skip_serializing_if = "Container::is_raw")]
+    pub container: Container
+}
+
+/// A single tagged property, e.g. a field of a save game object or of a struct
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Property {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub array_index: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guid: Option<FGuid>,
+    #[serde(flatten)]
+    pub value: Value
+}
+
+fn is_zero(value: &i32) -> bool {
+    *value == 0
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum Value {
+    #[serde(rename = "BoolProperty")]
+    Bool(bool),
+    #[serde(rename = "Int8Property")]
+    Int8(i8),
+    #[serde(rename = "Int16Property")]
+    Int16(i16),
+    #[serde(rename = "UInt16Property")]
+    UInt16(u16),
+    #[serde(rename = "IntProperty")]
+    Int(i32),
+    #[serde(rename = "UInt32Property")]
+    UInt32(u32),
+    #[serde(rename = "Int64Property")]
+    Int64(i64),
+    #[serde(rename = "UInt64Property")]
+    UInt64(u64),
+    #[serde(rename = "FloatProperty")]
+    Float(#[serde(with = "float")] f32),
+    #[serde(rename = "DoubleProperty")]
+    Double(#[serde(with = "float")] f64),
+    #[serde(rename = "ByteProperty")]
+    Byte { enum_name: String, value: ByteValue },
+    #[serde(rename = "EnumProperty")]
+    Enum { enum_name: String, value: String },
+    #[serde(rename = "StrProperty")]
+    Str(String),
+    #[serde(rename = "NameProperty")]
+    Name(String),
+    #[serde(rename = "ObjectProperty")]
+    Object(String),
+    /// Asset path and sub path
+    #[serde(rename = "SoftObjectProperty")]
+    SoftObject([String; 2]),
+    #[serde(rename = "StructProperty")]
+    Struct { struct_name: String, guid: FGuid, value: StructValue },
+    #[serde(rename = "ArrayProperty")]
+    Array { element_type: String, value: ArrayValue },
+    /// Elements are stored like those of [`ArrayValue::Values`], see [`Value::Map`] for structs.
+    /// `removed` holds the elements a set that was saved as a delta removes, which is rare.
+    #[serde(rename = "SetProperty")]
+    Set {
+        element_type: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        removed: Vec<Value>,
+        elements: Vec<Value>
+    },
+    /// Struct keys and values don't name their struct. They are read as properties with an empty
+    /// struct name, or as a `Guid` when they aren't properties. `removed` holds removed keys.
+    #[serde(rename = "MapProperty")]
+    Map {
+        key_type: String,
+        value_type: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        removed: Vec<Value>,
+        entries: Vec<(Value, Value)>
+    },
+    /// A property that wasn't decoded, `tag` holds the type specific part of its tag
+    Unknown { type_name: String, tag: Vec<u8>, data: Vec<u8> }
+}
+
+/// `ByteProperty` holds a plain byte unless it is tagged with an enum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ByteValue {
+    Byte(u8),
+    Label(String)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum StructValue {
+    DateTime(u64),
+    Timespan(i64),
+    Guid(FGuid),
+    Properties(Vec<Property>)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ArrayValue {
+    Bytes(Vec<u8>),
+    /// Elements of any other type, stored without tags. Enum elements (and byte elements holding
+    /// enum labels) have an enum name of `None`.
+    Values(Vec<Value>),
+    /// Preceded by a tag of their own, whose `name`, `array_index` and `property_guid` are kept
+    Structs {
+        name: String,
+        #[serde(default, skip_serializing_if = "is_zero")]
+        array_index: i32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        property_guid: Option<FGuid>,
+        struct_name: String,
+        guid: FGuid,
+        elements: Vec<StructValue>
+    }
+}
+
+/// Floats that JSON can't hold are written as strings, `NaN` keeps its bits when they aren't the usual ones
+mod float {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(super) trait Float: Copy + Serialize + for<'de> Deserialize<'de> {
+        const NAN: Self;
+        const INFINITY: Self;
+        const NEG_INFINITY: Self;
+        fn is_finite(self) -> bool;
+        fn is_nan(self) -> bool;
+        fn bits(self) -> u64;
+        fn from_bits(bits: u64) -> Option<Self>;
+    }
+
+    macro_rules! float {
+        ($float:ident, $bits:ident) => {
+            impl Float for $float {
+                const NAN: Self = $float::NAN;
+                const INFINITY: Self = $float::INFINITY;
+                const NEG_INFINITY: Self = $float::NEG_INFINITY;
+
+                fn is_finite(self) -> bool {
+                    $float::is_finite(self)
+                }
+
+                fn is_nan(self) -> bool {
+                    $float::is_nan(self)
+                }
+
+                fn bits(self) -> u64 {
+                    self.to_bits() as u64
+                }
+
+                fn from_bits(bits: u64) -> Option<Self> {
+                    Some($float::from_bits($bits::try_from(bits).ok()?))
+                }
+            }
+        };
+    }
+
+    float!(f32, u32);
+    float!(f64, u64);
+
+    pub(super) fn serialize<F: Float, S: Serializer>(value: &F, serializer: S) -> Result<S::Ok, S::Error> {
+        match *value {
+            value if value.is_finite() => value.serialize(serializer),
+            value if value.is_nan() && value.bits() == F::NAN.bits() => serializer.serialize_str("NaN"),
+            value if value.is_nan() => serializer.serialize_str(&format!("NaN:{:X}", value.bits())),
+            value if value.bits() == F::INFINITY.bits() => serializer.serialize_str("inf"),
+            _ => serializer.serialize_str("-inf")
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Text<F> {
+        Number(F),
+        Text(String)
+    }
+
+    pub(super) fn deserialize<'de, F: Float, D: Deserializer<'de>>(deserializer: D) -> Result<F, D::Error> {
+        match Text::<F>::deserialize(deserializer)? {
+            Text::Number(value) => Ok(value),
+            Text::Text(text) => match text.as_str() {
+                "NaN" => Ok(F::NAN),
+                "inf" => Ok(F::INFINITY),
+                "-inf" => Ok(F::NEG_INFINITY),
+                text => text.strip_prefix("NaN:")
+                    .and_then(|bits| u64::from_str_radix(bits, 16).ok())
+                    .and_then(F::from_bits)
+                    .filter(|value| value.is_nan())
+                    .ok_or_else(|| D::Error::custom(format!("Invalid float {}", text)))
+            }
+        }
+    }
+}
+
+impl Value {
+    pub fn type_name(&self) -> &str {
+        match self {
+            Value::Bool(_) => "BoolProperty",
+            Value::Int8(_) => "Int8Property",
+            Value::Int16(_) => "Int16Property",
+            Value::UInt16(_) => "UInt16Property",
+            Value::Int(_) => "IntProperty",
+            Value::UInt32(_) => "UInt32Property",
+            Value::Int64(_) => "Int64Property",
+            Value::UInt64(_) => "UInt64Property",
+            Value::Float(_) => "FloatProperty",
+            Value::Double(_) => "DoubleProperty",
+            Value::Byte { .. } => "ByteProperty",
+            Value::Enum { .. } => "EnumProperty",
+            Value::Str(_) => "StrProperty",
+            Value::Name(_) => "NameProperty",
+            Value::Object(_) => "ObjectProperty",
+            Value::SoftObject(_) => "SoftObjectProperty",
+            Value::Struct { .. } => "StructProperty",
+            Value::Array { .. } => "ArrayProperty",
+            Value::Set { .. } => "SetProperty",
+            Value::Map { .. } => "MapProperty",
+            Value::Unknown { type_name, .. } => type_name
+        }
+    }
+}
+
+/// Structs that are stored as plain values instead of properties, see [`crate::structs`] too
+pub(crate) fn is_native(struct_name: &str, structs: &StructRegistry) -> bool {
+    matches!(struct_name, "DateTime" | "Timespan" | "Guid") || structs.is_registered(struct_name)
+}
+
+impl Save {
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
+        Save::from_bytes_with_options(data, DeserializerOptions::default())
+    }
+
+    /// Reads a whole save file, decompressing it first if it is stored in a [`Container`]
+    pub fn from_bytes_with_options(data: Vec<u8>, options: DeserializerOptions) -> Result<Self> {
+        Ok(Save::from_bytes_with_diagnostics(data, options)?.0)
+    }
+
+    pub fn from_bytes_with_diagnostics(data: Vec<u8>, options: DeserializerOptions) -> Result<(Self, Vec<Diagnostic>)> {
+        let (container, data) = compression::decompress_with_options(data, &options)?;
+        let mut cursor = Cursor::new(data);
+        let header = header::de::from_bytes_with_options(&mut cursor, options.clone())?;
+        let (properties, diagnostics) = from_bytes_with_diagnostics(&mut cursor, options)?;
+        Ok((Save { header, properties, container }, diagnostics))
+    }
+
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        self.to_vec_with_structs(&StructRegistry::default())
+    }
+
+    /// Writes the whole save, the structs in `structs` with their codecs
+    pub fn to_vec_with_structs(&self, structs: &StructRegistry) -> Result<Vec<u8>> {
+        let mut output = header::ser::to_vec(&self.header)?;
+        to_writer_with_structs(&mut output, &self.properties, structs)?;
+        match self.container {
+            Container::Raw => Ok(output),
+            container => container.compress(&output)
+        }
+    }
+}
+
+pub fn from_bytes(input: &mut Cursor<Vec<u8>>) -> Result<Vec<Property>> {
+    Ok(from_bytes_with_diagnostics(input, DeserializerOptions::default())?.0)
+}
+
+/// Reads properties up to the terminating `None`. With [`DeserializerOptions::recover`] set,
+/// values that can't be decoded are kept as [`Value::Unknown`] and reported as diagnostics.
+pub fn from_bytes_with_diagnostics(input: &mut Cursor<Vec<u8>>, options: DeserializerOptions) -> Result<(Vec<Property>, Vec<Diagnostic>)> {
+    let start = input.position();
+    options.check_total_size(input).map_err(|e| e.at(start, &[]))?;
+    let mut deserializer = Deserializer::with_options(input, options);
+    let properties = deserializer.read_properties()?;
+    Ok((properties, deserializer.into_diagnostics()))
+}
+
+impl<'de> Deserializer<'de> {
+    pub(crate) fn read_properties(&mut self) -> Result<Vec<Property>> {
+        let mut properties = Vec::new();
+        loop {
+            let start = self.input.position();
+            let name = self.parse_string().map_err(|e| e.at(start, &self.path))?;
+            if name == "None" {
+                return Ok(properties);
+            }
+            check_limit("struct properties", self.options.max_struct_properties as u64, properties.len() as u64 + 1).map_err(|e| e.at(start, &self.path))?;
+
+            self.path.push(PathSegment::Field(name.clone()));
+            let property = self.read_property(start).map_err(|e| e.at(start, &self.path));
+            self.path.pop();
+            let (array_index, guid, value) = property?;
+            properties.push(Property { name, array_index, guid, value });
+        }
+    }
+
+    pub(crate) fn read_property(&mut self, start: u64) -> Result<(i32, Option<FGuid>, Value)> {
+        let type_name = self.parse_string()?;
+        let size = self.input.read_i32::<LittleEndian>()?;
+        let array_index = self.input.read_i32::<LittleEndian>()?;
+
+        let tag_start = self.input.position();
+        let tag = self.read_tag(&type_name)?;
+        let tag_end = self.input.position();
+
+        let guid = match self.input.read_u8()? {
+            0 => None,
+            _ => Some(self.parse_guid()?)
+        };
+
+        let value_start = self.input.position();
+        let value = match self.sized(size as i64, |de| de.read_value(&type_name, tag, size)) {
+            Ok(value) => value,
+            Err(err) => {
+                let end = value_start + size.max(0) as u64;
+                if !self.options.recover || size < 0 || end > self.input.get_ref().len() as u64 {
+                    return Err(err);
+                }
+                self.diagnostics.push(err.at(start, &self.path).into_diagnostic());
+                self.input.set_position(end);
+
+                let bytes = self.input.get_ref();
+                Value::Unknown {
+                    tag: bytes[tag_start as usize..tag_end as usize].to_vec(),
+                    data: bytes[value_start as usize..end as usize].to_vec(),
+                    type_name
+                }
+            }
+        };
+        Ok((array_index, guid, value))
+    }
+
+    pub(crate) fn read_tag(&mut self, type_name: &str) -> Result<Tag> {
+        PropertyType::from_name(type_name).read_tag(self)
+    }
+
+    pub(crate) fn read_value(&mut self, type_name: &str, tag: Tag, size: i32) -> Result<Value> {
+        match (PropertyType::from_name(type_name), &tag) {
+            (PropertyType::Struct, Tag::Struct(struct_name, guid)) => {
+                let value = self.read_struct_value(struct_name)?;
+                Ok(Value::Struct { struct_name: struct_name.clone(), guid: *guid, value })
+            },
+            (PropertyType::Array, Tag::Array(element_type)) => {
+                let value = self.nested(|de| de.read_array(element_type, size))?;
+                Ok(Value::Array { element_type: element_type.clone(), value })
+            },
+            (PropertyType::Set, Tag::Set(element_type)) => self.nested(|de| {
+                let end = u64::try_from(size).ok().map(|size| de.input.position() + size);
+                let struct_name = de.item_struct(de.property_name(), element_type, Item::Element);
+                let removed = de.read_collection("set", None, &[], |de, _| de.read_item(element_type, struct_name.as_deref(), None))?;
+                let elements = de.read_collection("set", end, &[element_type], |de, size| de.read_item(element_type, struct_name.as_deref(), size))?;
+                Ok(Value::Set { element_type: element_type.clone(), removed, elements })
+            }),
+            (PropertyType::Map, Tag::Map(key_type, value_type)) => self.nested(|de| {
+                let end = u64::try_from(size).ok().map(|size| de.input.position() + size);
+                let key_struct = de.item_struct(de.property_name(), key_type, Item::Key);
+                let value_struct = de.item_struct(de.property_name(), value_type, Item::Value);
+                let removed = de.read_collection("map", None, &[], |de, _| de.read_item(key_type, key_struct.as_deref(), None))?;
+                let entries = de.read_collection("map", end, &[key_type, value_type], |de, size| {
+                    Ok((de.read_item(key_type, key_struct.as_deref(), size)?, de.read_item(value_type, value_struct.as_deref(), size)?))
+                })?;
+                Ok(Value::Map { key_type: key_type.clone(), value_type: value_type.clone(), removed, entries })
+            }),
+            (property_type, tag) => property_type.read_value(self, tag)
+        }
+    }
+
+    /// Reads a value that isn't preceded by a tag, like an array element
+    pub(crate) fn read_bare(&mut self, type_name: &str) -> Result<Value> {
+        PropertyType::from_name(type_name).read_element(self)
+    }
+
+    pub(crate) fn read_struct_value(&mut self, struct_name: &str) -> Result<StructValue> {
+        if let Some(codec) = self.options.structs.codec(struct_name) {
+            return Ok(StructValue::Properties(codec.decode(self.input)?));
+        }
+        Ok(match struct_name {
+            "DateTime" => StructValue::DateTime(self.input.read_u64::<LittleEndian>()?),
+            "Timespan" => StructValue::Timespan(self.input.read_i64::<LittleEndian>()?),
+            "Guid" => StructValue::Guid(self.parse_guid()?),
+            _ => StructValue::Properties(self.nested(|de| de.read_properties())?)
+        })
+    }
+
+    fn read_array(&mut self, element_type: &str, size: i32) -> Result<ArrayValue> {
+        let elements = self.input.read_i32::<LittleEndian>()?;
+        if elements < 0 {
+            return Err(Error::make_data(format!("Invalid array length {}", elements)));
+        }
+        check_limit("array length", self.options.max_array_length as u64, elements as u64)?;
+
+        match self.array_element(element_type, size as u64, elements as usize)? {
+            Element::Label => {
+                Ok(ArrayValue::Values(self.read_elements(elements, |de| Ok(Value::Byte { enum_name: String::from("None"), value: ByteValue::Label(de.parse_string()?) }))?))
+            },
+            Element::Byte => {
+                if elements as u64 > self.remaining() {
+                    return Err(Error::new(ErrorCode::UnexpectedEof));
+                }
+                let mut bytes = vec![0u8; elements as usize];
+                self.input.read_exact(&mut bytes)?;
+                Ok(ArrayValue::Bytes(bytes))
+            },
+            Element::Struct(ArrayTag { name, size, array_index, struct_name, guid, property_guid, .. }) => {
+                let elements = self.sized(size as i64, |de| de.read_elements(elements, |de| de.read_struct_value(&struct_name)))?;
+                Ok(ArrayValue::Structs { name, array_index, property_guid, struct_name, guid, elements })
+            },
+            _ => Ok(ArrayValue::Values(self.read_elements(elements, |de| de.read_bare(element_type))?))
+        }
+    }
+
+    /// Reads the elements of a set or map, which end at `end` when that is known. Struct items among
+    /// `types` are read knowing their size when the entries can only be laid out one way, see [`item_size`].
+    fn read_collection<T, F>(&mut self, kind: &str, end: Option<u64>, types: &[&str], mut read: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&mut Self, Option<u64>) -> Result<T> {
+        let elements = self.input.read_i32::<LittleEndian>()?;
+        if elements < 0 {
+            return Err(Error::make_data(format!("Invalid {} length {}", kind, elements)));
+        }
+        check_limit("map length", self.options.max_map_length as u64, elements as u64)?;
+        let size = end.and_then(|end| item_size(end.checked_sub(self.input.position())?, elements as usize, types));
+        self.read_elements(elements, |de| read(de, size))
+    }
+
+    /// Name of the property being read, empty inside an array
+    pub(crate) fn property_name(&self) -> &str {
+        match self.path.last() {
+            Some(PathSegment::Field(name)) => name,
+            _ => ""
+        }
+    }
+
+    /// The struct registered for the struct elements of the set or map `property`, see [`StructRegistry::register_item`]
+    pub(crate) fn item_struct(&self, property: &str, type_name: &str, item: Item) -> Option<String> {
+        match PropertyType::from_name(type_name) {
+            PropertyType::Struct => self.options.structs.item_struct(property, item).map(String::from),
+            _ => None
+        }
+    }
+
+    /// Reads an element of a set or map, unlike array elements these can be structs of any kind.
+    /// Struct elements are read as `struct_name` when their struct is known, `size` is how many
+    /// bytes a struct element takes up when the size of the set or map tells.
+    pub(crate) fn read_item(&mut self, type_name: &str, struct_name: Option<&str>, size: Option<u64>) -> Result<Value> {
+        match (PropertyType::from_name(type_name), struct_name) {
+            (PropertyType::Struct, Some(struct_name)) => {
+                let value = self.read_struct_value(struct_name)?;
+                Ok(Value::Struct { struct_name: String::from(struct_name), guid: FGuid::new(0, 0, 0, 0), value })
+            },
+            (PropertyType::Struct, None) => self.read_struct_item(size),
+            (property_type, _) => property_type.read_element(self)
+        }
+    }
+
+    /// Nothing tells which struct an element is, so it is tried as properties and then as a guid
+    /// when it takes up exactly the 16 bytes of one
+    fn read_struct_item(&mut self, size: Option<u64>) -> Result<Value> {
+        let start = self.input.position();
+        let diagnostics = self.diagnostics.len();
+        let recover = mem::replace(&mut self.options.recover, false);
+        let properties = self.nested(|de| de.read_properties());
+        self.options.recover = recover;
+
+        let (struct_name, value) = match properties {
+            Ok(properties) => (String::new(), StructValue::Properties(properties)),
+            Err(_) if size == Some(16) => {
+                self.input.set_position(start);
+                self.diagnostics.truncate(diagnostics);
+                (String::from("Guid"), StructValue::Guid(self.parse_guid()?))
+            },
+            Err(_) => {
+                self.input.set_position(start);
+                self.diagnostics.truncate(diagnostics);
+                return Err(self.unsupported_item());
+            }
+        };
+        Ok(Value::Struct { struct_name, guid: FGuid::new(0, 0, 0, 0), value })
+    }
+
+    /// Error for a struct item that is neither properties nor a guid, named after its set or map
+    /// since that is what a struct gets registered for
+    pub(crate) fn unsupported_item(&self) -> Error {
+        let property = self.path.iter().rev().find_map(|segment| match segment {
+            PathSegment::Field(name) => Some(name.as_str()),
+            _ => None
+        });
+        Error::new(ErrorCode::UnsupportedStruct(Box::from(property.unwrap_or_default())))
+    }
+
+    fn read_elements<T, F>(&mut self, elements: i32, mut read: F) -> Result<Vec<T>>
+    where
+        F: FnMut(&mut Self) -> Result<T> {
+        let mut values = Vec::new();
+        for index in 0..elements as usize {
+            let start = self.input.position();
+            self.path.push(PathSegment::Index(index));
+            let value = read(self).map_err(|e| e.at(start, &self.path));
+            self.path.pop();
+            values.push(value?);
+        }
+        Ok(values)
+    }
+}
+
+/// How many bytes each struct item takes up when `remaining` bytes hold `entries` entries of
+/// `types`, if only structs and items of a fixed size are among them
+pub(crate) fn item_size(remaining: u64, entries: usize, types: &[&str]) -> Option<u64> {
+    if entries == 0 || !remaining.is_multiple_of(entries as u64) {
+        return None;
+    }
+    let (mut size, mut structs) = (remaining / entries as u64, 0);
+    for type_name in types {
+        match PropertyType::from_name(type_name) {
+            PropertyType::Struct => structs += 1,
+            PropertyType::Bool | PropertyType::Int8 => size = size.checked_sub(1)?,
+            PropertyType::Int16 | PropertyType::UInt16 => size = size.checked_sub(2)?,
+            PropertyType::Int | PropertyType::UInt32 | PropertyType::Float => size = size.checked_sub(4)?,
+            PropertyType::Int64 | PropertyType::UInt64 | PropertyType::Double => size = size.checked_sub(8)?,
+            _ => return None
+        }
+    }
+    (structs > 0 && size.is_multiple_of(structs)).then(|| size / structs)
+}
+
+pub fn to_vec(properties: &[Property]) -> Result<Vec<u8>> {
+    to_vec_with_structs(properties, &StructRegistry::default())
+}
+
+/// Like [`to_vec`], writing the structs in `structs` with their codecs
+pub fn to_vec_with_structs(properties: &[Property], structs: &StructRegistry) -> Result<Vec<u8>> {
+    let mut output = Vec::new();
+    to_writer_with_structs(&mut output, properties, structs)?;
+    Ok(output)
+}
+
+/// Writes the properties followed by the terminating `None`, the reverse of [`from_bytes`]
+pub fn to_writer<W: Write>(writer: W, properties: &[Property]) -> Result<()> {
+    to_writer_with_structs(writer, properties, &StructRegistry::default())
+}
+
+pub fn to_writer_with_structs<W: Write>(mut writer: W, properties: &[Property], structs: &StructRegistry) -> Result<()> {
+    let mut output = Vec::new();
+    write_properties(&mut output, properties, structs)?;
+    output.write_i32::<LittleEndian>(0)?;
+    writer.write_all(&output)?;
+    Ok(())
+}
+
+fn write_properties(output: &mut Vec<u8>, properties: &[Property], structs: &StructRegistry) -> Result<()> {
+    for property in properties {
+        write_property(output, property, structs)?;
+    }
+    write_fstring(output, "None")
+}
+
+fn write_property(output: &mut Vec<u8>, property: &Property, structs: &StructRegistry) -> Result<()> {
+    // sizes come first, so the value is written separately
+    let mut value = Vec::new();
+    write_value(&mut value, &property.value, structs)?;
+
+    write_fstring(output, &property.name)?;
+    write_fstring(output, property.value.type_name())?;
+    output.write_i32::<LittleEndian>(value.len() as i32)?;
+    output.write_i32::<LittleEndian>(property.array_index)?;
+    match &property.value {
+        Value::Bool(value) => output.write_u8(*value as u8)?,
+        Value::Byte { enum_name, .. } | Value::Enum { enum_name, .. } => write_fstring(output, enum_name)?,
+        Value::Struct { struct_name, guid, .. } => {
+            write_fstring(output, struct_name)?;
+            write_guid(output, guid)?;
+        },
+        Value::Array { element_type, .. } | Value::Set { element_type, .. } => write_fstring(output, element_type)?,
+        Value::Map { key_type, value_type, .. } => {
+            write_fstring(output, key_type)?;
+            write_fstring(output, value_type)?;
+        },
+        Value::Unknown { tag, .. } => output.write_all(tag)?,
+        _ => {}
+    }
+    match &property.guid {
+        Some(guid) => {
+            output.write_u8(1)?;
+            write_guid(output, guid)?;
+        },
+        None => output.write_u8(0)?
+    }
+    output.write_all(&value)?;
+    Ok(())
+}
+
+fn write_guid(output: &mut Vec<u8>, guid: &FGuid) -> Result<()> {
+    for part in [guid.a, guid.b, guid.c, guid.d] {
+        output.write_u32::<LittleEndian>(part)?;
+    }
+    Ok(())
+}
+
+/// Writes the value of a tagged property, bools live entirely in the tag
+pub(crate) fn write_value(output: &mut Vec<u8>, value: &Value, structs: &StructRegistry) -> Result<()> {
+    match value {
+        Value::Struct { struct_name, value, .. } => write_struct_value(output, struct_name, value, structs),
+        Value::Array { value, .. } => write_array(output, value, structs),
+        Value::Set { removed, elements, .. } => {
+            write_items(output, removed, |output, item| write_item(output, item, structs))?;
+            write_items(output, elements, |output, item| write_item(output, item, structs))
+        },
+        Value::Map { removed, entries, .. } => {
+            write_items(output, removed, |output, key| write_item(output, key, structs))?;
+            write_items(output, entries, |output, (key, value)| {
+                write_item(output, key, structs)?;
+                write_item(output, value, structs)
+            })
+        },
+        Value::Unknown { data, .. } => Ok(output.write_all(data)?),
+        _ => property::write_value(output, value)
+    }
+}
+
+fn write_items<T, F>(output: &mut Vec<u8>, items: &[T], mut write: F) -> Result<()>
+where
+    F: FnMut(&mut Vec<u8>, &T) -> Result<()> {
+    output.write_i32::<LittleEndian>(items.len() as i32)?;
+    for item in items {
+        write(output, item)?;
+    }
+    Ok(())
+}
+
+/// Writes an element of a set or map
+pub(crate) fn write_item(output: &mut Vec<u8>, value: &Value, structs: &StructRegistry) -> Result<()> {
+    match value {
+        Value::Struct { struct_name, value, .. } => write_struct_value(output, struct_name, value, structs),
+        _ => property::write_element(output, value)
+    }
+}
+
+pub(crate) fn write_struct_value(output: &mut Vec<u8>, struct_name: &str, value: &StructValue, structs: &StructRegistry) -> Result<()> {
+    match (value, structs.codec(struct_name)) {
+        (StructValue::Properties(fields), Some(codec)) => return codec.encode(fields, output),
+        (_, Some(_)) => return Err(Error::make_data(format!("{} is written from its fields", struct_name))),
+        _ => {}
+    }
+    match value {
+        StructValue::DateTime(ticks) => output.write_u64::<LittleEndian>(*ticks)?,
+        StructValue::Timespan(ticks) => output.write_i64::<LittleEndian>(*ticks)?,
+        StructValue::Guid(guid) => write_guid(output, guid)?,
+        StructValue::Properties(properties) => write_properties(output, properties, structs)?
+    }
+    Ok(())
+}
+
+fn write_array(output: &mut Vec<u8>, value: &ArrayValue, structs: &StructRegistry) -> Result<()> {
+    match value {
+        ArrayValue::Bytes(bytes) => {
+            output.write_i32::<LittleEndian>(bytes.len() as i32)?;
+            output.write_all(bytes)?;
+        },
+        ArrayValue::Values(values) => {
+            output.write_i32::<LittleEndian>(values.len() as i32)?;
+            for value in values {
+                property::write_element(output, value)?;
+            }
+        },
+        ArrayValue::Structs { name, array_index, property_guid, struct_name, guid, elements } => {
+            output.write_i32::<LittleEndian>(elements.len() as i32)?;
+            let mut values = Vec::new();
+            for element in elements {
+                write_struct_value(&mut values, struct_name, element, structs)?;
+            }
+            write_fstring(output, name)?;
+            write_fstring(output, "StructProperty")?;
+            output.write_i32::<LittleEndian>(values.len() as i32)?;
+            output.write_i32::<LittleEndian>(*array_index)?;
+            write_fstring(output, struct_name)?;
+            write_guid(output, guid)?;
+            match property_guid {
+                Some(property_guid) => {
+                    output.write_u8(1)?;
+                    write_guid(output, property_guid)?;
+                },
+                None => output.write_u8(0)?
+            }
+            output.write_all(&values)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use std::{env, fs, path::PathBuf, process::{Command, Output}};
+
+mod common;
+use common::TEST_FILE;
+
+fn write_save(name: &str, data: &[u8]) -> PathBuf {
+	let path = env::temp_dir().join(format!("serde_gvas_cli_{}_{}.sav", std::process::id(), name));
+	fs::write(&path, data).expect("Failed to write save!");
+	path
+}
+
+fn gvas(args: &[&str]) -> Output {
+	Command::new(env!("CARGO_BIN_EXE_gvas")).args(args).output().expect("Failed to run gvas!")
+}
+
+#[test]
+fn header_and_dump() {
+	let path = write_save("valid", &TEST_FILE);
+	let path = path.to_str().unwrap();
+
+	let output = gvas(&["header", path]);
+	assert!(output.status.success());
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	assert!(stdout.contains("Save game class: /Script/UE4SaveFIle.TestSaveFile"), "{}", stdout);
+
+	let output = gvas(&["dump", path]);
+	assert!(output.status.success());
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	assert!(stdout.contains("u8_test: ByteProperty = 123"), "{}", stdout);
+	assert!(stdout.contains("test_struct: StructProperty<TestStruct>\n        test_field: UInt32Property = 1234"), "{}", stdout);
+	fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn errors() {
+	assert_eq!(gvas(&[]).status.code(), Some(2));
+	assert_eq!(gvas(&["frobnicate", "x"]).status.code(), Some(2));
+	assert_eq!(gvas(&["header"]).status.code(), Some(2));
+
+	let path = write_save("truncated", &TEST_FILE[..1500]);
+	let output = gvas(&["dump", path.to_str().unwrap()]);
+	assert_eq!(output.status.code(), Some(1));
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(stderr.contains("at offset"), "{}", stderr);
+	fs::remove_file(path).unwrap();
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_conversion() {
+	let path = write_save("json", &TEST_FILE);
+	let json = path.with_extension("json");
+	let back = path.with_extension("back.sav");
+
+	assert!(gvas(&["to-json", path.to_str().unwrap(), json.to_str().unwrap()]).status.success());
+	assert!(gvas(&["from-json", json.to_str().unwrap(), back.to_str().unwrap()]).status.success());
+	assert_eq!(fs::read(&back).unwrap(), TEST_FILE);
+
+	fs::write(&json, "{\"header\": 1}").unwrap();
+	let output = gvas(&["from-json", json.to_str().unwrap(), back.to_str().unwrap()]);
+	assert_eq!(output.status.code(), Some(1));
+	for path in [path, json, back] {
+		fs::remove_file(path).unwrap();
+	}
+}
+
+#[test]
+fn codegen() {
+	let path = write_save("codegen", &TEST_FILE);
+	let output = gvas(&["codegen", path.to_str().unwrap()]);
+	assert!(output.status.success());
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	assert!(stdout.contains("pub struct TestSaveFile {"), "{}", stdout);
+	assert!(stdout.contains("pub test_struct: TestStruct,"), "{}", stdout);
+	fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn set() {
+	let path = write_save("set", &TEST_FILE);
+	let output = gvas(&["set", path.to_str().unwrap(), "test_struct.test_field=99", "str_property=Edited"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+	let stdout = String::from_utf8(gvas(&["dump", path.to_str().unwrap()]).stdout).unwrap();
+	assert!(stdout.contains("test_field: UInt32Property = 99"), "{}", stdout);
+	assert!(stdout.contains("str_property: StrProperty = \"Edited\""), "{}", stdout);
+
+	let output = gvas(&["set", path.to_str().unwrap(), "int32_test=1.5"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(gvas(&["set", path.to_str().unwrap()]).status.code(), Some(2));
+	// the path ends at the first = outside of quotes
+	let output = gvas(&["set", path.to_str().unwrap(), "\"a=b\"=1"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(String::from_utf8_lossy(&output.stderr).contains("No property a=b"), "{}", String::from_utf8_lossy(&output.stderr));
+	fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn hexdump() {
+	let path = write_save("hexdump", &TEST_FILE);
+	let output = gvas(&["hexdump", path.to_str().unwrap()]);
+	assert!(output.status.success());
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	assert!(stdout.contains("0000047E  08 00 00 00 75 38 5F 74 65 73 74 00              name \"u8_test\""), "{}", stdout);
+
+	fs::write(&path, &TEST_FILE[..1500]).unwrap();
+	let output = gvas(&["hexdump", path.to_str().unwrap()]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(String::from_utf8(output.stdout).unwrap().contains("!! unparsed"));
+	assert_eq!(String::from_utf8(output.stderr).unwrap(), "error: Unexpected end of input at offset 1470 in long_test\n");
+	fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,157 @@
+use serde_gvas::codegen;
+use serde_gvas::types::{EnumByte, FGuid, FName};
+use serde_gvas::value::{ArrayValue, ByteValue, Save, StructValue, Value};
+
+mod common;
+use common::{body, fstring, property, sample, TEST_FILE};
+
+/// Generated in [`generated_code_reads_sample`], compiled here to check that it reads and writes back what it was generated from
+#[allow(dead_code)]
+mod generated {
+	include!("codegen/sample.rs");
+}
+
+#[test]
+fn generate_fixture() {
+	let save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	assert_eq!(codegen::generate(&save), "use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TestSaveFile {
+    pub u8_test: u8,
+    pub i8_test: i8,
+    pub ushort_test: u16,
+    pub short_test: i16,
+    pub uint32_test: u32,
+    pub int32_test: i32,
+    pub ulong_test: u64,
+    pub long_test: i64,
+    pub test_struct: TestStruct,
+    pub f_property: f32,
+    pub d_property: f64,
+    pub str_property: String,
+    pub test_arr: Vec<bool>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TestStruct {
+    pub test_field: u32,
+}
+");
+}
+
+#[test]
+fn generate_types() {
+	let mut save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	save.header.save_game_class_name = String::from("/Game/Saves/BP_Save.BP_Save_C");
+	let mut tag = fstring("NameProperty");
+	tag.extend(fstring("IntProperty"));
+	save.properties = vec![
+		property("Difficulty", Value::Enum { enum_name: String::from("/Script/Game.EDifficulty"), value: String::from("EDifficulty::Hard") }),
+		property("Mode", Value::Byte { enum_name: String::from("EMode"), value: ByteValue::Label(String::from("EMode::co_op")) }),
+		property("Modes", Value::Array { element_type: String::from("EnumProperty"), value: ArrayValue::Values(vec![
+			Value::Enum { enum_name: String::from("None"), value: String::from("EMode::Solo") }
+		]) }),
+		property("Counts", Value::Unknown { type_name: String::from("MapProperty"), tag, data: Vec::new() }),
+		property("type", Value::Name(String::from("Player"))),
+		property("Items", Value::Array { element_type: String::from("StructProperty"), value: ArrayValue::Structs {
+			name: String::from("Items"), array_index: 0, property_guid: None, struct_name: String::from("Item"), guid: FGuid::new(0, 0, 0, 0), elements: vec![
+				StructValue::Properties(vec![property("ID", Value::Int(1)), property("Spawned", Value::Struct {
+					struct_name: String::from("DateTime"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::DateTime(0)
+				})]),
+				StructValue::Properties(vec![property("ID", Value::Int(2)), property("Text", Value::Unknown {
+					type_name: String::from("TextProperty"), tag: Vec::new(), data: Vec::new()
+				})])
+			]
+		} })
+	];
+
+	assert_eq!(codegen::generate(&save), "use serde::{Deserialize, Serialize};
+use serde_gvas::types::{EnumByte, FDateTime, FName};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BP_Save_C {
+    #[serde(rename = \"Difficulty\")]
+    pub difficulty: EDifficulty,
+    #[serde(rename = \"Mode\")]
+    pub mode: EnumByte<EMode>,
+    #[serde(rename = \"Modes\")]
+    pub modes: Vec<EMode>,
+    // \"Counts\" is left out, MapProperty isn't decoded, reading it needs recover
+    pub r#type: FName,
+    #[serde(rename = \"Items\")]
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Item {
+    #[serde(rename = \"ID\")]
+    pub id: i32,
+    #[serde(rename = \"Spawned\", default, skip_serializing_if = \"Option::is_none\")]
+    pub spawned: Option<FDateTime>,
+    // \"Text\" is left out, TextProperty isn't decoded, reading it needs recover
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename = \"/Script/Game.EDifficulty\")]
+pub enum EDifficulty {
+    #[serde(rename = \"EDifficulty::Hard\")]
+    Hard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum EMode {
+    #[serde(rename = \"EMode::co_op\")]
+    CoOp,
+    #[serde(rename = \"EMode::Solo\")]
+    Solo,
+}
+");
+}
+
+#[test]
+fn generated_code_reads_sample() {
+	// the sample with optional fields, a soft object and an enum
+	let mut save = sample();
+	save.properties.extend([
+		property("Rewards", Value::Array { element_type: String::from("StructProperty"), value: ArrayValue::Structs {
+			name: String::from("Rewards"), array_index: 0, property_guid: None, struct_name: String::from("Reward"), guid: FGuid::new(0, 0, 0, 0), elements: vec![
+				StructValue::Properties(vec![property("ID", Value::Int(1)), property("Name", Value::Str(String::from("Sword")))]),
+				StructValue::Properties(vec![property("ID", Value::Int(2))])
+			]
+		} }),
+		property("Icon", Value::SoftObject([String::from("/Game/Icons/Sword.Sword"), String::new()])),
+		property("Mode", Value::Enum { enum_name: String::from("EMode"), value: String::from("EMode::Solo") }),
+		property("Unlocked", Value::Set { element_type: String::from("NameProperty"), removed: Vec::new(), elements: vec![Value::Name(String::from("Forest"))] }),
+		property("Stash", Value::Map { key_type: String::from("IntProperty"), value_type: String::from("StructProperty"), removed: Vec::new(), entries: vec![
+			(Value::Int(4), Value::Struct { struct_name: String::new(), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Properties(vec![property("Count", Value::Int(2))]) })
+		] })
+	]);
+	assert_eq!(codegen::generate(&save), include_str!("codegen/sample.rs"));
+
+	let parsed: generated::TestSaveFile = serde_gvas::from_bytes(&mut body(save.to_vec().unwrap())).expect("Failed to parse file!");
+	assert_eq!(parsed.test_struct.test_field, 1234);
+	assert_eq!(parsed.player_data.inventory.len(), 3);
+	assert_eq!(parsed.player_data.moods, [EnumByte(generated::EMood::Calm)]);
+	assert!(!parsed.player_data.flags.quest_01_done);
+	assert_eq!(parsed.player_data.checksum, [1, 2, 3]);
+	assert_eq!(parsed.rewards.len(), 2);
+	assert_eq!((parsed.rewards[0].id, parsed.rewards[0].name.as_deref()), (1, Some("Sword")));
+	assert_eq!((parsed.rewards[1].id, parsed.rewards[1].name.as_deref()), (2, None));
+	assert_eq!(parsed.icon.asset_path, "/Game/Icons/Sword.Sword");
+	assert_eq!(parsed.mode, generated::EMode::Solo);
+	assert_eq!(parsed.player_data.quests["Quest_02"], 0);
+	assert_eq!(parsed.unlocked, [FName(String::from("Forest"))]);
+	assert_eq!(parsed.stash[&4].count, 2);
+
+	// maps and sets are only read
+	save.properties.retain(|property| !matches!(property.value, Value::Set { .. } | Value::Map { .. }));
+	for property in &mut save.properties {
+		if let Value::Struct { value: StructValue::Properties(fields), .. } = &mut property.value {
+			fields.retain(|field| !matches!(field.value, Value::Set { .. } | Value::Map { .. }));
+		}
+	}
+	let input = body(save.to_vec().expect("Failed to write save!"));
+	let written = serde_gvas::to_vec(&parsed).expect("Failed to serialize file!");
+	assert_eq!(written, input.get_ref()[input.position() as usize..]);
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_gvas::types::{EnumByte, FDateTime, FName, SoftObjectPath};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TestSaveFile {
+    pub u8_test: u8,
+    pub i8_test: i8,
+    pub ushort_test: u16,
+    pub short_test: i16,
+    pub uint32_test: u32,
+    pub int32_test: i32,
+    pub ulong_test: u64,
+    pub long_test: i64,
+    pub test_struct: TestStruct,
+    pub f_property: f32,
+    pub d_property: f64,
+    pub str_property: String,
+    pub test_arr: Vec<bool>,
+    #[serde(rename = "PlayerData")]
+    pub player_data: PlayerData,
+    #[serde(rename = "Rewards")]
+    pub rewards: Vec<Reward>,
+    #[serde(rename = "Icon")]
+    pub icon: SoftObjectPath,
+    #[serde(rename = "Mode")]
+    pub mode: EMode,
+    #[serde(rename = "Unlocked", skip_serializing)]
+    pub unlocked: Vec<FName>,
+    #[serde(rename = "Stash", skip_serializing)]
+    pub stash: HashMap<i32, StashValue>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TestStruct {
+    pub test_field: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PlayerData {
+    #[serde(rename = "Inventory")]
+    pub inventory: Vec<Item>,
+    #[serde(rename = "Names")]
+    pub names: Vec<FName>,
+    #[serde(rename = "Moods")]
+    pub moods: Vec<EnumByte<EMood>>,
+    #[serde(rename = "Difficulty")]
+    pub difficulty: EDifficulty,
+    #[serde(rename = "Flags")]
+    pub flags: Flags,
+    #[serde(rename = "Quests", skip_serializing)]
+    pub quests: HashMap<String, i32>,
+    #[serde(rename = "Checksum")]
+    pub checksum: Vec<u8>,
+    #[serde(rename = "Saved")]
+    pub saved: FDateTime,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Item {
+    #[serde(rename = "ItemId")]
+    pub item_id: FName,
+    #[serde(rename = "Count")]
+    pub count: i32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Flags {
+    #[serde(rename = "Quest_01.Done")]
+    pub quest_01_done: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Reward {
+    #[serde(rename = "ID")]
+    pub id: i32,
+    #[serde(rename = "Name", default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StashValue {
+    #[serde(rename = "Count")]
+    pub count: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum EMood {
+    #[serde(rename = "EMood::Calm")]
+    Calm,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum EDifficulty {
+    #[serde(rename = "EDifficulty::Easy")]
+    Easy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum EMode {
+    #[serde(rename = "EMode::Solo")]
+    Solo,
+}
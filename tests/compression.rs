@@ -0,0 +1,128 @@
+#![cfg(feature = "compression")]
+
+use std::io::Cursor;
+
+use serde_gvas::compression::{self, Container, PlzCompression};
+use serde_gvas::{header, hexdump, value, DeserializerOptions, ErrorCode};
+use serde_gvas::index::SaveIndex;
+use serde_gvas::types::GvasHeader;
+
+mod common;
+use common::TEST_FILE;
+
+#[test]
+fn plz_round_trip() {
+	for compression in [PlzCompression::None, PlzCompression::Zlib, PlzCompression::ZlibTwice] {
+		let container = Container::Plz(compression);
+		let compressed = container.compress(&TEST_FILE).expect("Failed to compress!");
+		assert_eq!(&compressed[8..11], b"PlZ");
+		assert_eq!(Container::detect(&compressed), container);
+
+		let (detected, data) = compression::decompress(compressed.clone()).expect("Failed to decompress!");
+		assert_eq!(detected, container);
+		assert_eq!(data, TEST_FILE);
+		assert_eq!(detected.compress(&data).expect("Failed to compress!"), compressed);
+
+		let mut cursor = Cursor::new(data);
+		let _: GvasHeader = header::de::from_bytes(&mut cursor).expect("Failed to parse file header!");
+		assert_eq!(value::from_bytes(&mut cursor).expect("Failed to parse file!").len(), 13);
+	}
+}
+
+#[test]
+fn raw_passthrough() {
+	assert_eq!(Container::detect(&TEST_FILE), Container::Raw);
+	let (container, data) = compression::decompress(TEST_FILE.to_vec()).expect("Failed to decompress!");
+	assert_eq!(container, Container::Raw);
+	assert_eq!(data, TEST_FILE);
+}
+
+#[test]
+fn decompression_limit() {
+	let compressed = Container::Plz(PlzCompression::ZlibTwice).compress(&TEST_FILE).expect("Failed to compress!");
+	let options = DeserializerOptions { max_total_size: 1024, ..Default::default() };
+	let err = compression::decompress_with_options(compressed.clone(), &options).expect_err("Decompressing should fail!");
+	assert!(matches!(err.code(), ErrorCode::LimitExceeded { .. }));
+
+	// a header lying about the size doesn't get past the limit either
+	let mut lying = compressed;
+	lying[..4].copy_from_slice(&16u32.to_le_bytes());
+	let err = compression::decompress(lying).expect_err("Decompressing should fail!");
+	assert!(matches!(err.code(), ErrorCode::LimitExceeded { .. }));
+}
+
+#[test]
+fn chunked_round_trip() {
+	for chunk_size in [compression::DEFAULT_CHUNK_SIZE, 256] {
+		let container = Container::Chunked { chunk_size };
+		let compressed = container.compress(&TEST_FILE).expect("Failed to compress!");
+		assert_eq!(&compressed[..8], &0x9E2A83C1i64.to_le_bytes());
+		assert_eq!(Container::detect(&compressed), container);
+
+		let (detected, data) = compression::decompress(compressed.clone()).expect("Failed to decompress!");
+		assert_eq!(detected, container);
+		assert_eq!(data, TEST_FILE);
+		assert_eq!(detected.compress(&data).expect("Failed to compress!"), compressed);
+
+		let mut cursor = Cursor::new(data);
+		let _: GvasHeader = header::de::from_bytes(&mut cursor).expect("Failed to parse file header!");
+		assert_eq!(value::from_bytes(&mut cursor).expect("Failed to parse file!").len(), 13);
+	}
+
+	// large archives are stored as several blocks back to back
+	let container = Container::Chunked { chunk_size: 256 };
+	let (first, second) = TEST_FILE.split_at(1000);
+	let mut blocks = container.compress(first).expect("Failed to compress!");
+	blocks.extend(container.compress(second).expect("Failed to compress!"));
+	let (_, data) = compression::decompress(blocks).expect("Failed to decompress!");
+	assert_eq!(data, TEST_FILE);
+}
+
+#[test]
+fn chunked_invalid() {
+	let compressed = Container::Chunked { chunk_size: 256 }.compress(&TEST_FILE).expect("Failed to compress!");
+
+	let options = DeserializerOptions { max_total_size: 1024, ..Default::default() };
+	let err = compression::decompress_with_options(compressed.clone(), &options).expect_err("Decompressing should fail!");
+	assert!(matches!(err.code(), ErrorCode::LimitExceeded { .. }));
+
+	let mut zero_chunks = compressed.clone();
+	zero_chunks[8..16].fill(0);
+	compression::decompress(zero_chunks).expect_err("Decompressing should fail!");
+
+	// a chunk claiming fewer bytes than it inflates to
+	let mut lying = compressed.clone();
+	lying[40..48].copy_from_slice(&16i64.to_le_bytes());
+	compression::decompress(lying).expect_err("Decompressing should fail!");
+
+	compression::decompress(compressed[..compressed.len() - 10].to_vec()).expect_err("Decompressing should fail!");
+
+	// a chunk count whose table size overflows
+	let mut huge = compressed;
+	huge[8..16].copy_from_slice(&1i64.to_le_bytes());
+	huge[24..32].copy_from_slice(&i64::MAX.to_le_bytes());
+	compression::decompress_with_options(huge, &DeserializerOptions::unlimited()).expect_err("Decompressing should fail!");
+}
+
+#[test]
+fn compressed_save() {
+	for container in [Container::Plz(PlzCompression::Zlib), Container::Chunked { chunk_size: 256 }] {
+		let compressed = container.compress(&TEST_FILE).expect("Failed to compress!");
+		let save = value::Save::from_bytes(compressed.clone()).expect("Failed to parse file!");
+		assert_eq!(save.container, container);
+		assert_eq!(save.properties.len(), 13);
+		assert_eq!(save.to_vec().expect("Failed to write file!"), compressed);
+
+		let (header, detected, _): (GvasHeader, _, _) = header::de::from_save_bytes(compressed.clone(), DeserializerOptions::default()).expect("Failed to parse header!");
+		assert_eq!(header, save.header);
+		assert_eq!(detected, container);
+
+		let index = SaveIndex::new(compressed.clone()).expect("Failed to index file!");
+		assert_eq!(index.container(), container);
+		assert_eq!(index.entries().len(), 13);
+
+		let (dump, error) = hexdump::annotate(compressed);
+		assert!(error.is_none(), "{}", dump);
+		assert!(dump.contains("name \"test_struct\""), "{}", dump);
+	}
+}
@@ -0,0 +1,148 @@
+#![cfg(feature = "derive")]
+
+use std::io::Cursor;
+
+use serde::{Deserialize, Serialize};
+use serde_gvas::{value, GvasDeserialize, GvasSerialize};
+use serde_gvas::value::{ArrayValue, ByteValue, Property, StructValue, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum Difficulty {
+	#[serde(rename = "EDifficulty::Easy")]
+	Easy,
+	#[serde(rename = "EDifficulty::Hard")]
+	Hard
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum Slot {
+	Head,
+	Chest
+}
+
+#[derive(Debug, PartialEq, GvasSerialize, GvasDeserialize)]
+#[gvas(struct_name = "InventoryItem")]
+struct Item {
+	#[gvas(rename = "ItemId", property = "NameProperty")]
+	id: String,
+	#[gvas(rename = "Count")]
+	count: i32
+}
+
+#[derive(Debug, PartialEq, GvasSerialize, GvasDeserialize)]
+struct SaveData {
+	#[gvas(rename = "PlayerName")]
+	player_name: String,
+	#[gvas(rename = "Level", property = "NameProperty")]
+	level: String,
+	#[gvas(rename = "Owner", property = "ObjectProperty")]
+	owner: String,
+	#[gvas(rename = "Difficulty", enum_name = "EDifficulty")]
+	difficulty: Difficulty,
+	#[gvas(rename = "Slot", property = "ByteProperty")]
+	slot: Slot,
+	#[gvas(rename = "Mood", property = "ByteProperty", enum_name = "EMood")]
+	mood: String,
+	#[gvas(rename = "Tags", property = "NameProperty")]
+	tags: Vec<String>,
+	#[gvas(rename = "Items")]
+	items: Vec<Item>,
+	#[gvas(rename = "Equipped", struct_name = "EquippedItem")]
+	equipped: Item,
+	#[gvas(rename = "Missing", default)]
+	missing: i32
+}
+
+fn sample() -> SaveData {
+	SaveData {
+		player_name: String::from("Player"),
+		level: String::from("Level_01"),
+		owner: String::from("/Game/Maps/Level_01.Level_01:PersistentLevel.Player_C_0"),
+		difficulty: Difficulty::Hard,
+		slot: Slot::Chest,
+		mood: String::from("EMood::Happy"),
+		tags: vec![String::from("Brave"), String::from("Lucky")],
+		items: vec![Item { id: String::from("Potion"), count: 3 }, Item { id: String::from("Sword"), count: 1 }],
+		equipped: Item { id: String::from("Helmet"), count: 1 },
+		missing: 0
+	}
+}
+
+fn find<'a>(properties: &'a [Property], name: &str) -> &'a Value {
+	&properties.iter().find(|property| property.name == name).expect("Property not found!").value
+}
+
+#[test]
+fn derive_property_types() {
+	let bytes = serde_gvas::to_vec(&sample()).expect("Failed to serialize!");
+	let properties = value::from_bytes(&mut Cursor::new(bytes.clone())).expect("Failed to parse properties!");
+
+	assert_eq!(find(&properties, "PlayerName"), &Value::Str(String::from("Player")));
+	assert_eq!(find(&properties, "Level"), &Value::Name(String::from("Level_01")));
+	assert!(matches!(find(&properties, "Owner"), Value::Object(_)));
+	assert_eq!(find(&properties, "Difficulty"), &Value::Enum { enum_name: String::from("EDifficulty"), value: String::from("EDifficulty::Hard") });
+	assert_eq!(find(&properties, "Slot"), &Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(1) });
+	assert_eq!(find(&properties, "Mood"), &Value::Byte { enum_name: String::from("EMood"), value: ByteValue::Label(String::from("EMood::Happy")) });
+	assert_eq!(find(&properties, "Tags"), &Value::Array { element_type: String::from("NameProperty"), value: ArrayValue::Values(vec![
+		Value::Name(String::from("Brave")), Value::Name(String::from("Lucky"))
+	]) });
+	match find(&properties, "Items") {
+		Value::Array { value: ArrayValue::Structs { struct_name, elements, .. }, .. } => {
+			assert_eq!(struct_name, "InventoryItem");
+			match &elements[0] {
+				StructValue::Properties(fields) => assert_eq!(find(fields, "ItemId"), &Value::Name(String::from("Potion"))),
+				element => panic!("Unexpected element {:?}", element)
+			}
+		},
+		value => panic!("Unexpected value {:?}", value)
+	}
+	assert!(matches!(find(&properties, "Equipped"), Value::Struct { struct_name, .. } if struct_name == "EquippedItem"));
+
+	let back: SaveData = serde_gvas::from_bytes(&mut Cursor::new(bytes)).expect("Failed to deserialize!");
+	assert_eq!(back, sample());
+}
+
+#[derive(Debug, GvasDeserialize)]
+struct WrongType {
+	#[gvas(rename = "PlayerName", property = "NameProperty")]
+	_player_name: String
+}
+
+#[derive(Debug, GvasDeserialize)]
+struct WrongStruct {
+	#[gvas(rename = "Equipped", struct_name = "Weapon")]
+	_equipped: Item
+}
+
+#[test]
+fn derive_mismatch() {
+	let bytes = serde_gvas::to_vec(&sample()).expect("Failed to serialize!");
+
+	let err = serde_gvas::from_bytes::<WrongType>(&mut Cursor::new(bytes.clone())).expect_err("Deserializing should fail!");
+	assert!(err.to_string().contains("Expected NameProperty but found StrProperty"), "{}", err);
+	assert_eq!(err.path(), Some("PlayerName"));
+
+	let err = serde_gvas::from_bytes::<WrongStruct>(&mut Cursor::new(bytes)).expect_err("Deserializing should fail!");
+	assert!(err.to_string().contains("Expected struct Weapon but found EquippedItem"), "{}", err);
+}
+
+#[derive(Debug, PartialEq, GvasSerialize, GvasDeserialize)]
+struct Empty {
+	#[gvas(rename = "Scores")]
+	scores: Vec<i32>,
+	#[gvas(rename = "Items", struct_name = "InventoryItem")]
+	items: Vec<Item>
+}
+
+#[test]
+fn derive_empty_arrays() {
+	let empty = Empty { scores: Vec::new(), items: Vec::new() };
+	let bytes = serde_gvas::to_vec(&empty).expect("Failed to serialize!");
+	let properties = value::from_bytes(&mut Cursor::new(bytes.clone())).expect("Failed to parse properties!");
+	assert_eq!(find(&properties, "Scores"), &Value::Array { element_type: String::from("IntProperty"), value: ArrayValue::Values(Vec::new()) });
+	assert!(matches!(find(&properties, "Items"), Value::Array { value: ArrayValue::Structs { struct_name, elements, .. }, .. }
+		if struct_name == "InventoryItem" && elements.is_empty()));
+
+	let back: Empty = serde_gvas::from_bytes(&mut Cursor::new(bytes)).expect("Failed to deserialize!");
+	assert_eq!(back, empty);
+}
@@ -0,0 +1,80 @@
+use serde_gvas::diff::{diff_with_options, Change, DiffOptions};
+use serde_gvas::value::{ArrayValue, Property, Save, StructValue, Value};
+
+mod common;
+use common::{inventory, item, property, TEST_FILE};
+
+fn find<'a>(properties: &'a mut [Property], name: &str) -> &'a mut Value {
+	&mut properties.iter_mut().find(|property| property.name == name).expect("Property not found!").value
+}
+
+#[test]
+fn diff_saves() {
+	let a = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	assert!(serde_gvas::diff(&a, &a).is_empty());
+
+	let mut b = a.clone();
+	b.header.save_game_class_name = String::from("/Script/Other.SaveGame");
+	b.header.custom_versions.remove(0);
+	*find(&mut b.properties, "int32_test") = Value::Int(250);
+	if let Value::Struct { value: StructValue::Properties(fields), .. } = find(&mut b.properties, "test_struct") {
+		*find(fields, "test_field") = Value::UInt32(1);
+	}
+	if let Value::Array { value: ArrayValue::Values(values), .. } = find(&mut b.properties, "test_arr") {
+		values[1] = Value::Bool(false);
+		values.push(Value::Bool(true));
+	}
+	b.properties.retain(|property| property.name != "str_property");
+	b.properties.push(Property { array_index: 1, ..property("extra", Value::Str(String::from("new"))) });
+
+	let diff = serde_gvas::diff(&a, &b);
+	assert_eq!(diff.to_string(), "\
+header.custom_versions.FReleaseObjectVersion: removed
+header.save_game_class_name: /Script/UE4SaveFIle.TestSaveFile -> /Script/Other.SaveGame
+int32_test: 1251 -> 250
+test_struct.test_field: 1234 -> 1
+str_property: removed
+test_arr[1]: true -> false
+test_arr[6]: added
+extra(1): added
+");
+	assert_eq!(diff.properties[2].change, Change::Removed(Value::Str(String::from("Some content in string"))));
+	assert!(serde_gvas::diff(&b, &a).header.iter().any(|change| change.to_string() == "header.custom_versions.FReleaseObjectVersion: added"));
+}
+
+#[test]
+fn diff_keyed_arrays() {
+	let mut a = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	let mut b = a.clone();
+	a.properties = vec![inventory(vec![item("Sword", 1), item("Potion", 3), item("Shield", 1)])];
+	b.properties = vec![inventory(vec![item("Potion", 5), item("Sword", 1), item("Bow", 1)])];
+
+	let by_index = serde_gvas::diff(&a, &b).to_string();
+	assert!(by_index.starts_with("Inventory[0].ItemId: \"Sword\" -> \"Potion\"\n"), "{}", by_index);
+
+	let options = DiffOptions { array_keys: [(String::from("Inventory"), String::from("ItemId"))].into_iter().collect() };
+	assert_eq!(diff_with_options(&a, &b, &options).to_string(), "\
+Inventory[ItemId=\"Potion\"].Count: 3 -> 5
+Inventory[ItemId=\"Shield\"]: removed
+Inventory[ItemId=\"Bow\"]: added
+");
+}
+
+#[test]
+fn diff_floats_and_slots() {
+	let mut a = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	a.properties = vec![
+		property("Ratio", Value::Float(f32::NAN)),
+		property("Scores", Value::Array { element_type: String::from("IntProperty"), value: ArrayValue::Values(vec![Value::Int(1), Value::Int(2)]) }),
+		Property { array_index: 1, ..property("Scores", Value::Array { element_type: String::from("IntProperty"), value: ArrayValue::Values(vec![Value::Int(3)]) }) }
+	];
+	assert!(serde_gvas::diff(&a, &a).is_empty());
+
+	let mut b = a.clone();
+	b.properties[0].value = Value::Float(f32::from_bits(0x7FC00001));
+	b.properties[2].value = Value::Array { element_type: String::from("IntProperty"), value: ArrayValue::Values(vec![Value::Int(4)]) };
+	assert_eq!(serde_gvas::diff(&a, &b).to_string(), "\
+Ratio: NaN -> NaN:7FC00001
+Scores(1)[0]: 3 -> 4
+");
+}
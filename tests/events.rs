@@ -0,0 +1,139 @@
+use std::io::Read;
+
+use serde_gvas::events::{Event, EventReader};
+use serde_gvas::types::FGuid;
+use serde_gvas::value::{self, ArrayValue, ByteValue, Property, Save, StructValue, Value};
+use serde_gvas::DeserializerOptions;
+
+mod common;
+use common::{body, property, sample, TEST_FILE};
+
+/// Hands out a few bytes per read, so the reader has to ask for more in the middle of properties
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		let len = buf.len().min(self.0.len()).min(7);
+		buf[..len].copy_from_slice(&self.0[..len]);
+		self.0 = &self.0[len..];
+		Ok(len)
+	}
+}
+
+/// Builds the value tree back from the events of a property list
+fn properties(events: &mut impl Iterator<Item = Event>) -> Vec<Property> {
+	let mut properties = Vec::new();
+	while let Some(event) = events.next() {
+		match event {
+			Event::PropertyStart { name, array_index, guid, .. } => {
+				let value = value(events, &name);
+				properties.push(Property { name, array_index, guid, value });
+			},
+			Event::End => break,
+			event => panic!("Unexpected event {:?}", event)
+		}
+	}
+	properties
+}
+
+fn value(events: &mut impl Iterator<Item = Event>, name: &str) -> Value {
+	match events.next().expect("Missing value!") {
+		Event::Value(value) => value,
+		Event::StructStart { struct_name, guid } => Value::Struct { struct_name, guid, value: StructValue::Properties(properties(events)) },
+		Event::ArrayStart { element_type, .. } => {
+			let mut values = Vec::new();
+			let mut structs = None;
+			let mut bytes = None;
+			loop {
+				match events.next().expect("Missing element!") {
+					Event::End => break,
+					Event::Value(value) => values.push(value),
+					Event::Bytes(data) => bytes = Some(data),
+					Event::StructStart { struct_name, guid } => {
+						let (_, _, elements) = structs.get_or_insert_with(|| (struct_name, guid, Vec::new()));
+						elements.push(StructValue::Properties(properties(events)));
+					},
+					event => panic!("Unexpected event {:?}", event)
+				}
+			}
+			let value = match (structs, bytes) {
+				(Some((struct_name, guid, elements)), _) => ArrayValue::Structs { name: String::from(name), array_index: 0, property_guid: None, struct_name, guid, elements },
+				(_, Some(bytes)) => ArrayValue::Bytes(bytes),
+				_ => ArrayValue::Values(values)
+			};
+			Value::Array { element_type, value }
+		},
+		event => panic!("Unexpected event {:?}", event)
+	}
+}
+
+#[test]
+fn events_rebuild_value_tree() {
+	let data = sample().to_vec().unwrap();
+	let expected = value::from_bytes(&mut body(data.clone())).expect("Failed to parse properties!");
+
+	let mut input = body(data);
+	let events: Vec<Event> = EventReader::new(&mut input).collect::<Result<_, _>>().expect("Failed to read events!");
+	assert_eq!(properties(&mut events.clone().into_iter()), expected);
+
+	assert_eq!(&events[..2], [
+		Event::PropertyStart { name: String::from("u8_test"), type_name: String::from("ByteProperty"), size: 1, array_index: 0, guid: None },
+		Event::Value(Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(123) })
+	]);
+	let start = events.iter().position(|event| matches!(event, Event::PropertyStart { name, .. } if name == "test_struct")).unwrap();
+	assert_eq!(&events[start + 1..start + 5], [
+		Event::StructStart { struct_name: String::from("TestStruct"), guid: FGuid::new(0, 0, 0, 0) },
+		Event::PropertyStart { name: String::from("test_field"), type_name: String::from("UInt32Property"), size: 4, array_index: 0, guid: None },
+		Event::Value(Value::UInt32(1234)),
+		Event::End
+	]);
+	assert!(events.contains(&Event::ArrayStart { element_type: String::from("StructProperty"), len: 3 }));
+	assert!(events.contains(&Event::Bytes(vec![1, 2, 3])));
+}
+
+#[test]
+fn events_errors() {
+	let data = sample().to_vec().unwrap();
+	let mut input = body(data[..1500].to_vec());
+	let mut reader = EventReader::new(&mut input);
+	let err = reader.by_ref().find_map(Result::err).expect("Reading should fail!");
+	assert!(err.offset().is_some());
+	assert!(reader.next().is_none());
+
+	// unknown types are only skipped when recovering
+	let mut save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse save!");
+	save.properties = vec![property("Text", Value::Unknown { type_name: String::from("TextProperty"), tag: vec![], data: vec![0; 4] }), property("After", Value::Int(1))];
+	let data = save.to_vec().unwrap();
+	assert!(EventReader::new(&mut body(data.clone())).any(|event| event.is_err()));
+
+	let mut input = body(data);
+	let mut reader = EventReader::with_options(&mut input, DeserializerOptions { recover: true, ..Default::default() });
+	let events: Vec<Event> = reader.by_ref().collect::<Result<_, _>>().expect("Failed to read events!");
+	assert!(matches!(&events[1], Event::Value(Value::Unknown { type_name, .. }) if type_name == "TextProperty"));
+	assert_eq!(events[3], Event::Value(Value::Int(1)));
+	assert_eq!(reader.diagnostics().len(), 1);
+}
+
+#[test]
+fn events_from_reader() {
+	let data = sample().to_vec().unwrap();
+	let input = body(data.clone());
+	let start = input.position() as usize;
+	let expected: Vec<Event> = EventReader::new(input).collect::<Result<_, _>>().expect("Failed to read events!");
+	let events: Vec<Event> = EventReader::new(Trickle(&data[start..])).collect::<Result<_, _>>().expect("Failed to read events!");
+	assert_eq!(events, expected);
+
+	// offsets count from where the reader started, past the properties that were dropped
+	let err = EventReader::new(Trickle(&data[start..1500])).find_map(Result::err).expect("Reading should fail!");
+	let expected = value::from_bytes(&mut body(data[..1500].to_vec())).unwrap_err();
+	assert_eq!(err.offset(), expected.offset().map(|offset| offset - start as u64));
+
+	let mut save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse save!");
+	save.properties = vec![property("Before", Value::Int(1)), property("Text", Value::Unknown { type_name: String::from("TextProperty"), tag: vec![], data: vec![0; 4] })];
+	let data = save.to_vec().unwrap();
+	let options = DeserializerOptions { recover: true, ..Default::default() };
+	let (_, expected) = value::from_bytes_with_diagnostics(&mut body(data.clone()), options.clone()).expect("Failed to parse properties!");
+	let mut reader = EventReader::with_options(Trickle(&data[start..]), options);
+	assert!(reader.by_ref().all(|event| event.is_ok()));
+	assert_eq!(reader.diagnostics()[0].offset, expected[0].offset - start as u64);
+}
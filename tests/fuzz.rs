@@ -0,0 +1,95 @@
+use std::io::Cursor;
+
+use serde::Deserialize;
+use serde_gvas::{header, DeserializerOptions};
+use serde_gvas::types::GvasHeader;
+
+mod common;
+use common::TEST_FILE;
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct CustomStruct {
+    test_field: u32
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct UnrealFile {
+    u8_test: u8,
+    i8_test: i8,
+    ushort_test: u16,
+    short_test: i16,
+    uint32_test: u32,
+    int32_test: i32,
+    ulong_test: u64,
+    long_test: i64,
+    f_property: f32,
+    d_property: f64,
+    str_property: String,
+    test_struct: CustomStruct,
+    test_arr: Vec<bool>
+}
+
+/// Every property is skipped, so this walks through whatever the input contains.
+#[derive(Deserialize)]
+struct Anything {}
+
+fn parse(bytes: &[u8]) {
+	let mut cursor = Cursor::new(bytes.to_vec());
+	let header: serde_gvas::Result<GvasHeader> = header::de::from_bytes(&mut cursor);
+	let body_start = match header {
+		Ok(_) => cursor.position(),
+		Err(_) => 0
+	};
+
+	cursor.set_position(body_start);
+	let _ = serde_gvas::from_bytes::<UnrealFile>(&mut cursor);
+	cursor.set_position(body_start);
+	let _ = serde_gvas::from_bytes::<Anything>(&mut cursor);
+	cursor.set_position(body_start);
+	let _ = serde_gvas::value::from_bytes(&mut cursor);
+	cursor.set_position(body_start);
+	let _ = serde_gvas::value::from_bytes_with_diagnostics(&mut cursor, DeserializerOptions { recover: true, lenient: true, ..Default::default() });
+}
+
+#[test]
+fn truncated_input() {
+	for len in 0..TEST_FILE.len() {
+		parse(&TEST_FILE[..len]);
+	}
+}
+
+#[test]
+fn corrupted_bytes() {
+	let mut bytes = TEST_FILE.to_vec();
+	for i in 0..bytes.len() {
+		let original = bytes[i];
+		for value in [0x00, 0xFF, 0x80, original ^ 0x01] {
+			bytes[i] = value;
+			parse(&bytes);
+		}
+		bytes[i] = original;
+	}
+}
+
+#[test]
+fn random_mutations() {
+	let mut state = 0x2545F4914F6CDD1Du64;
+	let mut next = move || {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		state
+	};
+
+	for _ in 0..2000 {
+		let mut bytes = TEST_FILE.to_vec();
+		for _ in 0..(next() % 8 + 1) {
+			let i = (next() % bytes.len() as u64) as usize;
+			bytes[i] = next() as u8;
+		}
+		let len = bytes.len() - (next() % 64) as usize;
+		parse(&bytes[..len]);
+	}
+}
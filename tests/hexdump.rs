@@ -0,0 +1,62 @@
+use serde_gvas::hexdump;
+use serde_gvas::value::{Property, Save, Value};
+
+mod common;
+use common::TEST_FILE;
+
+#[test]
+fn annotate_properties() {
+	let (dump, error) = hexdump::annotate(TEST_FILE.to_vec());
+	assert!(error.is_none(), "{:?}", error);
+	assert!(dump.starts_with("00000000  47 56 41 53"), "{}", dump);
+	assert!(dump.contains("0000047E  08 00 00 00 75 38 5F 74 65 73 74 00              name \"u8_test\"\n"), "{}", dump);
+	assert!(dump.contains("0000048A  0D 00 00 00 42 79 74 65 50 72 6F 70 65 72 74 79  type \"ByteProperty\"\n0000049A  00\n"), "{}", dump);
+	assert!(dump.contains("0000049B  01 00 00 00                                      size 1\n"), "{}", dump);
+	assert!(dump.contains("0000049F  00 00 00 00                                      array index 0\n"), "{}", dump);
+	assert!(dump.contains("000004AC  00                                               guid flag 0\n000004AD  7B                                               value 123\n"), "{}", dump);
+	assert!(dump.contains("00000639  00                                               guid flag 0\n0000063A  0B 00 00 00 74 65 73 74 5F 66 69 65 6C 64 00         name \"test_field\"\n"), "{}", dump);
+	assert!(dump.contains("00000751  06 00 00 00                                      length 6\n"), "{}", dump);
+	assert!(dump.contains("0000075B  05 00 00 00 4E 6F 6E 65 00                       None\n"), "{}", dump);
+	assert!(!dump.contains("!!"), "{}", dump);
+}
+
+#[test]
+fn annotate_failure() {
+	let (dump, error) = hexdump::annotate(TEST_FILE[..1500].to_vec());
+	let error = error.expect("Truncated save should fail!");
+	assert_eq!(error.to_string(), "Unexpected end of input at offset 1470 in long_test");
+	assert!(dump.ends_with("000005BE  0A 00 00 00 6C 6F 6E 67 5F 74 65 73 74 00        name \"long_test\"\n\
+		!! Unexpected end of input at offset 1470 in long_test\n\
+		000005CC  0E 00 00 00 49 6E 74 36 34 50 72 6F 70 65 72 74  !! unparsed\n"), "{}", dump);
+
+	// a broken size shows up right after the value it belongs to
+	let mut data = TEST_FILE.to_vec();
+	data[0x57E] = 3;
+	let (dump, error) = hexdump::annotate(data);
+	assert_eq!(error.expect("Wrong size should fail!").to_string(), "Expected value size of 3 got 4 at offset 1375 in int32_test");
+	assert!(dump.contains("00000587  E3 04 00 00                                      value 1251\n!! "), "{}", dump);
+	assert!(dump.contains("0000058B  0B 00 00 00 75 6C 6F 6E 67 5F 74 65 73 74 00 0F  !! unparsed\n"), "{}", dump);
+}
+
+#[test]
+fn annotate_containers() {
+	let mut save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	let property = |name: &str, value: Value| Property { name: String::from(name), array_index: 0, guid: None, value };
+	save.properties = vec![
+		property("Icon", Value::SoftObject([String::from("/Game/Map"), String::new()])),
+		property("Seen", Value::Set { element_type: String::from("NameProperty"), removed: Vec::new(), elements: vec![Value::Name(String::from("Cave"))] }),
+		property("Counts", Value::Map { key_type: String::from("StrProperty"), value_type: String::from("IntProperty"), removed: Vec::new(), entries: vec![
+			(Value::Str(String::from("a")), Value::Int(5))
+		] })
+	];
+
+	let (dump, error) = hexdump::annotate(save.to_vec().unwrap());
+	assert!(error.is_none(), "{:?}", error);
+	assert!(dump.contains("value \"/Game/Map\" \"\"\n"), "{}", dump);
+	assert!(dump.contains("    value \"Cave\"\n"), "{}", dump);
+	assert!(dump.contains("key type \"StrProperty\"\n"), "{}", dump);
+	assert!(dump.contains("    removed 0\n"), "{}", dump);
+	assert!(dump.contains("    value \"a\"\n"), "{}", dump);
+	assert!(dump.contains("    value 5\n"), "{}", dump);
+	assert!(!dump.contains("undecoded"), "{}", dump);
+}
@@ -0,0 +1,51 @@
+use serde::Deserialize;
+use serde_gvas::index::SaveIndex;
+use serde_gvas::value::Value;
+use serde_gvas::DeserializerOptions;
+
+mod common;
+use common::sample;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct TestStruct {
+	test_field: u32
+}
+
+#[test]
+fn index_top_level() {
+	let data = sample().to_vec().unwrap();
+	let mut index = SaveIndex::new(data.clone()).expect("Failed to index save!");
+	assert_eq!(index.header().save_game_class_name, "/Script/UE4SaveFIle.TestSaveFile");
+
+	let names: Vec<&str> = index.entries().iter().map(|entry| entry.name()).collect();
+	assert_eq!(names, ["u8_test", "i8_test", "ushort_test", "short_test", "uint32_test", "int32_test", "ulong_test", "long_test",
+		"test_struct", "f_property", "d_property", "str_property", "test_arr", "PlayerData"]);
+	for entry in index.entries() {
+		let name = entry.name().as_bytes();
+		let start = entry.offset as usize + 4;
+		assert_eq!(&data[start..start + name.len()], name);
+	}
+	let entry = index.get("int32_test").unwrap();
+	assert_eq!((entry.type_name.as_str(), entry.size), ("IntProperty", 4));
+	assert!(index.get("test_struct.test_field").is_none());
+
+	assert_eq!(index.decode::<i32>("int32_test").unwrap(), 1251);
+	assert_eq!(index.decode::<String>("str_property").unwrap(), "Some content in string");
+	assert_eq!(index.decode::<TestStruct>("test_struct").unwrap(), TestStruct { test_field: 1234 });
+	assert_eq!(index.value("f_property").unwrap(), Value::Float(125.213));
+	assert!(index.decode::<i32>("missing").is_err());
+	assert_eq!(index.into_inner(), data);
+}
+
+#[test]
+fn index_nested() {
+	let mut index = SaveIndex::with_options(sample().to_vec().unwrap(), true, DeserializerOptions::default()).expect("Failed to index save!");
+	let paths: Vec<String> = index.entries().iter().map(|entry| entry.path.to_string()).collect();
+	assert!(paths.contains(&String::from("test_struct.test_field")), "{:?}", paths);
+	let start = paths.iter().position(|path| path == "PlayerData.Inventory").unwrap();
+	assert_eq!(&paths[start..start + 3], ["PlayerData.Inventory", "PlayerData.Inventory[0].ItemId", "PlayerData.Inventory[0].Count"]);
+	assert!(paths.contains(&String::from(r#"PlayerData.Flags."Quest_01.Done""#)), "{:?}", paths);
+
+	assert_eq!(index.decode::<u32>("test_struct.test_field").unwrap(), 1234);
+	assert_eq!(index.value("PlayerData.Inventory[1].Count").unwrap(), Value::Int(1));
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "json")]
+
+use serde_gvas::{json, DeserializerOptions};
+use serde_gvas::value::{Property, Save, Value};
+
+mod common;
+use common::TEST_FILE;
+
+#[test]
+fn json_round_trip() {
+	let save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	let text = json::to_string(&save).expect("Failed to convert to JSON!");
+	assert!(text.contains("\"type\": \"StructProperty\""), "{}", text);
+	assert!(text.contains("\"struct_name\": \"TestStruct\""), "{}", text);
+	assert!(text.contains("\"element_type\": \"BoolProperty\""), "{}", text);
+
+	let parsed = json::from_str(&text).expect("Failed to parse JSON!");
+	assert_eq!(parsed, save);
+	assert_eq!(parsed.to_vec().expect("Failed to write file!"), TEST_FILE);
+}
+
+#[test]
+fn json_edit() {
+	let save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	let text = json::to_string(&save).unwrap().replace("\"value\": 1251", "\"value\": 250");
+	let edited = json::from_str(&text).expect("Failed to parse JSON!");
+	let property = edited.properties.iter().find(|property| property.name == "int32_test").unwrap();
+	assert_eq!(property.value, Value::Int(250));
+	assert_eq!(Save::from_bytes(edited.to_vec().unwrap()).unwrap(), edited);
+
+	json::from_str("{\"header\": {}}").expect_err("Parsing should fail!");
+}
+
+#[test]
+fn json_unknown_bytes() {
+	let mut bytes = TEST_FILE.to_vec();
+	let start = bytes.windows(14).position(|window| window == b"UInt32Property").unwrap();
+	bytes[start..start + 6].copy_from_slice(b"UInt99");
+
+	let options = DeserializerOptions { recover: true, ..Default::default() };
+	let save = Save::from_bytes_with_options(bytes.clone(), options).expect("Failed to parse file!");
+	let text = json::to_string(&save).unwrap();
+	assert!(text.contains("\"type_name\": \"UInt99Property\""), "{}", text);
+	assert_eq!(json::from_str(&text).unwrap().to_vec().unwrap(), bytes);
+}
+
+#[test]
+fn json_special_floats() {
+	let mut save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	let floats = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY, f32::from_bits(0x7FC00001)];
+	for float in floats {
+		save.properties.push(Property { name: String::from("Float"), array_index: 0, guid: None, value: Value::Float(float) });
+	}
+	save.properties.push(Property { name: String::from("Double"), array_index: 0, guid: None, value: Value::Double(f64::NAN) });
+
+	let text = json::to_string(&save).unwrap();
+	assert!(text.contains("\"value\": \"-inf\""), "{}", text);
+	let parsed = json::from_str(&text).expect("Failed to parse JSON!");
+	assert_eq!(parsed.to_vec().unwrap(), save.to_vec().unwrap());
+	json::from_str(&text.replace("\"-inf\"", "\"-infinity\"")).expect_err("Parsing should fail!");
+}
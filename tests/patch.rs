@@ -0,0 +1,96 @@
+use serde_gvas::{patch, path};
+use serde_gvas::path::Path;
+use serde_gvas::types::FGuid;
+use serde_gvas::value::{ByteValue, Save, StructValue, Value};
+
+mod common;
+use common::{body, item, property, sample, TEST_FILE};
+
+/// Patching must give the same bytes as editing the value tree and encoding it again
+fn check_patch(target: &str, value: Value) {
+	check_patch_in(&sample(), target, value);
+}
+
+fn check_patch_in(save: &Save, target: &str, value: Value) {
+	let target: Path = target.parse().unwrap();
+	let mut input = body(save.to_vec().unwrap());
+
+	let mut edited = save.clone();
+	let expected_old = path::set(&mut edited.properties, &target, value.clone()).expect("Failed to set value!");
+	assert_eq!(patch::get(&mut input, &target).unwrap(), expected_old, "{}", target);
+
+	let position = input.position();
+	let old = patch::set(&mut input, &target, value).expect("Failed to patch value!");
+	assert_eq!(old, expected_old, "{}", target);
+	assert_eq!(input.position(), position);
+	assert_eq!(input.into_inner(), edited.to_vec().unwrap(), "{}", target);
+}
+
+#[test]
+fn patch_values() {
+	check_patch("int32_test", Value::Int(-5));
+	check_patch("test_arr[2]", Value::Bool(false));
+	check_patch("str_property", Value::Str(String::from("Longer content than before in string")));
+	check_patch("str_property", Value::Str(String::from("äöü")));
+	check_patch("test_struct.test_field", Value::UInt32(1));
+	check_patch("PlayerData.Inventory[1].ItemId", Value::Name(String::from("Greatsword")));
+	check_patch("PlayerData.Inventory[2].Count", Value::Int(2));
+	check_patch("PlayerData.Inventory[0]", Value::Struct { struct_name: String::from("Item"), guid: FGuid::new(0, 0, 0, 0), value: item("Elixir", 10) });
+	check_patch("PlayerData.Names[1]", Value::Name(String::from("Charlotte")));
+	check_patch("PlayerData.Moods[0]", Value::Byte { enum_name: String::from("None"), value: ByteValue::Label(String::from("EMood::Happy")) });
+	check_patch("PlayerData.Difficulty", Value::Enum { enum_name: String::from("EDifficulty"), value: String::from("EDifficulty::Hard") });
+	check_patch(r#"PlayerData.Quests["Quest_02"]"#, Value::Int(7));
+	check_patch("PlayerData.Checksum[1]", Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(9) });
+}
+
+#[test]
+fn patch_sets_and_maps() {
+	let mut save = sample();
+	save.properties.extend([
+		property("Seen", Value::Set { element_type: String::from("StrProperty"), removed: vec![Value::Str(String::from("Old"))], elements: vec![
+			Value::Str(String::from("Cave")), Value::Str(String::from("Town"))
+		] }),
+		property("Owners", Value::Map { key_type: String::from("IntProperty"), value_type: String::from("StructProperty"), removed: vec![Value::Int(3)], entries: vec![
+			(Value::Int(1), Value::Struct { struct_name: String::new(), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Properties(vec![property("Name", Value::Str(String::from("Alice")))]) }),
+			(Value::Int(2), Value::Struct { struct_name: String::new(), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Properties(vec![property("Name", Value::Str(String::from("Bob")))]) })
+		] })
+	]);
+	check_patch_in(&save, "Seen[1]", Value::Str(String::from("Capital city")));
+	check_patch_in(&save, "Owners[2].Name", Value::Str(String::from("Charlotte")));
+	check_patch_in(&save, "Owners[1]", Value::Struct { struct_name: String::new(), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Properties(vec![
+		property("Name", Value::Str(String::from("Someone else")))
+	]) });
+
+	let mut input = body(save.to_vec().unwrap());
+	let err = patch::get(&mut input, &"Owners[3]".parse().unwrap()).expect_err("Reading should fail!");
+	assert_eq!(err.to_string(), "No entry Owners[3]");
+	let err = patch::get(&mut input, &"Seen[2]".parse().unwrap()).expect_err("Reading should fail!");
+	assert!(err.to_string().starts_with("Index out of range in Seen[2]"), "{}", err);
+}
+
+#[test]
+fn patch_bools() {
+	let mut save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse save!");
+	save.properties.push(property("Dead", Value::Bool(false)));
+	let target: Path = "Dead".parse().unwrap();
+	let mut input = body(save.to_vec().unwrap());
+
+	assert_eq!(patch::set(&mut input, &target, Value::Bool(true)).unwrap(), Value::Bool(false));
+	assert_eq!(patch::get(&mut input, &target).unwrap(), Value::Bool(true));
+}
+
+#[test]
+fn patch_errors() {
+	let mut input = body(sample().to_vec().unwrap());
+	let original = input.get_ref().clone();
+
+	let err = patch::set(&mut input, &"int32_test".parse().unwrap(), Value::Float(1.0)).expect_err("Patching should fail!");
+	assert_eq!(err.to_string(), "Can't set int32_test of type IntProperty to FloatProperty");
+	let err = patch::get(&mut input, &"PlayerData.Missing".parse().unwrap()).expect_err("Reading should fail!");
+	assert_eq!(err.to_string(), "No property PlayerData.Missing");
+	let err = patch::get(&mut input, &"PlayerData.Inventory[3]".parse().unwrap()).expect_err("Reading should fail!");
+	assert_eq!(err.to_string(), "Index out of range in PlayerData.Inventory[3], the array has 3 elements");
+	let err = patch::get(&mut input, &"int32_test.value".parse().unwrap()).expect_err("Reading should fail!");
+	assert_eq!(err.to_string(), "Can't address int32_test.value in IntProperty");
+	assert_eq!(input.into_inner(), original);
+}
@@ -0,0 +1,167 @@
+use serde_gvas::path::{self, Path, Segment};
+use serde_gvas::types::FGuid;
+use serde_gvas::value::{ArrayValue, ByteValue, Property, Save, Value};
+
+mod common;
+use common::{item, property, sample, TEST_FILE};
+
+#[test]
+fn parse_paths() {
+	let parsed: Path = "PlayerData.Inventory[3].Count".parse().unwrap();
+	assert_eq!(parsed.segments(), &[
+		Segment::Field(String::from("PlayerData")), Segment::Field(String::from("Inventory")), Segment::Index(3), Segment::Field(String::from("Count"))
+	]);
+	let parsed: Path = r#"Flags["Quest_01"]."a.\"b\"".Scores(2)[0]"#.parse().unwrap();
+	assert_eq!(parsed.segments(), &[
+		Segment::Field(String::from("Flags")), Segment::Key(String::from("\"Quest_01\"")), Segment::Field(String::from("a.\"b\"")),
+		Segment::Slot(String::from("Scores"), 2), Segment::Index(0)
+	]);
+	assert_eq!(parsed.to_string(), r#"Flags["Quest_01"]."a.\"b\"".Scores(2)[0]"#);
+	let parsed: Path = r#""Odd]"(0)["a]" "b"]"#.parse().unwrap();
+	assert_eq!(parsed.segments(), &[Segment::Field(String::from("Odd]")), Segment::Key(String::from("\"a]\" \"b\""))]);
+
+	for invalid in ["", "Stats.", "Stats..Gold", "Items[]", "Items[0", "Flags[\"Quest]", "Flags.\"Quest", "Items[0]Count", "Scores(x)", "Scores(1"] {
+		assert!(invalid.parse::<Path>().is_err(), "{}", invalid);
+	}
+}
+
+#[test]
+fn get_and_set() {
+	let mut save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse save!");
+	assert_eq!(save.get("test_struct.test_field").unwrap(), &Value::UInt32(1234));
+	assert_eq!(save.get("test_arr[5]").unwrap(), &Value::Bool(true));
+
+	assert_eq!(save.set("test_struct.test_field", Value::UInt32(99)).unwrap(), Value::UInt32(1234));
+	*save.get_mut("int32_test").unwrap() = Value::Int(7);
+	save.set("test_arr[0]", Value::Bool(false)).unwrap();
+
+	let back = Save::from_bytes(save.to_vec().unwrap()).expect("Failed to parse edited save!");
+	assert_eq!(back.get("test_struct.test_field").unwrap(), &Value::UInt32(99));
+	assert_eq!(back.get("int32_test").unwrap(), &Value::Int(7));
+	assert_eq!(back.get("test_arr[0]").unwrap(), &Value::Bool(false));
+
+	let err = save.set("int32_test", Value::Float(1.0)).expect_err("Setting should fail!");
+	assert_eq!(err.to_string(), "Can't set int32_test of type IntProperty to FloatProperty");
+	let err = save.get("test_struct.missing").expect_err("Getting should fail!");
+	assert_eq!(err.to_string(), "No property test_struct.missing");
+	let err = save.get("test_arr[6]").expect_err("Getting should fail!");
+	assert_eq!(err.to_string(), "Index out of range in test_arr[6], the array has 6 elements");
+	let err = save.get("int32_test.value").expect_err("Getting should fail!");
+	assert_eq!(err.to_string(), "Can't address int32_test.value in IntProperty");
+}
+
+#[test]
+fn struct_arrays_and_keys() {
+	let mut properties = sample().properties;
+	let count: Path = "PlayerData.Inventory[1].Count".parse().unwrap();
+	assert_eq!(path::get(&properties, &count).unwrap(), &Value::Int(1));
+	path::set(&mut properties, &count, Value::Int(5)).unwrap();
+	assert_eq!(path::get(&properties, &count).unwrap(), &Value::Int(5));
+
+	let flag: Path = r#"PlayerData.Flags."Quest_01.Done""#.parse().unwrap();
+	path::set(&mut properties, &flag, Value::Bool(true)).unwrap();
+	assert_eq!(path::get(&properties, &flag).unwrap(), &Value::Bool(true));
+
+	let difficulty: Path = "PlayerData.Difficulty".parse().unwrap();
+	let err = path::set(&mut properties, &difficulty, Value::Enum { enum_name: String::from("EMood"), value: String::from("EMood::Happy") }).expect_err("Setting should fail!");
+	assert_eq!(err.to_string(), "Can't set PlayerData.Difficulty of type EnumProperty<EDifficulty> to EnumProperty<EMood>");
+
+	let element: Path = "PlayerData.Inventory[0]".parse().unwrap();
+	assert!(path::get(&properties, &element).is_err());
+	let old = path::set(&mut properties, &element, Value::Struct { struct_name: String::from("Item"), guid: FGuid::new(0, 0, 0, 0), value: item("Shield", 1) }).unwrap();
+	assert!(matches!(old, Value::Struct { value, .. } if value == item("Potion", 3)));
+	assert!(path::set(&mut properties, &element, Value::Int(1)).is_err());
+
+	let byte: Path = "PlayerData.Checksum[2]".parse().unwrap();
+	let old = path::set(&mut properties, &byte, Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(9) }).unwrap();
+	assert_eq!(old, Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(3) });
+}
+
+#[test]
+fn insert_and_remove() {
+	let mut properties = sample().properties;
+	let gold: Path = "PlayerData.Gold".parse().unwrap();
+	path::insert(&mut properties, &gold, Value::Int(250)).unwrap();
+	assert_eq!(path::get(&properties, &gold).unwrap(), &Value::Int(250));
+	assert!(path::insert(&mut properties, &gold, Value::Int(1)).is_err());
+	assert_eq!(path::remove(&mut properties, &gold).unwrap(), Value::Int(250));
+	assert!(path::get(&properties, &gold).is_err());
+
+	let first: Path = "PlayerData.Inventory[0]".parse().unwrap();
+	path::insert(&mut properties, &first, Value::Struct { struct_name: String::from("Item"), guid: FGuid::new(0, 0, 0, 0), value: item("Key", 1) }).unwrap();
+	assert_eq!(path::get(&properties, &"PlayerData.Inventory[0].ItemId".parse().unwrap()).unwrap(), &Value::Name(String::from("Key")));
+	let end: Path = "PlayerData.Inventory[5]".parse().unwrap();
+	let err = path::insert(&mut properties, &end, Value::Struct { struct_name: String::from("Item"), guid: FGuid::new(0, 0, 0, 0), value: item("Key", 1) }).expect_err("Inserting should fail!");
+	assert_eq!(err.to_string(), "Index out of range in PlayerData.Inventory[5], the array has 4 elements");
+	let err = path::insert(&mut properties, &first, Value::Struct { struct_name: String::from("Weapon"), guid: FGuid::new(0, 0, 0, 0), value: item("Axe", 1) }).expect_err("Inserting should fail!");
+	assert_eq!(err.to_string(), "Can't set PlayerData.Inventory[0] of type StructProperty<Item> to StructProperty<Weapon>");
+
+	let removed = path::remove(&mut properties, &"PlayerData.Inventory[2]".parse().unwrap()).unwrap();
+	assert!(matches!(removed, Value::Struct { struct_name, value, .. } if struct_name == "Item" && value == item("Sword", 1)));
+	let removed = path::remove(&mut properties, &"PlayerData.Checksum[0]".parse().unwrap()).unwrap();
+	assert_eq!(removed, Value::Byte { enum_name: String::from("None"), value: ByteValue::Byte(1) });
+	assert_eq!(path::get(&properties, &"PlayerData.Checksum".parse().unwrap()).unwrap(), &Value::Array {
+		element_type: String::from("ByteProperty"), value: ArrayValue::Bytes(vec![2, 3])
+	});
+}
+
+#[test]
+fn slots_and_map_keys() {
+	let mut properties = sample().properties;
+	properties.push(property("Scores", Value::Int(1)));
+	properties.push(Property { array_index: 1, ..property("Scores", Value::Int(2)) });
+	assert_eq!(path::get(&properties, &"Scores".parse().unwrap()).unwrap(), &Value::Int(1));
+	let slot: Path = "Scores(1)".parse().unwrap();
+	assert_eq!(path::set(&mut properties, &slot, Value::Int(3)).unwrap(), Value::Int(2));
+	assert_eq!(path::get(&properties, &slot).unwrap(), &Value::Int(3));
+	path::insert(&mut properties, &"Scores(2)".parse().unwrap(), Value::Int(4)).unwrap();
+	assert_eq!(properties.last().unwrap().array_index, 2);
+	assert_eq!(path::remove(&mut properties, &slot).unwrap(), Value::Int(3));
+	assert_eq!(path::get(&properties, &slot).expect_err("Getting should fail!").to_string(), "No property Scores(1)");
+
+	let quest: Path = r#"PlayerData.Quests["Quest_02"]"#.parse().unwrap();
+	assert_eq!(path::set(&mut properties, &quest, Value::Int(5)).unwrap(), Value::Int(0));
+	assert_eq!(path::get(&properties, &quest).unwrap(), &Value::Int(5));
+	let err = path::set(&mut properties, &quest, Value::Bool(true)).expect_err("Setting should fail!");
+	assert_eq!(err.to_string(), r#"Can't set PlayerData.Quests["Quest_02"] of type IntProperty to BoolProperty"#);
+	let err = path::get(&properties, &r#"PlayerData.Quests["Quest_03"]"#.parse().unwrap()).expect_err("Getting should fail!");
+	assert_eq!(err.to_string(), r#"No entry PlayerData.Quests["Quest_03"]"#);
+	assert!(path::get(&properties, &"PlayerData.Quests[Quest_01]".parse().unwrap()).is_err());
+
+	let new: Path = r#"PlayerData.Quests["Quest_03"]"#.parse().unwrap();
+	path::insert(&mut properties, &new, Value::Int(2)).unwrap();
+	assert!(path::insert(&mut properties, &new, Value::Int(2)).is_err());
+	assert_eq!(path::remove(&mut properties, &"PlayerData.Quests[\"Quest_01\"]".parse().unwrap()).unwrap(), Value::Int(1));
+	assert_eq!(path::get(&properties, &"PlayerData.Quests".parse().unwrap()).unwrap(), &Value::Map {
+		key_type: String::from("StrProperty"), value_type: String::from("IntProperty"), removed: Vec::new(), entries: vec![
+			(Value::Str(String::from("Quest_02")), Value::Int(5)), (Value::Str(String::from("Quest_03")), Value::Int(2))
+		]
+	});
+
+	// set elements are addressed by their position
+	let mut seen = vec![property("Seen", Value::Set { element_type: String::from("NameProperty"), removed: Vec::new(), elements: vec![Value::Name(String::from("Cave"))] })];
+	path::insert(&mut seen, &"Seen[1]".parse().unwrap(), Value::Name(String::from("Town"))).unwrap();
+	assert!(path::insert(&mut seen, &"Seen[0]".parse().unwrap(), Value::Int(1)).is_err());
+	assert_eq!(path::remove(&mut seen, &"Seen[0]".parse().unwrap()).unwrap(), Value::Name(String::from("Cave")));
+	assert_eq!(path::get(&seen, &"Seen[0]".parse().unwrap()).unwrap(), &Value::Name(String::from("Town")));
+
+	// integer keys fit in brackets like indices
+	let mut counts = vec![property("Counts", Value::Map { key_type: String::from("IntProperty"), value_type: String::from("IntProperty"), removed: Vec::new(), entries: vec![
+		(Value::Int(7), Value::Int(1)), (Value::Int(-1), Value::Int(2))
+	] })];
+	assert_eq!(path::get(&counts, &"Counts[7]".parse().unwrap()).unwrap(), &Value::Int(1));
+	assert_eq!(path::set(&mut counts, &"Counts[-1]".parse().unwrap(), Value::Int(3)).unwrap(), Value::Int(2));
+}
+
+#[test]
+fn parse_values() {
+	let difficulty = Value::Enum { enum_name: String::from("EDifficulty"), value: String::from("EDifficulty::Easy") };
+	assert_eq!(path::parse_value(&difficulty, "Hard").unwrap(), Value::Enum { enum_name: String::from("EDifficulty"), value: String::from("EDifficulty::Hard") });
+	assert_eq!(path::parse_value(&Value::Int(0), " 42").unwrap(), Value::Int(42));
+	assert_eq!(path::parse_value(&Value::Name(String::new()), "Level_01").unwrap(), Value::Name(String::from("Level_01")));
+	assert!(path::parse_value(&Value::UInt16(0), "-1").is_err());
+	assert!(path::parse_value(&Value::Bool(false), "yes").is_err());
+	let icon = Value::SoftObject([String::new(), String::new()]);
+	assert_eq!(path::parse_value(&icon, "\"/Game/Map\" \"\"").unwrap(), Value::SoftObject([String::from("/Game/Map"), String::new()]));
+	assert!(path::parse_value(&icon, "/Game/Map").is_err());
+}
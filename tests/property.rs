@@ -0,0 +1,71 @@
+use std::io::Cursor;
+
+use serde::Deserialize;
+use serde_gvas::de::Deserializer;
+use serde_gvas::property::{BoolProperty, ByteProperty, EnumProperty, IntProperty, PropertyCodec, PropertyType, SoftObjectProperty, Tag};
+use serde_gvas::types::FGuid;
+use serde_gvas::value::ByteValue;
+
+mod common;
+use common::fstring;
+
+#[test]
+fn property_type_names() {
+	for name in ["BoolProperty", "IntProperty", "SoftObjectProperty", "StructProperty", "MapProperty"] {
+		assert_eq!(PropertyType::from_name(name).name(), name);
+	}
+	assert_eq!(PropertyType::from_name("UInt32Property"), PropertyType::UInt32);
+	assert_eq!(PropertyType::from_name("TextProperty"), PropertyType::Other(String::from("TextProperty")));
+	assert_eq!(IntProperty::TYPE.to_string(), "IntProperty");
+}
+
+#[test]
+fn codecs_round_trip() {
+	let mut output = Vec::new();
+	IntProperty::write_element(&mut output, &-7).unwrap();
+	ByteProperty::write_element(&mut output, &ByteValue::Label(String::from("EColor::Red"))).unwrap();
+	SoftObjectProperty::write_element(&mut output, &[String::from("/Game/Map"), String::new()]).unwrap();
+	Tag::Enum(String::from("EColor")).write(&mut output).unwrap();
+	Tag::Struct(String::from("Vector"), FGuid::new(1, 2, 3, 4)).write(&mut output).unwrap();
+
+	let mut cursor = Cursor::new(output);
+	let mut de = Deserializer::from_bytes(&mut cursor);
+	assert_eq!(IntProperty::read_element(&mut de).unwrap(), -7);
+	let tag = Tag::Enum(String::from("EColor"));
+	assert_eq!(ByteProperty::read_value(&mut de, &tag).unwrap(), ByteValue::Label(String::from("EColor::Red")));
+	assert_eq!(SoftObjectProperty::read_element(&mut de).unwrap(), [String::from("/Game/Map"), String::new()]);
+	assert_eq!(EnumProperty::read_tag(&mut de).unwrap(), tag);
+	assert_eq!(PropertyType::Struct.read_tag(&mut de).unwrap(), Tag::Struct(String::from("Vector"), FGuid::new(1, 2, 3, 4)));
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Flags {
+	alive: bool,
+	flags: Vec<bool>
+}
+
+#[test]
+fn bools_with_guid() {
+	// a bool property with a guid, the tagged value and array elements must agree
+	let mut bytes = fstring("alive");
+	bytes.extend(fstring("BoolProperty"));
+	bytes.extend([0; 8]);
+	bytes.extend([0, 1]);
+	bytes.extend([0xAB; 16]);
+	bytes.extend(fstring("flags"));
+	bytes.extend(fstring("ArrayProperty"));
+	bytes.extend(6i32.to_le_bytes());
+	bytes.extend([0; 4]);
+	bytes.extend(fstring("BoolProperty"));
+	bytes.push(0);
+	bytes.extend(2i32.to_le_bytes());
+	bytes.extend([0, 1]);
+	bytes.extend(fstring("None"));
+
+	let flags: Flags = serde_gvas::from_bytes(&mut Cursor::new(bytes)).expect("Failed to parse flags!");
+	assert_eq!(flags, Flags { alive: false, flags: vec![false, true] });
+
+	let mut output = Vec::new();
+	BoolProperty::write_value(&mut output, &true).unwrap();
+	assert!(output.is_empty());
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use serde::{Deserialize, Serialize};
+use serde_gvas::{patch, path, DeserializerOptions, Error, ErrorCode};
+use serde_gvas::structs::{Item, StructRegistry};
+use serde_gvas::types::FGuid;
+use serde_gvas::value::{self, ArrayValue, Property, StructValue, Value};
+
+mod common;
+use common::{fstring, property};
+
+/// An id followed by a count, without tags
+fn register(struct_name: &str) -> StructRegistry {
+	let mut structs = StructRegistry::new();
+	structs.register(struct_name, |input| {
+		let id = input.read_u32::<LittleEndian>()?;
+		let count = input.read_u16::<LittleEndian>()?;
+		Ok(vec![property("Id", Value::UInt32(id)), property("Count", Value::UInt16(count))])
+	}, |fields, output| {
+		match fields {
+			[Property { value: Value::UInt32(id), .. }, Property { value: Value::UInt16(count), .. }] => {
+				output.write_u32::<LittleEndian>(*id)?;
+				output.write_u16::<LittleEndian>(*count)?;
+				Ok(())
+			},
+			_ => Err(Error::make_data(String::from("Record needs an Id and a Count")))
+		}
+	});
+	structs
+}
+
+fn options(structs: &StructRegistry) -> DeserializerOptions {
+	DeserializerOptions { structs: structs.clone(), ..Default::default() }
+}
+
+fn record(id: u32, count: u16) -> Vec<u8> {
+	let mut bytes = id.to_le_bytes().to_vec();
+	bytes.extend(count.to_le_bytes());
+	bytes
+}
+
+/// `item` holding one record and `items` holding two
+fn save(struct_name: &str) -> Vec<u8> {
+	let mut bytes = fstring("item");
+	bytes.extend(fstring("StructProperty"));
+	bytes.extend(6i64.to_le_bytes());
+	bytes.extend(fstring(struct_name));
+	bytes.extend([0; 17]);
+	bytes.extend(record(7, 3));
+
+	let mut inner = fstring("items");
+	inner.extend(fstring("StructProperty"));
+	inner.extend(12i64.to_le_bytes());
+	inner.extend(fstring(struct_name));
+	inner.extend([0; 17]);
+	bytes.extend(fstring("items"));
+	bytes.extend(fstring("ArrayProperty"));
+	bytes.extend((4 + inner.len() as i64 + 12).to_le_bytes());
+	bytes.extend(fstring("StructProperty"));
+	bytes.push(0);
+	bytes.extend(2i32.to_le_bytes());
+	bytes.extend(inner);
+	bytes.extend(record(1, 10));
+	bytes.extend(record(2, 20));
+	bytes.extend(fstring("None"));
+	bytes
+}
+
+#[test]
+fn registered_value_round_trip() {
+	let mut structs = register("TreeRecord");
+	let bytes = save("TreeRecord");
+	let (properties, _) = value::from_bytes_with_diagnostics(&mut Cursor::new(bytes.clone()), options(&structs)).expect("Failed to parse records!");
+	assert_eq!(properties[0].value, Value::Struct {
+		struct_name: String::from("TreeRecord"),
+		guid: FGuid::new(0, 0, 0, 0),
+		value: StructValue::Properties(vec![property("Id", Value::UInt32(7)), property("Count", Value::UInt16(3))])
+	});
+	match &properties[1].value {
+		Value::Array { value: ArrayValue::Structs { elements, .. }, .. } => {
+			assert_eq!(elements[1], StructValue::Properties(vec![property("Id", Value::UInt32(2)), property("Count", Value::UInt16(20))]));
+		},
+		other => panic!("Expected a struct array, got {:?}", other)
+	}
+
+	let mut output = Vec::new();
+	value::to_writer_with_structs(&mut output, &properties, &structs).unwrap();
+	assert_eq!(output[..bytes.len()], bytes);
+	// without the registry the fields are written as tagged properties
+	assert_ne!(value::to_vec(&properties).unwrap()[..bytes.len()], bytes);
+
+	let mut properties = properties;
+	properties[0].value = Value::Struct { struct_name: String::from("TreeRecord"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::DateTime(0) };
+	assert!(value::to_vec_with_structs(&properties, &structs).is_err());
+
+	assert!(structs.unregister("TreeRecord"));
+	assert!(!structs.unregister("TreeRecord"));
+	assert!(value::from_bytes_with_diagnostics(&mut Cursor::new(bytes.clone()), options(&structs)).is_err());
+	assert!(value::from_bytes(&mut Cursor::new(bytes)).is_err());
+}
+
+#[test]
+fn registered_patch() {
+	// fields of registered structs are patched by encoding the whole struct again, like the value tree does
+	let structs = register("PatchRecord");
+	let options = options(&structs);
+	let mut input = Cursor::new(save("PatchRecord"));
+	let (mut properties, _) = value::from_bytes_with_diagnostics(&mut Cursor::new(input.get_ref().clone()), options.clone()).expect("Failed to parse records!");
+	for (target, value) in [("item.Count", Value::UInt16(5)), ("items[1].Id", Value::UInt32(9))] {
+		let target: path::Path = target.parse().unwrap();
+		let old = path::set(&mut properties, &target, value.clone()).unwrap();
+		assert_eq!(patch::set_with_options(&mut input, &target, value.clone(), &options).unwrap(), old);
+		assert_eq!(patch::get_with_options(&mut input, &target, &options).unwrap(), value);
+	}
+	let expected = value::to_vec_with_structs(&properties, &structs).unwrap();
+	assert_eq!(input.get_ref()[..], expected[..input.get_ref().len()]);
+	assert!(patch::set_with_options(&mut input, &"item.Count".parse().unwrap(), Value::Int(1), &options).is_err());
+	assert!(patch::get(&mut input, &"item.Count".parse().unwrap()).is_err());
+}
+
+/// `Stash` mapping ints to records and `Records` holding a set of them, nothing in them names the struct
+fn collections() -> Vec<u8> {
+	let mut stash = vec![0; 4];
+	stash.extend(2i32.to_le_bytes());
+	for (key, id, count) in [(1i32, 1, 10), (2, 2, 20)] {
+		stash.extend(key.to_le_bytes());
+		stash.extend(record(id, count));
+	}
+	let mut bytes = fstring("Stash");
+	bytes.extend(fstring("MapProperty"));
+	bytes.extend((stash.len() as i64).to_le_bytes());
+	bytes.extend(fstring("IntProperty"));
+	bytes.extend(fstring("StructProperty"));
+	bytes.push(0);
+	bytes.extend(stash);
+
+	let mut records = vec![0; 4];
+	records.extend(1i32.to_le_bytes());
+	records.extend(record(3, 30));
+	bytes.extend(fstring("Records"));
+	bytes.extend(fstring("SetProperty"));
+	bytes.extend((records.len() as i64).to_le_bytes());
+	bytes.extend(fstring("StructProperty"));
+	bytes.push(0);
+	bytes.extend(records);
+	bytes.extend(fstring("None"));
+	bytes
+}
+
+#[test]
+fn registered_items() {
+	let mut structs = register("ItemRecord");
+	structs.register_item("Stash", Item::Value, "ItemRecord");
+	structs.register_item("Records", Item::Element, "ItemRecord");
+	let options = options(&structs);
+	let bytes = collections();
+
+	let (properties, _) = value::from_bytes_with_diagnostics(&mut Cursor::new(bytes.clone()), options.clone()).expect("Failed to parse collections!");
+	let record = |id, count| Value::Struct {
+		struct_name: String::from("ItemRecord"),
+		guid: FGuid::new(0, 0, 0, 0),
+		value: StructValue::Properties(vec![property("Id", Value::UInt32(id)), property("Count", Value::UInt16(count))])
+	};
+	match &properties[0].value {
+		Value::Map { entries, .. } => assert_eq!(entries[1], (Value::Int(2), record(2, 20))),
+		other => panic!("Expected a map, got {:?}", other)
+	}
+	match &properties[1].value {
+		Value::Set { elements, .. } => assert_eq!(elements, &[record(3, 30)]),
+		other => panic!("Expected a set, got {:?}", other)
+	}
+	assert_eq!(value::to_vec_with_structs(&properties, &structs).unwrap()[..bytes.len()], bytes);
+	// unregistered the records don't read as properties or a guid
+	assert!(value::from_bytes(&mut Cursor::new(bytes.clone())).is_err());
+
+	let mut input = Cursor::new(bytes);
+	let target: path::Path = "Stash[2].Count".parse().unwrap();
+	assert_eq!(patch::set_with_options(&mut input, &target, Value::UInt16(21), &options).unwrap(), Value::UInt16(20));
+	assert_eq!(patch::get_with_options(&mut input, &"Records[0].Id".parse().unwrap(), &options).unwrap(), Value::UInt32(3));
+
+	#[derive(Deserialize, Debug, PartialEq)]
+	struct Collections {
+		#[serde(rename = "Stash")]
+		stash: HashMap<i32, SerdeRecord>,
+		#[serde(rename = "Records")]
+		records: Vec<SerdeRecord>
+	}
+	let collections: Collections = serde_gvas::from_bytes_with_options(&mut input, options).expect("Failed to parse collections!");
+	assert_eq!(collections, Collections {
+		stash: HashMap::from([(1, SerdeRecord { id: 1, count: 10 }), (2, SerdeRecord { id: 2, count: 21 })]),
+		records: vec![SerdeRecord { id: 3, count: 30 }]
+	});
+}
+
+#[test]
+fn unregistered_items() {
+	// records are neither properties nor guids, recovering keeps their bytes
+	let bytes = collections();
+	let err = value::from_bytes(&mut Cursor::new(bytes.clone())).expect_err("Records aren't guids");
+	assert!(matches!(err.code(), ErrorCode::UnsupportedStruct(name) if &**name == "Stash"));
+
+	let options = DeserializerOptions { recover: true, ..Default::default() };
+	let (properties, diagnostics) = value::from_bytes_with_diagnostics(&mut Cursor::new(bytes.clone()), options).expect("Failed to parse collections!");
+	assert_eq!(diagnostics.len(), 2);
+	assert!(properties.iter().all(|property| matches!(property.value, Value::Unknown { .. })));
+	assert_eq!(value::to_vec(&properties).unwrap()[..bytes.len()], bytes);
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+struct SerdeRecord {
+	#[serde(rename = "Id")]
+	id: u32,
+	#[serde(rename = "Count")]
+	count: u16
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+struct Inventory {
+	item: SerdeRecord,
+	items: Vec<SerdeRecord>
+}
+
+#[test]
+fn registered_serde_round_trip() {
+	let structs = register("SerdeRecord");
+	let mut bytes = save("SerdeRecord");
+	let inventory: Inventory = serde_gvas::from_bytes_with_options(&mut Cursor::new(bytes.clone()), options(&structs)).expect("Failed to parse inventory!");
+	assert_eq!(inventory, Inventory {
+		item: SerdeRecord { id: 7, count: 3 },
+		items: vec![SerdeRecord { id: 1, count: 10 }, SerdeRecord { id: 2, count: 20 }]
+	});
+
+	bytes.extend([0; 4]);
+	assert_eq!(serde_gvas::to_vec_with_structs(&inventory, &structs).unwrap(), bytes);
+}
+
+#[test]
+fn registered_serde_fields() {
+	#[derive(Serialize)]
+	struct Entry {
+		#[serde(rename = "Name")]
+		name: serde_gvas::types::FName,
+		#[serde(rename = "Saved")]
+		saved: serde_gvas::types::FDateTime,
+		#[serde(rename = "Data")]
+		data: Vec<u8>,
+		#[serde(rename = "Note")]
+		note: Option<String>
+	}
+
+	#[derive(Serialize)]
+	struct Log {
+		entry: Entry
+	}
+
+	// the encoder gets the fields as values, the same as the decoder hands them out, once while measuring and once writing
+	let received = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+	let mut structs = StructRegistry::new();
+	let sink = received.clone();
+	structs.register("Entry", |_| Ok(Vec::new()), move |fields, _| {
+		*sink.lock().unwrap() = fields.to_vec();
+		Ok(())
+	});
+
+	let log = Log { entry: Entry { name: serde_gvas::types::FName(String::from("Start")), saved: serde_gvas::types::FDateTime(5), data: vec![1, 2], note: None } };
+	serde_gvas::to_vec_with_structs(&log, &structs).expect("Failed to serialize log!");
+	assert_eq!(*received.lock().unwrap(), vec![
+		property("Name", Value::Name(String::from("Start"))),
+		property("Saved", Value::Struct { struct_name: String::from("DateTime"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::DateTime(5) }),
+		property("Data", Value::Array { element_type: String::from("ByteProperty"), value: ArrayValue::Bytes(vec![1, 2]) })
+	]);
+}
@@ -1,171 +1,15 @@
 use std::io::Cursor;
 
-use serde::Deserialize;
-use serde_gvas::header::{self, de, ser};
+use serde::{Deserialize, Serialize};
+use serde_gvas::{DeserializerOptions, ErrorCode};
+use serde_gvas::header;
+use serde_gvas::custom_version::{self, FEditorObjectVersion, FReleaseObjectVersion, FUE5MainStreamObjectVersion};
+use serde_gvas::engine_version::EngineVersion;
 use serde_gvas::types::{FEngineVersion, GvasHeader};
+use serde_gvas::value::Save;
 
-// Test file bytes
-// This file contains unreal header, as well as some properties for testing
-const TEST_FILE: [u8; 1896] = [
-	0x47, 0x56, 0x41, 0x53, 0x02, 0x00, 0x00, 0x00, 0x06, 0x02, 0x00, 0x00,
-	0x04, 0x00, 0x19, 0x00, 0x04, 0x00, 0x1D, 0xCA, 0xDC, 0x00, 0x13, 0x00,
-	0x00, 0x00, 0x2B, 0x2B, 0x55, 0x45, 0x34, 0x2B, 0x52, 0x65, 0x6C, 0x65,
-	0x61, 0x73, 0x65, 0x2D, 0x34, 0x2E, 0x32, 0x35, 0x00, 0x03, 0x00, 0x00,
-	0x00, 0x35, 0x00, 0x00, 0x00, 0x22, 0xD5, 0x54, 0x9C, 0xBE, 0x4F, 0x26,
-	0xA8, 0x46, 0x07, 0x21, 0x94, 0xD0, 0x82, 0xB4, 0x61, 0x1E, 0x00, 0x00,
-	0x00, 0xE4, 0x32, 0xD8, 0xB0, 0x0D, 0x4F, 0x89, 0x1F, 0xB7, 0x7E, 0xCF,
-	0xAC, 0xA2, 0x4A, 0xFD, 0x36, 0x0A, 0x00, 0x00, 0x00, 0x28, 0x43, 0xC6,
-	0xE1, 0x53, 0x4D, 0x2C, 0xA2, 0x86, 0x8E, 0x6C, 0xA3, 0x8C, 0xBD, 0x17,
-	0x64, 0x00, 0x00, 0x00, 0x00, 0x3C, 0xC1, 0x5E, 0x37, 0xFB, 0x48, 0xE4,
-	0x06, 0xF0, 0x84, 0x00, 0xB5, 0x7E, 0x71, 0x2A, 0x26, 0x04, 0x00, 0x00,
-	0x00, 0xED, 0x68, 0xB0, 0xE4, 0xE9, 0x42, 0x94, 0xF4, 0x0B, 0xDA, 0x31,
-	0xA2, 0x41, 0xBB, 0x46, 0x2E, 0x26, 0x00, 0x00, 0x00, 0x3F, 0x74, 0xFC,
-	0xCF, 0x80, 0x44, 0xB0, 0x43, 0xDF, 0x14, 0x91, 0x93, 0x73, 0x20, 0x1D,
-	0x17, 0x25, 0x00, 0x00, 0x00, 0xB5, 0x49, 0x2B, 0xB0, 0xE9, 0x44, 0x20,
-	0xBB, 0xB7, 0x32, 0x04, 0xA3, 0x60, 0x03, 0xE4, 0x52, 0x02, 0x00, 0x00,
-	0x00, 0x5C, 0x10, 0xE4, 0xA4, 0xB5, 0x49, 0xA1, 0x59, 0xC4, 0x40, 0xC5,
-	0xA7, 0xEE, 0xDF, 0x7E, 0x54, 0x00, 0x00, 0x00, 0x00, 0xC9, 0x31, 0xC8,
-	0x39, 0xDC, 0x47, 0xE6, 0x5A, 0x17, 0x9C, 0x44, 0x9A, 0x7C, 0x8E, 0x1C,
-	0x3E, 0x00, 0x00, 0x00, 0x00, 0x33, 0x1B, 0xF0, 0x78, 0x98, 0x4F, 0xEA,
-	0xEB, 0xEA, 0x84, 0xB4, 0xB9, 0xA2, 0x5A, 0xB9, 0xCC, 0x04, 0x00, 0x00,
-	0x00, 0x0F, 0x38, 0x31, 0x66, 0xE0, 0x43, 0x4D, 0x2D, 0x27, 0xCF, 0x09,
-	0x80, 0x5A, 0xA9, 0x56, 0x69, 0x00, 0x00, 0x00, 0x00, 0x9F, 0x8B, 0xF8,
-	0x12, 0xFC, 0x4A, 0x75, 0x88, 0x0C, 0xD9, 0x7C, 0xA6, 0x29, 0xBD, 0x3A,
-	0x38, 0x2B, 0x00, 0x00, 0x00, 0x4C, 0xE7, 0x5A, 0x7B, 0x10, 0x4C, 0x70,
-	0xD2, 0x98, 0x57, 0x58, 0xA9, 0x5A, 0x2A, 0x21, 0x0B, 0x0C, 0x00, 0x00,
-	0x00, 0x18, 0x69, 0x29, 0xD7, 0xDD, 0x4B, 0xD6, 0x1D, 0xA8, 0x64, 0xE2,
-	0x9D, 0x84, 0x38, 0xC1, 0x3C, 0x03, 0x00, 0x00, 0x00, 0x78, 0x52, 0xA1,
-	0xC2, 0xFE, 0x4A, 0xE7, 0xBF, 0xFF, 0x90, 0x17, 0x6C, 0x55, 0xF7, 0x1D,
-	0x53, 0x01, 0x00, 0x00, 0x00, 0xD4, 0xA3, 0xAC, 0x6E, 0xC1, 0x4C, 0xEC,
-	0x40, 0xED, 0x8B, 0x86, 0xB7, 0xC5, 0x8F, 0x42, 0x09, 0x03, 0x00, 0x00,
-	0x00, 0xDD, 0x75, 0xE5, 0x29, 0x27, 0x46, 0xA3, 0xE0, 0x76, 0xD2, 0x10,
-	0x9D, 0xEA, 0xDC, 0x2C, 0x23, 0x11, 0x00, 0x00, 0x00, 0x5D, 0xA6, 0x43,
-	0xAF, 0x47, 0x49, 0xD3, 0x7F, 0x8E, 0x3E, 0x73, 0x98, 0x05, 0xBB, 0xC1,
-	0xD9, 0x07, 0x00, 0x00, 0x00, 0xEC, 0x6C, 0x26, 0x6B, 0x8F, 0x4B, 0xC7,
-	0x1E, 0xD9, 0xE4, 0x0B, 0xA3, 0x07, 0xFC, 0x42, 0x09, 0x01, 0x00, 0x00,
-	0x00, 0x61, 0x3D, 0xF7, 0x0D, 0xEA, 0x47, 0x3F, 0xA2, 0xE9, 0x89, 0x27,
-	0xB7, 0x9A, 0x49, 0x41, 0x0C, 0x01, 0x00, 0x00, 0x00, 0x86, 0x18, 0x1D,
-	0x60, 0x84, 0x4F, 0x64, 0xAC, 0xDE, 0xD3, 0x16, 0xAA, 0xD6, 0xC7, 0xEA,
-	0x0D, 0x1F, 0x00, 0x00, 0x00, 0xD6, 0xBC, 0xFF, 0x9D, 0x58, 0x01, 0x4F,
-	0x49, 0x82, 0x12, 0x21, 0xE2, 0x88, 0xA8, 0x92, 0x3C, 0x0A, 0x00, 0x00,
-	0x00, 0xAC, 0xD0, 0xAE, 0xF2, 0x6F, 0x41, 0xFE, 0x9A, 0x7F, 0xAA, 0x64,
-	0x86, 0xFC, 0xD6, 0x26, 0xFA, 0x01, 0x00, 0x00, 0x00, 0x0B, 0x1F, 0x4F,
-	0x17, 0xA5, 0x45, 0xC6, 0xB4, 0xE8, 0x2E, 0x3F, 0xB1, 0x7D, 0x91, 0xFB,
-	0xD0, 0x0A, 0x00, 0x00, 0x00, 0x83, 0x4A, 0xF9, 0x35, 0x6C, 0x40, 0x58,
-	0xE2, 0xF5, 0x09, 0x18, 0xA3, 0x7C, 0x24, 0x10, 0x96, 0x25, 0x00, 0x00,
-	0x00, 0x6E, 0xC1, 0x8F, 0xB6, 0xE2, 0x42, 0x1B, 0x8B, 0x5C, 0x21, 0x53,
-	0xB4, 0xFE, 0x44, 0x88, 0x05, 0x01, 0x00, 0x00, 0x00, 0x06, 0x85, 0xE1,
-	0xB2, 0xC2, 0xCF, 0x73, 0x42, 0xBB, 0xF4, 0x4E, 0xA5, 0x07, 0xBA, 0x8B,
-	0x75, 0x01, 0x00, 0x00, 0x00, 0x50, 0x32, 0x68, 0x54, 0xAF, 0x48, 0x99,
-	0x80, 0x96, 0x98, 0xC8, 0x8B, 0xB7, 0xF9, 0xAD, 0xFB, 0x00, 0x00, 0x00,
-	0x00, 0xE7, 0x9E, 0x7F, 0x71, 0x3A, 0x49, 0xB0, 0xE9, 0x32, 0x91, 0xB3,
-	0x88, 0x07, 0x81, 0x38, 0x1B, 0x06, 0x00, 0x00, 0x00, 0xB3, 0xDC, 0x7D,
-	0x8E, 0xBB, 0x47, 0xDA, 0x80, 0xA2, 0x46, 0xD3, 0x9F, 0xF6, 0x4D, 0x98,
-	0x93, 0x01, 0x00, 0x00, 0x00, 0xCD, 0xB0, 0x8A, 0xCB, 0xDE, 0x4B, 0x8C,
-	0xE7, 0x93, 0x13, 0x62, 0xA8, 0x62, 0xEF, 0xE9, 0x14, 0x00, 0x00, 0x00,
-	0x00, 0x96, 0x51, 0x96, 0xAB, 0xFC, 0x08, 0xD8, 0x45, 0x8D, 0x22, 0xD7,
-	0xB7, 0x9E, 0x56, 0xAD, 0x78, 0x01, 0x00, 0x00, 0x00, 0x0E, 0xB7, 0x50,
-	0x99, 0x17, 0x4E, 0x1A, 0xB4, 0x0D, 0xFA, 0xCC, 0xBB, 0xD6, 0x7F, 0x81,
-	0x57, 0x01, 0x00, 0x00, 0x00, 0xF2, 0x0A, 0x68, 0xFB, 0xA3, 0x4B, 0xEF,
-	0x59, 0xB5, 0x19, 0xA8, 0xBA, 0x3D, 0x44, 0xC8, 0x73, 0x02, 0x00, 0x00,
-	0x00, 0x91, 0x86, 0xE0, 0xAF, 0x52, 0x49, 0x0D, 0x3A, 0x3B, 0x67, 0x73,
-	0xB6, 0x1E, 0x2D, 0xF2, 0x7C, 0x02, 0x00, 0x00, 0x00, 0xBD, 0xFD, 0xB5,
-	0x2E, 0x10, 0x4D, 0xAC, 0x01, 0x8F, 0xF3, 0x36, 0x81, 0xDA, 0xA5, 0x93,
-	0x33, 0x05, 0x00, 0x00, 0x00, 0x4F, 0x35, 0x9D, 0x50, 0x2F, 0x49, 0xE6,
-	0xF6, 0xB2, 0x85, 0x49, 0xA7, 0x1C, 0x63, 0x3C, 0x07, 0x00, 0x00, 0x00,
-	0x00, 0xEA, 0xB7, 0x62, 0xA4, 0x3A, 0x4E, 0x99, 0xF4, 0x1F, 0xEC, 0xC1,
-	0x99, 0xB2, 0xE1, 0x24, 0x82, 0x02, 0x00, 0x00, 0x00, 0x19, 0x4D, 0x0C,
-	0x43, 0x70, 0x49, 0x54, 0x71, 0x69, 0x9B, 0x69, 0x87, 0xE5, 0xB0, 0x90,
-	0xDF, 0x0E, 0x00, 0x00, 0x00, 0xBD, 0x32, 0xFE, 0xAA, 0x14, 0x4C, 0x95,
-	0x53, 0x25, 0x5E, 0x6A, 0xB6, 0xDD, 0xD1, 0x32, 0x10, 0x01, 0x00, 0x00,
-	0x00, 0x8E, 0xE1, 0xAF, 0x23, 0x58, 0x4E, 0xE1, 0x4C, 0x52, 0xC2, 0x61,
-	0x8D, 0xB7, 0xBE, 0x53, 0xB9, 0x0B, 0x00, 0x00, 0x00, 0x40, 0xEB, 0x56,
-	0x4A, 0xDC, 0x11, 0xF5, 0x10, 0x7E, 0x34, 0xD3, 0x92, 0xE7, 0x6A, 0xC9,
-	0xB2, 0x02, 0x00, 0x00, 0x00, 0x00, 0x4A, 0x8A, 0xD7, 0x97, 0x46, 0x58,
-	0xE8, 0xB5, 0x19, 0xA8, 0xBA, 0xB4, 0x46, 0x7D, 0x48, 0x11, 0x00, 0x00,
-	0x00, 0x86, 0xF8, 0x79, 0x55, 0x1F, 0x4C, 0x3A, 0x93, 0x7B, 0x08, 0xBA,
-	0x83, 0x2F, 0xB9, 0x61, 0x63, 0x01, 0x00, 0x00, 0x00, 0x52, 0xBE, 0x2F,
-	0x61, 0x0B, 0x40, 0x53, 0xDA, 0x91, 0x4F, 0x0D, 0x91, 0x7C, 0x85, 0xB1,
-	0x9F, 0x01, 0x00, 0x00, 0x00, 0x36, 0x7A, 0x23, 0xA4, 0xC9, 0x41, 0xEA,
-	0xCA, 0xF8, 0x18, 0xA2, 0x8F, 0xF3, 0x1B, 0x68, 0x58, 0x04, 0x00, 0x00,
-	0x00, 0x75, 0x3F, 0x4E, 0x80, 0x49, 0x4B, 0x88, 0x70, 0x06, 0x8C, 0xD6,
-	0xA4, 0xDC, 0xB6, 0x7E, 0x3C, 0x05, 0x00, 0x00, 0x00, 0xF3, 0x7A, 0xBB,
-	0x24, 0x83, 0x4F, 0x46, 0x56, 0xC2, 0x2D, 0x2F, 0x1F, 0xFF, 0x96, 0xAD,
-	0x49, 0x05, 0x00, 0x00, 0x00, 0xFA, 0x7A, 0xF5, 0xFC, 0x83, 0x42, 0x76,
-	0x50, 0x58, 0xE6, 0xA9, 0xB9, 0x32, 0x2D, 0xA0, 0xFF, 0x3D, 0x00, 0x00,
-	0x00, 0xED, 0x0A, 0x31, 0x11, 0x61, 0x4D, 0x55, 0x2E, 0xA3, 0x9A, 0x67,
-	0xAF, 0x2C, 0x08, 0xA1, 0xC5, 0x11, 0x00, 0x00, 0x00, 0x29, 0x23, 0xA5,
-	0x76, 0xB5, 0x45, 0x23, 0x09, 0x41, 0xD8, 0xAE, 0x98, 0xD8, 0x6A, 0x2F,
-	0xCF, 0x02, 0x00, 0x00, 0x00, 0x07, 0x69, 0xBC, 0x5F, 0xAE, 0x40, 0xC8,
-	0x55, 0x84, 0xF1, 0x67, 0x8E, 0x3F, 0xF1, 0xFF, 0x5E, 0x01, 0x00, 0x00,
-	0x00, 0x12, 0xE4, 0x26, 0xFB, 0x4D, 0x4B, 0x15, 0x1F, 0x0A, 0x55, 0x72,
-	0x93, 0x70, 0x2F, 0x1D, 0x96, 0x03, 0x00, 0x00, 0x00, 0x21, 0x00, 0x00,
-	0x00, 0x2F, 0x53, 0x63, 0x72, 0x69, 0x70, 0x74, 0x2F, 0x55, 0x45, 0x34,
-	0x53, 0x61, 0x76, 0x65, 0x46, 0x49, 0x6C, 0x65, 0x2E, 0x54, 0x65, 0x73,
-	0x74, 0x53, 0x61, 0x76, 0x65, 0x46, 0x69, 0x6C, 0x65, 0x00, 0x08, 0x00,
-	0x00, 0x00, 0x75, 0x38, 0x5F, 0x74, 0x65, 0x73, 0x74, 0x00, 0x0D, 0x00,
-	0x00, 0x00, 0x42, 0x79, 0x74, 0x65, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72,
-	0x74, 0x79, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05,
-	0x00, 0x00, 0x00, 0x4E, 0x6F, 0x6E, 0x65, 0x00, 0x00, 0x7B, 0x08, 0x00,
-	0x00, 0x00, 0x69, 0x38, 0x5F, 0x74, 0x65, 0x73, 0x74, 0x00, 0x0D, 0x00,
-	0x00, 0x00, 0x49, 0x6E, 0x74, 0x38, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72,
-	0x74, 0x79, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-	0x0F, 0x0C, 0x00, 0x00, 0x00, 0x75, 0x73, 0x68, 0x6F, 0x72, 0x74, 0x5F,
-	0x74, 0x65, 0x73, 0x74, 0x00, 0x0F, 0x00, 0x00, 0x00, 0x55, 0x49, 0x6E,
-	0x74, 0x31, 0x36, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72, 0x74, 0x79, 0x00,
-	0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE7, 0x04, 0x0B,
-	0x00, 0x00, 0x00, 0x73, 0x68, 0x6F, 0x72, 0x74, 0x5F, 0x74, 0x65, 0x73,
-	0x74, 0x00, 0x0E, 0x00, 0x00, 0x00, 0x49, 0x6E, 0x74, 0x31, 0x36, 0x50,
-	0x72, 0x6F, 0x70, 0x65, 0x72, 0x74, 0x79, 0x00, 0x02, 0x00, 0x00, 0x00,
-	0x00, 0x00, 0x00, 0x00, 0x00, 0xE3, 0x30, 0x0C, 0x00, 0x00, 0x00, 0x75,
-	0x69, 0x6E, 0x74, 0x33, 0x32, 0x5F, 0x74, 0x65, 0x73, 0x74, 0x00, 0x0F,
-	0x00, 0x00, 0x00, 0x55, 0x49, 0x6E, 0x74, 0x33, 0x32, 0x50, 0x72, 0x6F,
-	0x70, 0x65, 0x72, 0x74, 0x79, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00,
-	0x00, 0x00, 0x00, 0xCB, 0x3D, 0x00, 0x00, 0x0B, 0x00, 0x00, 0x00, 0x69,
-	0x6E, 0x74, 0x33, 0x32, 0x5F, 0x74, 0x65, 0x73, 0x74, 0x00, 0x0C, 0x00,
-	0x00, 0x00, 0x49, 0x6E, 0x74, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72, 0x74,
-	0x79, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE3,
-	0x04, 0x00, 0x00, 0x0B, 0x00, 0x00, 0x00, 0x75, 0x6C, 0x6F, 0x6E, 0x67,
-	0x5F, 0x74, 0x65, 0x73, 0x74, 0x00, 0x0F, 0x00, 0x00, 0x00, 0x55, 0x49,
-	0x6E, 0x74, 0x36, 0x34, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72, 0x74, 0x79,
-	0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x78, 0x2F,
-	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0A, 0x00, 0x00, 0x00, 0x6C, 0x6F,
-	0x6E, 0x67, 0x5F, 0x74, 0x65, 0x73, 0x74, 0x00, 0x0E, 0x00, 0x00, 0x00,
-	0x49, 0x6E, 0x74, 0x36, 0x34, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72, 0x74,
-	0x79, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x09,
-	0xE1, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x00, 0x00, 0x00, 0x74,
-	0x65, 0x73, 0x74, 0x5F, 0x73, 0x74, 0x72, 0x75, 0x63, 0x74, 0x00, 0x0F,
-	0x00, 0x00, 0x00, 0x53, 0x74, 0x72, 0x75, 0x63, 0x74, 0x50, 0x72, 0x6F,
-	0x70, 0x65, 0x72, 0x74, 0x79, 0x00, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00,
-	0x00, 0x00, 0x0B, 0x00, 0x00, 0x00, 0x54, 0x65, 0x73, 0x74, 0x53, 0x74,
-	0x72, 0x75, 0x63, 0x74, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0B, 0x00,
-	0x00, 0x00, 0x74, 0x65, 0x73, 0x74, 0x5F, 0x66, 0x69, 0x65, 0x6C, 0x64,
-	0x00, 0x0F, 0x00, 0x00, 0x00, 0x55, 0x49, 0x6E, 0x74, 0x33, 0x32, 0x50,
-	0x72, 0x6F, 0x70, 0x65, 0x72, 0x74, 0x79, 0x00, 0x04, 0x00, 0x00, 0x00,
-	0x00, 0x00, 0x00, 0x00, 0x00, 0xD2, 0x04, 0x00, 0x00, 0x05, 0x00, 0x00,
-	0x00, 0x4E, 0x6F, 0x6E, 0x65, 0x00, 0x0B, 0x00, 0x00, 0x00, 0x66, 0x5F,
-	0x70, 0x72, 0x6F, 0x70, 0x65, 0x72, 0x74, 0x79, 0x00, 0x0E, 0x00, 0x00,
-	0x00, 0x46, 0x6C, 0x6F, 0x61, 0x74, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72,
-	0x74, 0x79, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-	0x0E, 0x6D, 0xFA, 0x42, 0x0B, 0x00, 0x00, 0x00, 0x64, 0x5F, 0x70, 0x72,
-	0x6F, 0x70, 0x65, 0x72, 0x74, 0x79, 0x00, 0x0F, 0x00, 0x00, 0x00, 0x44,
-	0x6F, 0x75, 0x62, 0x6C, 0x65, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72, 0x74,
-	0x79, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC1,
-	0xCA, 0xA1, 0x45, 0x76, 0xCE, 0xA0, 0x40, 0x0D, 0x00, 0x00, 0x00, 0x73,
-	0x74, 0x72, 0x5F, 0x70, 0x72, 0x6F, 0x70, 0x65, 0x72, 0x74, 0x79, 0x00,
-	0x0C, 0x00, 0x00, 0x00, 0x53, 0x74, 0x72, 0x50, 0x72, 0x6F, 0x70, 0x65,
-	0x72, 0x74, 0x79, 0x00, 0x1B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
-	0x00, 0x17, 0x00, 0x00, 0x00, 0x53, 0x6F, 0x6D, 0x65, 0x20, 0x63, 0x6F,
-	0x6E, 0x74, 0x65, 0x6E, 0x74, 0x20, 0x69, 0x6E, 0x20, 0x73, 0x74, 0x72,
-	0x69, 0x6E, 0x67, 0x00, 0x09, 0x00, 0x00, 0x00, 0x74, 0x65, 0x73, 0x74,
-	0x5F, 0x61, 0x72, 0x72, 0x00, 0x0E, 0x00, 0x00, 0x00, 0x41, 0x72, 0x72,
-	0x61, 0x79, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72, 0x74, 0x79, 0x00, 0x0A,
-	0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0D, 0x00, 0x00, 0x00, 0x42,
-	0x6F, 0x6F, 0x6C, 0x50, 0x72, 0x6F, 0x70, 0x65, 0x72, 0x74, 0x79, 0x00,
-	0x00, 0x06, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x05,
-	0x00, 0x00, 0x00, 0x4E, 0x6F, 0x6E, 0x65, 0x00, 0x00, 0x00, 0x00, 0x00
-];
+mod common;
+use common::{sample, test_file, TestFile, TestStruct, TEST_FILE};
 
 #[derive(Deserialize, Debug, PartialEq, Eq)]
 struct CustomStruct {
@@ -194,6 +38,7 @@ fn get_expected_header() -> GvasHeader {
         file_type_tag: 0x53415647, // "GVAS" in the beginning of the header
         save_game_file_version: 2,
         package_file_ue4_version: 518,
+        package_file_ue5_version: None,
         engine_version: FEngineVersion {
             major: 4,
             minor: 25,
@@ -232,6 +77,7 @@ fn header_check(parse_header: &GvasHeader) {
     assert_eq!(parse_header.file_type_tag, expected_header.file_type_tag);
     assert_eq!(parse_header.save_game_file_version, expected_header.save_game_file_version);
     assert_eq!(parse_header.package_file_ue4_version, expected_header.package_file_ue4_version);
+    assert_eq!(parse_header.package_file_ue5_version, expected_header.package_file_ue5_version);
     assert_eq!(parse_header.engine_version, expected_header.engine_version);
     assert_eq!(parse_header.custom_version_format, expected_header.custom_version_format);
     //todo: add custom versions check
@@ -278,4 +124,261 @@ fn write_header() {
 
 	let serialized = header::ser::to_vec(&parsed).expect("Failed to serialize header!");
 	assert_eq!(TEST_FILE[..cursor.position() as usize].to_vec(), serialized);
-}
\ No newline at end of file
+}
+
+#[test]
+fn known_custom_versions() {
+	let mut cursor = Cursor::new(TEST_FILE.to_vec());
+	let parsed: GvasHeader = header::de::from_bytes(&mut cursor).expect("Failed to deserialize header!");
+
+	let release = parsed.custom_version("FReleaseObjectVersion").expect("Missing FReleaseObjectVersion!");
+	assert_eq!(release.version, 30);
+	assert_eq!(release.name(), Some("FReleaseObjectVersion"));
+	assert_eq!(release.version_name(), Some("FFieldPathOwnerSerialization"));
+	assert_eq!(FReleaseObjectVersion::from_version(release.version), Some(FReleaseObjectVersion::FFieldPathOwnerSerialization));
+
+	let editor = parsed.custom_version_by_guid(&FEditorObjectVersion::GUID).expect("Missing FEditorObjectVersion!");
+	assert_eq!(FEditorObjectVersion::from_version(editor.version), Some(FEditorObjectVersion::SkeletalMeshMoveEditorSourceDataToPrivateAsset));
+
+	assert!(parsed.custom_version("FUE5ReleaseStreamObjectVersion").is_none());
+	let main_stream = custom_version::find("FUE5MainStreamObjectVersion").expect("Missing FUE5MainStreamObjectVersion!");
+	assert_eq!(main_stream.version_name(0), Some("BeforeCustomVersionWasAdded"));
+	assert_eq!(FUE5MainStreamObjectVersion::from_version(1), Some(FUE5MainStreamObjectVersion::GeometryCollectionNaniteData));
+	assert_eq!(main_stream.guid, FUE5MainStreamObjectVersion::GUID);
+}
+
+#[test]
+fn engine_presets() {
+	let mut cursor = Cursor::new(TEST_FILE.to_vec());
+	let parsed: GvasHeader = header::de::from_bytes(&mut cursor).expect("Failed to deserialize header!");
+
+	let mut preset = GvasHeader::for_engine(EngineVersion::UE4_25);
+	preset.save_game_class_name = parsed.save_game_class_name.clone();
+	assert_eq!(preset, parsed);
+
+	let preset = GvasHeader::for_engine(EngineVersion::UE5_3);
+	let serialized = header::ser::to_vec(&preset).expect("Failed to serialize header!");
+	let parsed: GvasHeader = header::de::from_bytes(&mut Cursor::new(serialized)).expect("Failed to deserialize header!");
+	assert_eq!(parsed.package_file_ue5_version, Some(1009));
+	assert_eq!(parsed, preset);
+
+	assert_eq!("4.27".parse::<EngineVersion>().ok(), Some(EngineVersion::UE4_27));
+	assert_eq!("UE5_3".parse::<EngineVersion>().ok(), Some(EngineVersion::UE5_3));
+	assert_eq!("++UE5+Release-5.0".parse::<EngineVersion>().ok(), Some(EngineVersion::UE5_0));
+	assert!("4.1".parse::<EngineVersion>().is_err());
+	assert_eq!(EngineVersion::from_change_list(18319896), Some(EngineVersion::UE4_27));
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> usize {
+	haystack.windows(needle.len()).position(|window| window == needle).expect("Pattern not found!")
+}
+
+fn read_body(bytes: Vec<u8>) -> serde_gvas::Result<UnrealFile> {
+	read_body_with_options(bytes, DeserializerOptions::default())
+}
+
+fn read_body_with_options(bytes: Vec<u8>, options: DeserializerOptions) -> serde_gvas::Result<UnrealFile> {
+	let mut cursor = Cursor::new(bytes);
+	let _: GvasHeader = header::de::from_bytes(&mut cursor).expect("Failed to parse file header!");
+	serde_gvas::from_bytes_with_options(&mut cursor, options)
+}
+
+#[test]
+fn error_positions() {
+	let mut bytes = TEST_FILE.to_vec();
+	let field_start = find_bytes(&bytes, b"\x0B\x00\x00\x00test_field");
+	let type_start = find_bytes(&bytes[field_start..], b"UInt32Property") + field_start;
+	bytes[type_start..type_start + 6].copy_from_slice(b"UInt99");
+
+	let err = read_body(bytes).err().expect("Parsing should fail!");
+	assert!(matches!(err.code(), ErrorCode::UnknownPropertyType(t) if &**t == "UInt99Property"));
+	assert_eq!(err.path(), Some("test_struct.test_field"));
+	assert_eq!(err.offset(), Some(field_start as u64));
+
+	let array_start = find_bytes(&TEST_FILE, b"\x01\x01\x01\x01\x01\x01");
+	let err = read_body(TEST_FILE[..array_start + 3].to_vec()).err().expect("Parsing should fail!");
+	assert!(matches!(err.code(), ErrorCode::UnexpectedEof));
+	assert_eq!(err.path(), Some("test_arr[3]"));
+	assert_eq!(err.to_string(), format!("Unexpected end of input at offset {} in test_arr[3]", array_start + 3));
+}
+
+#[test]
+fn header_error_positions() {
+	let header: GvasHeader = header::de::from_bytes(&mut Cursor::new(TEST_FILE.to_vec())).expect("Failed to parse file header!");
+	assert_eq!(header.package_file_ue5_version, None);
+	let class_start = find_bytes(&TEST_FILE, header.save_game_class_name.as_bytes()) - 4;
+
+	let err = header::de::from_bytes::<GvasHeader>(&mut Cursor::new(TEST_FILE[..class_start + 8].to_vec())).expect_err("Parsing should fail!");
+	assert!(matches!(err.code(), ErrorCode::UnexpectedEof));
+	assert_eq!(err.path(), Some("save_game_class_name"));
+	assert_eq!(err.offset(), Some(class_start as u64));
+}
+
+fn limit_of(err: serde_gvas::Error) -> &'static str {
+	match err.code() {
+		ErrorCode::LimitExceeded { limit, .. } => limit,
+		code => panic!("Expected a limit error, got {}", code)
+	}
+}
+
+#[test]
+fn resource_limits() {
+	assert!(read_body_with_options(TEST_FILE.to_vec(), DeserializerOptions::unlimited()).is_ok());
+
+	let err = read_body_with_options(TEST_FILE.to_vec(), DeserializerOptions { max_string_length: 16, ..Default::default() }).err().unwrap();
+	assert_eq!(limit_of(err), "string length");
+
+	let err = read_body_with_options(TEST_FILE.to_vec(), DeserializerOptions { max_array_length: 5, ..Default::default() }).err().unwrap();
+	assert_eq!(limit_of(err), "array length");
+
+	let err = read_body_with_options(TEST_FILE.to_vec(), DeserializerOptions { max_struct_properties: 12, ..Default::default() }).err().unwrap();
+	assert_eq!(limit_of(err), "struct properties");
+
+	// the sample holds a map of two quests
+	let bytes = sample().to_vec().expect("Failed to write save!");
+	let err = Save::from_bytes_with_options(bytes, DeserializerOptions { max_map_length: 1, ..Default::default() }).err().unwrap();
+	assert_eq!(err.path(), Some("PlayerData.Quests"));
+	assert_eq!(limit_of(err), "map length");
+
+	let err = read_body_with_options(TEST_FILE.to_vec(), DeserializerOptions { max_depth: 0, ..Default::default() }).err().unwrap();
+	assert_eq!(err.path(), Some("test_struct"));
+	assert_eq!(limit_of(err), "nesting depth");
+
+	let err = read_body_with_options(TEST_FILE.to_vec(), DeserializerOptions { max_total_size: 64, ..Default::default() }).err().unwrap();
+	assert_eq!(limit_of(err), "total size");
+
+	// a huge declared string length is rejected before allocating
+	let mut bytes = TEST_FILE.to_vec();
+	let string_start = find_bytes(&bytes, b"\x17\x00\x00\x00Some content");
+	bytes[string_start..string_start + 4].copy_from_slice(&i32::MAX.to_le_bytes());
+	let err = read_body(bytes).err().unwrap();
+	assert_eq!(err.path(), Some("str_property"));
+	assert_eq!(limit_of(err), "string length");
+}
+
+#[test]
+fn value_size_validation() {
+	// pad test_struct with 4 bytes the decoder doesn't know about and declare them in its size
+	let mut bytes = TEST_FILE.to_vec();
+	let size_start = find_bytes(&bytes, b"\x0C\x00\x00\x00test_struct\x00\x0F\x00\x00\x00StructProperty\x00") + 35;
+	let declared = i64::from_le_bytes(bytes[size_start..size_start + 8].try_into().unwrap());
+	bytes[size_start..size_start + 8].copy_from_slice(&(declared + 4).to_le_bytes());
+	let end = find_bytes(&bytes[size_start..], b"\x05\x00\x00\x00None\x00") + size_start + 9;
+	bytes.splice(end..end, [0xAA; 4]);
+
+	let err = read_body(bytes.clone()).err().expect("Parsing should fail!");
+	assert!(matches!(err.code(), ErrorCode::SizeMismatch { expected, actual } if *expected == declared + 4 && *actual == declared));
+	assert_eq!(err.path(), Some("test_struct"));
+
+	let mut cursor = Cursor::new(bytes);
+	let _: GvasHeader = header::de::from_bytes(&mut cursor).expect("Failed to parse file header!");
+	let options = DeserializerOptions { lenient: true, ..Default::default() };
+	let (parse_file, diagnostics): (UnrealFile, _) = serde_gvas::from_bytes_with_diagnostics(&mut cursor, options).expect("Failed to parse file!");
+	assert_eq!(parse_file.test_struct, get_expected_file().test_struct);
+	assert_eq!(parse_file.test_arr, get_expected_file().test_arr);
+	assert_eq!(diagnostics.len(), 1);
+	assert_eq!(diagnostics[0].path, "test_struct");
+	assert!(matches!(diagnostics[0].code, ErrorCode::SizeMismatch { .. }));
+}
+
+#[test]
+fn write_body() {
+	let mut cursor = Cursor::new(TEST_FILE.to_vec());
+	let _: GvasHeader = header::de::from_bytes(&mut cursor).expect("Failed to parse file header!");
+	let body = &TEST_FILE[cursor.position() as usize..];
+
+	let written = serde_gvas::to_vec(&test_file()).expect("Failed to serialize file!");
+	assert_eq!(written, body);
+	let read: TestFile = serde_gvas::from_bytes(&mut Cursor::new(written)).expect("Failed to parse file!");
+	assert_eq!(read, test_file());
+
+	let mut file = test_file();
+	file.test_arr.clear();
+	let err = serde_gvas::to_vec(&file).expect_err("Serializing should fail!");
+	assert!(matches!(err.code(), ErrorCode::Data(_)));
+}
+
+#[test]
+fn write_empty_array() {
+	#[derive(Serialize)]
+	struct Flags {
+		#[serde(serialize_with = "bool_elements")]
+		flags: Vec<bool>
+	}
+
+	fn bool_elements<S: serde::Serializer>(flags: &Vec<bool>, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_newtype_struct("$gvas:property=BoolProperty", flags)
+	}
+
+	let bytes = serde_gvas::to_vec(&Flags { flags: Vec::new() }).expect("Failed to serialize!");
+	let properties = serde_gvas::value::from_bytes(&mut Cursor::new(bytes)).expect("Failed to parse properties!");
+	assert_eq!(properties[0].value, serde_gvas::value::Value::Array {
+		element_type: String::from("BoolProperty"),
+		value: serde_gvas::value::ArrayValue::Values(Vec::new())
+	});
+}
+
+#[test]
+fn write_to_stream() {
+	// only implements Write, no Seek
+	struct Stream(Vec<u8>);
+	impl std::io::Write for Stream {
+		fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+			self.0.write(buf)
+		}
+
+		fn flush(&mut self) -> std::io::Result<()> {
+			Ok(())
+		}
+	}
+
+	let header: GvasHeader = header::de::from_bytes(&mut Cursor::new(TEST_FILE.to_vec())).expect("Failed to parse file header!");
+	let mut stream = Stream(Vec::new());
+	header::ser::to_writer(&mut stream, &header).expect("Failed to serialize header!");
+	serde_gvas::to_writer(&mut stream, &test_file()).expect("Failed to serialize file!");
+	assert_eq!(stream.0, TEST_FILE);
+}
+
+#[test]
+fn write_struct_array() {
+	#[derive(Deserialize, Serialize, Debug, PartialEq)]
+	struct Inventory {
+		items: Vec<TestStruct>,
+		names: Vec<String>
+	}
+
+	let inventory = Inventory {
+		items: vec![TestStruct { test_field: 1 }, TestStruct { test_field: 2 }],
+		names: vec![String::from("first"), String::from("second")]
+	};
+	let bytes = serde_gvas::to_vec(&inventory).expect("Failed to serialize!");
+	let read: Inventory = serde_gvas::from_bytes(&mut Cursor::new(bytes)).expect("Failed to deserialize!");
+	assert_eq!(read, inventory);
+}
+
+#[test]
+fn write_options() {
+	#[derive(Deserialize, Serialize, Debug, PartialEq)]
+	struct Reward {
+		name: Option<String>,
+		count: i32
+	}
+
+	#[derive(Deserialize, Serialize, Debug, PartialEq)]
+	struct Rewards {
+		rewards: Vec<Reward>,
+		bonus: Option<i32>,
+		penalty: Option<i32>
+	}
+
+	let rewards = Rewards {
+		rewards: vec![Reward { name: Some(String::from("Sword")), count: 1 }, Reward { name: None, count: 2 }],
+		bonus: Some(5),
+		penalty: None
+	};
+	let bytes = serde_gvas::to_vec(&rewards).expect("Failed to serialize!");
+	let properties = serde_gvas::value::from_bytes(&mut Cursor::new(bytes.clone())).expect("Failed to parse properties!");
+	assert_eq!(properties.iter().map(|property| property.name.as_str()).collect::<Vec<_>>(), ["rewards", "bonus"]);
+	let read: Rewards = serde_gvas::from_bytes(&mut Cursor::new(bytes)).expect("Failed to deserialize!");
+	assert_eq!(read, rewards);
+}
@@ -0,0 +1,99 @@
+use serde_gvas::{text, ErrorCode};
+use serde_gvas::types::FGuid;
+use serde_gvas::value::{ArrayValue, ByteValue, Property, Save, StructValue, Value};
+
+mod common;
+use common::{property, TEST_FILE};
+
+#[test]
+fn text_round_trip() {
+	let save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	let printed = text::to_string(&save);
+	assert!(printed.contains("\n    u8_test: ByteProperty = 123\n"), "{}", printed);
+	assert!(printed.contains("\n    test_struct: StructProperty<TestStruct>\n        test_field: UInt32Property = 1234\n"), "{}", printed);
+	assert!(printed.contains("\n    str_property: StrProperty = \"Some content in string\"\n"), "{}", printed);
+
+	let parsed = text::from_str(&printed).expect("Failed to parse text!");
+	assert_eq!(parsed, save);
+	assert_eq!(parsed.to_vec().expect("Failed to write file!"), TEST_FILE);
+}
+
+#[test]
+fn text_edge_cases() {
+	let mut save = Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	let guid = FGuid::new(1, 2, 3, 0xDEADBEEF);
+	save.properties = vec![
+		Property { name: String::from("odd name: \"quoted\""), array_index: 2, guid: Some(guid), value: Value::Str(String::from("line\nbreak\t\u{1}ä")) },
+		property("Difficulty", Value::Byte { enum_name: String::from("EDifficulty"), value: ByteValue::Label(String::from("EDifficulty::Hard")) }),
+		property("Float", Value::Float(-0.0)),
+		property("Time", Value::Struct { struct_name: String::from("DateTime"), guid, value: StructValue::DateTime(637_000_000_000) }),
+		property("Empty", Value::Struct { struct_name: String::from("Empty"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Properties(Vec::new()) }),
+		property("Bytes", Value::Array { element_type: String::from("ByteProperty"), value: ArrayValue::Bytes(vec![0, 10, 255]) }),
+		property("NoBytes", Value::Array { element_type: String::from("ByteProperty"), value: ArrayValue::Bytes(Vec::new()) }),
+		property("Labels", Value::Array { element_type: String::from("EnumProperty"), value: ArrayValue::Values(vec![Value::Enum { enum_name: String::from("None"), value: String::from("A::B") }]) }),
+		property("Points", Value::Array { element_type: String::from("StructProperty"), value: ArrayValue::Structs {
+			name: String::from("Inner"), array_index: 0, property_guid: None, struct_name: String::from("Point"), guid: FGuid::new(0, 0, 0, 0),
+			elements: vec![StructValue::Properties(vec![property("X", Value::Int(1))]), StructValue::Properties(Vec::new())]
+		} }),
+		property("Map", Value::Unknown { type_name: String::from("MapProperty"), tag: vec![1, 2], data: Vec::new() }),
+		property("Icons", Value::Array { element_type: String::from("SoftObjectProperty"), value: ArrayValue::Values(vec![
+			Value::SoftObject([String::from("/Game/Icons/Sword.Sword"), String::new()])
+		]) }),
+		property("Items", Value::Array { element_type: String::from("StructProperty"), value: ArrayValue::Structs {
+			name: String::from("Items"), array_index: 2, property_guid: None, struct_name: String::from("Item"), guid: FGuid::new(0, 0, 0, 0), elements: Vec::new()
+		} }),
+		property("Tagged", Value::Array { element_type: String::from("StructProperty"), value: ArrayValue::Structs {
+			name: String::from("Tagged"), array_index: 0, property_guid: Some(guid), struct_name: String::from("Item"), guid: FGuid::new(0, 0, 0, 0), elements: Vec::new()
+		} }),
+		property("NaNs", Value::Array { element_type: String::from("FloatProperty"), value: ArrayValue::Values(vec![
+			Value::Float(f32::NAN), Value::Float(f32::from_bits(0x7FC00001)), Value::Float(f32::NEG_INFINITY)
+		]) }),
+		property("Seen", Value::Set { element_type: String::from("NameProperty"), removed: vec![Value::Name(String::from("Old"))], elements: vec![
+			Value::Name(String::from("Cave")), Value::Name(String::from("Town"))
+		] }),
+		property("Owners", Value::Map { key_type: String::from("StructProperty"), value_type: String::from("StructProperty"), removed: Vec::new(), entries: vec![
+			(Value::Struct { struct_name: String::from("Guid"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Guid(guid) },
+				Value::Struct { struct_name: String::new(), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Properties(vec![property("Level", Value::Int(3))]) }),
+			(Value::Struct { struct_name: String::new(), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Properties(Vec::new()) },
+				Value::Struct { struct_name: String::from("Guid"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Guid(guid) })
+		] }),
+		property("Counts", Value::Map { key_type: String::from("StrProperty"), value_type: String::from("IntProperty"), removed: vec![Value::Str(String::from("b"))], entries: vec![
+			(Value::Str(String::from("a = 1")), Value::Int(5))
+		] })
+	];
+
+	let printed = text::to_string(&save);
+	assert!(printed.contains("\"odd name: \\\"quoted\\\"\"[2]: StrProperty @00000001-00000002-00000003-DEADBEEF = \"line\\nbreak\\t\\u{1}ä\""), "{}", printed);
+	assert!(printed.contains("Points: ArrayProperty<StructProperty<Point>, Inner>\n        -\n            X: IntProperty = 1\n        -\n"), "{}", printed);
+	assert!(printed.contains("Map: Unknown<MapProperty> = [01 02] []"), "{}", printed);
+	assert!(printed.contains("Items: ArrayProperty<StructProperty<Item>, Items, 2>\n"), "{}", printed);
+	assert!(printed.contains("Tagged: ArrayProperty<StructProperty<Item>, Tagged, 0, 00000001-00000002-00000003-DEADBEEF>\n"), "{}", printed);
+	assert!(printed.contains("NaNs: ArrayProperty<FloatProperty>\n        - NaN\n        - NaN:7FC00001\n        - -inf\n"), "{}", printed);
+	assert!(printed.contains("Seen: SetProperty<NameProperty>\n        ~ \"Old\"\n        - \"Cave\"\n"), "{}", printed);
+	assert!(printed.contains("Owners: MapProperty<StructProperty, StructProperty>\n        -\n            key = 00000001-00000002-00000003-DEADBEEF\n            value:\n                Level: IntProperty = 3\n        -\n            key:\n            value = "), "{}", printed);
+	assert!(printed.contains("Counts: MapProperty<StrProperty, IntProperty>\n        ~ \"b\"\n        -\n            key = \"a = 1\"\n            value = 5\n"), "{}", printed);
+	assert!(printed.contains("Icons: ArrayProperty<SoftObjectProperty>\n        - \"/Game/Icons/Sword.Sword\" \"\"\n"), "{}", printed);
+
+	let parsed = text::from_str(&printed).expect("Failed to parse text!");
+	assert_eq!(parsed.to_vec().unwrap(), save.to_vec().unwrap());
+	// NaN isn't equal to itself
+	assert_eq!(format!("{:?}", parsed), format!("{:?}", save));
+
+	// comments and blank lines are ignored
+	let commented = printed.replace("properties:\n", "# edited by hand\nproperties:\n\n");
+	assert_eq!(text::from_str(&commented).expect("Failed to parse text!").to_vec().unwrap(), save.to_vec().unwrap());
+}
+
+#[test]
+fn text_errors() {
+	let printed = text::to_string(&Save::from_bytes(TEST_FILE.to_vec()).unwrap());
+	let line = printed.lines().position(|line| line.starts_with("    int32_test")).unwrap() + 1;
+
+	for (from, to) in [("IntProperty = 1251", "IntProperty = lots"), ("IntProperty = 1251", "IntProperty"), ("int32_test: IntProperty", "int32_test: FancyProperty")] {
+		let err = text::from_str(&printed.replace(from, to)).expect_err("Parsing should fail!");
+		assert!(matches!(err.code(), ErrorCode::Data(_)));
+		assert!(err.to_string().contains(&format!("Line {}", line)), "{}", err);
+	}
+	text::from_str(&printed.replace("\n    i8_test", "\n      i8_test")).expect_err("Parsing should fail!");
+	text::from_str("properties:\n").expect_err("Parsing should fail!");
+}
@@ -0,0 +1,74 @@
+use std::io::Cursor;
+
+use serde_gvas::transform::{Pipeline, SaveTransform, SkipPrefix, Xor};
+#[cfg(feature = "encryption")]
+use serde_gvas::transform::{Aes, AesMode, AesPadding};
+use serde_gvas::{header, value};
+use serde_gvas::types::GvasHeader;
+
+mod common;
+use common::TEST_FILE;
+
+#[cfg(feature = "encryption")]
+fn hex(s: &str) -> Vec<u8> {
+	(0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+#[test]
+#[cfg(feature = "encryption")]
+fn aes_vectors() {
+	// FIPS-197 appendix C
+	let plaintext = hex("00112233445566778899aabbccddeeff");
+	for (key, expected) in [
+		("000102030405060708090a0b0c0d0e0f", "69c4e0d86a7b0430d8cdb78070b4c55a"),
+		("000102030405060708090a0b0c0d0e0f1011121314151617", "dda97ca4864cdfe06eaf70a0ec0d7191"),
+		("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", "8ea2b7ca516745bfeafc49904b496089")
+	] {
+		let aes = Aes::new(&hex(key), AesMode::Ecb, AesPadding::None).expect("Failed to create cipher!");
+		assert_eq!(aes.encode(&plaintext).unwrap(), hex(expected));
+		assert_eq!(aes.decode(&hex(expected)).unwrap(), plaintext);
+	}
+
+	// SP 800-38A F.2.1
+	let iv = hex("000102030405060708090a0b0c0d0e0f").try_into().unwrap();
+	let aes = Aes::new(&hex("2b7e151628aed2a6abf7158809cf4f3c"), AesMode::Cbc { iv }, AesPadding::None).unwrap();
+	let plaintext = hex("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51");
+	let ciphertext = hex("7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2");
+	assert_eq!(aes.encode(&plaintext).unwrap(), ciphertext);
+	assert_eq!(aes.decode(&ciphertext).unwrap(), plaintext);
+
+	assert!(Aes::new(&[0; 15], AesMode::Ecb, AesPadding::None).is_err());
+	assert!(aes.encode(&plaintext[1..]).is_err());
+	assert!(aes.decode(&ciphertext[1..]).is_err());
+
+	let pipeline = Pipeline::new()
+		.then(SkipPrefix::with_bytes(*b"SAVE"))
+		.then(Aes::new(b"0123456789abcdef", AesMode::Cbc { iv: [7; 16] }, AesPadding::Pkcs7).unwrap());
+	let encoded = pipeline.encode(&TEST_FILE).expect("Failed to encode!");
+	assert_eq!(encoded.len(), 4 + TEST_FILE.len().div_ceil(16) * 16);
+	assert_eq!(pipeline.decode(&encoded).expect("Failed to decode!"), TEST_FILE);
+
+	// a wrong key shows up as bad padding
+	let wrong = Aes::new(b"fedcba9876543210", AesMode::Ecb, AesPadding::Pkcs7).unwrap();
+	let encrypted = Aes::new(b"0123456789abcdef", AesMode::Ecb, AesPadding::Pkcs7).unwrap().encode(&TEST_FILE).unwrap();
+	assert!(wrong.decode(&encrypted).map(|d| d != TEST_FILE).unwrap_or(true));
+}
+
+#[test]
+fn pipeline_round_trip() {
+	let pipeline = Pipeline::new()
+		.then(SkipPrefix::with_bytes(*b"SAVE"))
+		.then(Xor::new(*b"key"));
+
+	let encoded = pipeline.encode(&TEST_FILE).expect("Failed to encode!");
+	assert_eq!(&encoded[..4], b"SAVE");
+	assert_eq!(encoded.len(), 4 + TEST_FILE.len());
+
+	let decoded = pipeline.decode(&encoded).expect("Failed to decode!");
+	assert_eq!(decoded, TEST_FILE);
+	let mut cursor = Cursor::new(decoded);
+	let _: GvasHeader = header::de::from_bytes(&mut cursor).expect("Failed to parse file header!");
+	assert_eq!(value::from_bytes(&mut cursor).expect("Failed to parse file!").len(), 13);
+
+	assert!(SkipPrefix::new(8).decode(&[0; 4]).is_err());
+}
@@ -0,0 +1,192 @@
+use std::io::Cursor;
+
+use serde::Deserialize;
+use serde_gvas::{DeserializerOptions, ErrorCode};
+use serde_gvas::structs::{Item, StructRegistry};
+use serde_gvas::types::FGuid;
+use serde_gvas::value::{self, ArrayValue, Property, StructValue, Value};
+
+mod common;
+use common::{body, property, sample, TEST_FILE};
+
+fn find<'a>(properties: &'a [Property], name: &str) -> &'a Value {
+	&properties.iter().find(|property| property.name == name).expect("Property not found!").value
+}
+
+fn corrupted_file() -> Vec<u8> {
+	let mut bytes = TEST_FILE.to_vec();
+	let field_start = bytes.windows(14).position(|window| window == b"\x0B\x00\x00\x00test_field").unwrap();
+	let type_start = bytes[field_start..].windows(14).position(|window| window == b"UInt32Property").unwrap() + field_start;
+	bytes[type_start..type_start + 6].copy_from_slice(b"UInt99");
+	bytes
+}
+
+#[test]
+fn read_tree() {
+	let properties = value::from_bytes(&mut body(TEST_FILE.to_vec())).expect("Failed to parse file!");
+	assert_eq!(properties.len(), 13);
+	assert_eq!(find(&properties, "int32_test"), &Value::Int(1251));
+	assert_eq!(find(&properties, "d_property"), &Value::Double(2151.231));
+	assert_eq!(find(&properties, "str_property"), &Value::Str(String::from("Some content in string")));
+
+	match find(&properties, "test_struct") {
+		Value::Struct { value: StructValue::Properties(fields), .. } => assert_eq!(find(fields, "test_field"), &Value::UInt32(1234)),
+		value => panic!("Unexpected value {:?}", value)
+	}
+	match find(&properties, "test_arr") {
+		Value::Array { element_type, value: ArrayValue::Values(elements) } => {
+			assert_eq!(element_type, "BoolProperty");
+			assert_eq!(elements, &vec![Value::Bool(true); 6]);
+		},
+		value => panic!("Unexpected value {:?}", value)
+	}
+}
+
+#[test]
+fn recover_tree() {
+	let err = value::from_bytes(&mut body(corrupted_file())).expect_err("Parsing should fail!");
+	assert!(matches!(err.code(), ErrorCode::UnknownPropertyType(_)));
+
+	let options = DeserializerOptions { recover: true, ..Default::default() };
+	let (properties, diagnostics) = value::from_bytes_with_diagnostics(&mut body(corrupted_file()), options).expect("Failed to parse file!");
+	assert_eq!(properties.len(), 13);
+	assert_eq!(diagnostics.len(), 1);
+	assert_eq!(diagnostics[0].path, "test_struct.test_field");
+
+	match find(&properties, "test_struct") {
+		Value::Struct { value: StructValue::Properties(fields), .. } => {
+			assert_eq!(find(fields, "test_field"), &Value::Unknown { type_name: String::from("UInt99Property"), tag: Vec::new(), data: 1234u32.to_le_bytes().to_vec() });
+		},
+		value => panic!("Unexpected value {:?}", value)
+	}
+	assert_eq!(find(&properties, "str_property"), &Value::Str(String::from("Some content in string")));
+}
+
+#[derive(Deserialize)]
+struct Partial {
+	str_property: String,
+	test_arr: Vec<bool>
+}
+
+#[test]
+fn recover_serde() {
+	let options = DeserializerOptions { recover: true, ..Default::default() };
+	let (partial, diagnostics): (Partial, _) = serde_gvas::from_bytes_with_diagnostics(&mut body(corrupted_file()), options).expect("Failed to parse file!");
+	assert_eq!(partial.str_property, "Some content in string");
+	assert_eq!(partial.test_arr, [true; 6]);
+	assert_eq!(diagnostics.len(), 1);
+	assert_eq!(diagnostics[0].path, "test_struct.test_field");
+	assert!(matches!(diagnostics[0].code, ErrorCode::UnknownPropertyType(_)));
+}
+
+#[test]
+fn recover_serde_struct() {
+	// the struct can't be read past its first field name, so it is skipped as a whole
+	let mut bytes = TEST_FILE.to_vec();
+	let field_start = bytes.windows(14).position(|window| window == b"\x0B\x00\x00\x00test_field").unwrap();
+	bytes[field_start..field_start + 4].copy_from_slice(&i32::MAX.to_le_bytes());
+	assert!(serde_gvas::from_bytes::<Partial>(&mut body(bytes.clone())).is_err());
+
+	let options = DeserializerOptions { recover: true, ..Default::default() };
+	let (partial, diagnostics): (Partial, _) = serde_gvas::from_bytes_with_diagnostics(&mut body(bytes), options).expect("Failed to parse file!");
+	assert_eq!(partial.str_property, "Some content in string");
+	assert_eq!(partial.test_arr, [true; 6]);
+	assert_eq!(diagnostics.len(), 1);
+	assert!(diagnostics[0].path.starts_with("test_struct"));
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct SampleItem {
+	#[serde(rename = "ItemId")]
+	item_id: String,
+	#[serde(rename = "Count")]
+	count: i32
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct SamplePlayer {
+	#[serde(rename = "Inventory")]
+	inventory: Vec<SampleItem>,
+	#[serde(rename = "Checksum")]
+	checksum: Vec<u8>,
+	#[serde(rename = "Saved")]
+	saved: u64
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct TestStruct {
+	test_field: u32
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct SampleFile {
+	test_struct: TestStruct,
+	#[serde(rename = "PlayerData")]
+	player_data: SamplePlayer
+}
+
+#[test]
+fn recover_serde_nested() {
+	// structs and arrays read into the value tree when recovering come out the same
+	let bytes = sample().to_vec().expect("Failed to write save!");
+	let read: SampleFile = serde_gvas::from_bytes(&mut body(bytes.clone())).expect("Failed to parse file!");
+	let options = DeserializerOptions { recover: true, ..Default::default() };
+	let (recovered, diagnostics): (SampleFile, _) = serde_gvas::from_bytes_with_diagnostics(&mut body(bytes), options).expect("Failed to parse file!");
+	assert_eq!(recovered, read);
+	assert_eq!(recovered.player_data.inventory[1], SampleItem { item_id: String::from("Sword"), count: 1 });
+	assert_eq!(recovered.player_data.checksum, [1, 2, 3]);
+	assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn write_tree() {
+	let save = value::Save::from_bytes(TEST_FILE.to_vec()).expect("Failed to parse file!");
+	assert_eq!(save.properties.len(), 13);
+	assert_eq!(save.to_vec().expect("Failed to write file!"), TEST_FILE);
+
+	// unknown properties are written back as they were read
+	let options = DeserializerOptions { recover: true, ..Default::default() };
+	let save = value::Save::from_bytes_with_options(corrupted_file(), options).expect("Failed to parse file!");
+	assert_eq!(save.to_vec().expect("Failed to write file!"), corrupted_file());
+}
+
+#[test]
+fn sets_and_maps() {
+	let guid = FGuid::new(1, 2, 3, 4);
+	let properties = vec![
+		property("Counts", Value::Map { key_type: String::from("StrProperty"), value_type: String::from("IntProperty"), removed: Vec::new(), entries: vec![
+			(Value::Str(String::from("a")), Value::Int(5))
+		] }),
+		property("Owners", Value::Map { key_type: String::from("StructProperty"), value_type: String::from("StructProperty"), removed: Vec::new(), entries: vec![
+			(Value::Struct { struct_name: String::from("Guid"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Guid(guid) },
+				Value::Struct { struct_name: String::new(), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Properties(vec![property("Level", Value::Int(3))]) })
+		] }),
+		property("Seen", Value::Set { element_type: String::from("NameProperty"), removed: vec![Value::Name(String::from("Old"))], elements: vec![
+			Value::Name(String::from("Cave")), Value::Name(String::from("Town"))
+		] }),
+		property("Keys", Value::Set { element_type: String::from("StructProperty"), removed: Vec::new(), elements: vec![
+			Value::Struct { struct_name: String::from("Guid"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Guid(guid) },
+			Value::Struct { struct_name: String::from("Guid"), guid: FGuid::new(0, 0, 0, 0), value: StructValue::Guid(FGuid::new(5, 6, 7, 8)) }
+		] }),
+		property("Icons", Value::Array { element_type: String::from("SoftObjectProperty"), value: ArrayValue::Values(vec![
+			Value::SoftObject([String::from("/Game/Icons/Sword.Sword"), String::new()])
+		]) }),
+		property("Items", Value::Array { element_type: String::from("StructProperty"), value: ArrayValue::Structs {
+			name: String::from("Items"), array_index: 2, property_guid: Some(guid), struct_name: String::from("Item"), guid: FGuid::new(0, 0, 0, 0), elements: vec![
+				StructValue::Properties(vec![property("Count", Value::Int(1))])
+			]
+		} })
+	];
+
+	let bytes = value::to_vec(&properties).expect("Failed to write properties!");
+	// no removed entries, one entry "a" => 5
+	let counts = [0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, b'a', 0, 5, 0, 0, 0];
+	assert!(bytes.windows(counts.len()).any(|window| window == counts));
+	// a guid key only reads as one when its map says so, the guids of a set fill all of it
+	assert!(value::from_bytes(&mut Cursor::new(bytes.clone())).is_err());
+	let mut structs = StructRegistry::default();
+	structs.register_item("Owners", Item::Key, "Guid");
+	let options = DeserializerOptions { structs, ..Default::default() };
+	let (read, _) = value::from_bytes_with_diagnostics(&mut Cursor::new(bytes), options).expect("Failed to read properties!");
+	assert_eq!(read, properties);
+}
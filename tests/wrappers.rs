@@ -0,0 +1,98 @@
+use std::io::Cursor;
+
+use serde::{Deserialize, Serialize};
+use serde_gvas::struct_name;
+use serde_gvas::types::{ByteArray, EnumByte, FName, ObjectPath, SoftObjectPath, Struct};
+use serde_gvas::value::{self, ArrayValue, ByteValue, Property, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename = "EMood")]
+enum Mood {
+	#[serde(rename = "EMood::Calm")]
+	Calm,
+	#[serde(rename = "EMood::Happy")]
+	Happy
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Vector {
+	x: f32,
+	y: f32
+}
+
+struct_name!(SpawnPoint = "SpawnPoint");
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SaveData {
+	level: FName,
+	tags: Vec<FName>,
+	owner: ObjectPath,
+	icon: SoftObjectPath,
+	mood: EnumByte<Mood>,
+	thumbnail: ByteArray,
+	spawn: Struct<SpawnPoint, Vector>
+}
+
+fn sample() -> SaveData {
+	SaveData {
+		level: FName(String::from("Level_01")),
+		tags: vec![FName(String::from("Brave"))],
+		owner: ObjectPath(String::from("/Game/Maps/Level_01.Level_01:PersistentLevel.Player_C_0")),
+		icon: SoftObjectPath { asset_path: String::from("/Game/UI/Icons/Sword.Sword"), sub_path: String::new() },
+		mood: EnumByte(Mood::Happy),
+		thumbnail: ByteArray(vec![0x89, 0x50, 0x4E, 0x47]),
+		spawn: Struct::new(Vector { x: 1.5, y: -2.0 })
+	}
+}
+
+fn find<'a>(properties: &'a [Property], name: &str) -> &'a Value {
+	&properties.iter().find(|property| property.name == name).expect("Property not found!").value
+}
+
+#[test]
+fn wrapper_round_trip() {
+	let bytes = serde_gvas::to_vec(&sample()).expect("Failed to serialize!");
+	let properties = value::from_bytes(&mut Cursor::new(bytes.clone())).expect("Failed to parse properties!");
+
+	assert_eq!(find(&properties, "level"), &Value::Name(String::from("Level_01")));
+	assert_eq!(find(&properties, "tags"), &Value::Array { element_type: String::from("NameProperty"), value: ArrayValue::Values(vec![Value::Name(String::from("Brave"))]) });
+	assert!(matches!(find(&properties, "owner"), Value::Object(_)));
+	assert_eq!(find(&properties, "icon"), &Value::SoftObject([String::from("/Game/UI/Icons/Sword.Sword"), String::new()]));
+	assert_eq!(find(&properties, "mood"), &Value::Byte { enum_name: String::from("EMood"), value: ByteValue::Label(String::from("EMood::Happy")) });
+	assert_eq!(find(&properties, "thumbnail"), &Value::Array { element_type: String::from("ByteProperty"), value: ArrayValue::Bytes(vec![0x89, 0x50, 0x4E, 0x47]) });
+	assert!(matches!(find(&properties, "spawn"), Value::Struct { struct_name, .. } if struct_name == "SpawnPoint"));
+
+	let back: SaveData = serde_gvas::from_bytes(&mut Cursor::new(bytes)).expect("Failed to deserialize!");
+	assert_eq!(back, sample());
+}
+
+#[derive(Serialize)]
+struct Plain {
+	level: String,
+	mood: u8
+}
+
+#[derive(Debug, Deserialize)]
+struct Pinned {
+	#[serde(rename = "level")]
+	_level: FName
+}
+
+#[test]
+fn wrapper_checks() {
+	let bytes = serde_gvas::to_vec(&Plain { level: String::from("Level_01"), mood: 1 }).expect("Failed to serialize!");
+
+	let err = serde_gvas::from_bytes::<Pinned>(&mut Cursor::new(bytes.clone())).expect_err("Deserializing should fail!");
+	assert!(err.to_string().contains("Expected NameProperty but found StrProperty"), "{}", err);
+	assert_eq!(err.path(), Some("level"));
+
+	// a plain byte is the index of the value
+	#[derive(Deserialize)]
+	struct Indexed {
+		#[serde(rename = "level")]
+		_level: String,
+		mood: EnumByte<Mood>
+	}
+	let indexed: Indexed = serde_gvas::from_bytes(&mut Cursor::new(bytes)).expect("Failed to deserialize!");
+	assert_eq!(indexed.mood, EnumByte(Mood::Happy));
+}